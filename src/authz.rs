@@ -0,0 +1,64 @@
+//! Minimal protobuf encoding for the authz messages the contract sends as
+//! `CosmosMsg::Stargate`. Only the fields we set are written; there is no
+//! decoder. Field numbers follow cosmos-sdk `authz/v1beta1/tx.proto` and
+//! `bank/v1beta1/tx.proto`.
+
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg};
+
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Length-delimited field (wire type 2): strings, bytes and embedded messages.
+fn encode_bytes_field(field: u32, bytes: &[u8], buf: &mut Vec<u8>) {
+    encode_varint(((field as u64) << 3) | 2, buf);
+    encode_varint(bytes.len() as u64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn encode_coin(coin: &Coin) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_bytes_field(1, coin.denom.as_bytes(), &mut buf);
+    encode_bytes_field(2, coin.amount.to_string().as_bytes(), &mut buf);
+    buf
+}
+
+/// `cosmos.bank.v1beta1.MsgSend`
+pub fn encode_msg_send(from: &Addr, to: &Addr, amount: &[Coin]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_bytes_field(1, from.as_bytes(), &mut buf);
+    encode_bytes_field(2, to.as_bytes(), &mut buf);
+    for coin in amount {
+        encode_bytes_field(3, &encode_coin(coin), &mut buf);
+    }
+    buf
+}
+
+/// `google.protobuf.Any`
+fn encode_any(type_url: &str, value: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_bytes_field(1, type_url.as_bytes(), &mut buf);
+    encode_bytes_field(2, value, &mut buf);
+    buf
+}
+
+/// `cosmos.authz.v1beta1.MsgExec` wrapping a single `MsgSend` from `granter` to
+/// `grantee`. Requires `granter` to have granted `grantee` a SendAuthorization
+/// (or GenericAuthorization for MsgSend); otherwise the message fails.
+pub fn exec_send_from_granter(grantee: &Addr, granter: &Addr, amount: Coin) -> CosmosMsg {
+    let send = encode_msg_send(granter, grantee, &[amount]);
+    let mut exec = Vec::new();
+    encode_bytes_field(1, grantee.as_bytes(), &mut exec);
+    encode_bytes_field(2, &encode_any(MSG_SEND_TYPE_URL, &send), &mut exec);
+    CosmosMsg::Stargate {
+        type_url: MSG_EXEC_TYPE_URL.to_string(),
+        value: Binary::from(exec),
+    }
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult};
 use cw2::{get_contract_version, set_contract_version};
 
 use crate::error::ContractError;
@@ -10,14 +10,24 @@ use crate::query::{
     query_payout_history, query_payouts, query_penalties, query_refunds, query_circles,
     query_member_locked_amount, query_blocked_members, query_member_pseudonym,
     query_private_members, query_distribution_calendar, query_archived_date, query_pending_payout,
-    query_member_accumulated_late_fees,
+    query_member_accumulated_late_fees, query_invite_codes, query_waitlist,
+    query_quote_creator_lock, query_collateral_config, query_creator_succession,
+    query_wind_down_preview, query_pause_history, query_auto_debit_members,
+    query_simulate_exit, query_simulate_ejection, query_simulate_deposit,
+    query_preview_create_circle, query_member_position, query_member_reputation,
+    query_private_reservations, query_staking_config, query_guarantor_exposure, query_loans, query_templates,
+};
+use crate::state::{
+    CircleStatus, DistributionThreshold, PlatformConfig, PrivateReservation, CIRCLES,
+    MEMBER_LOCKED_AMOUNTS, MEMBER_PSEUDONYMS, PRIVATE_MEMBER_LIST, PRIVATE_RESERVATIONS,
 };
-use crate::state::{CircleStatus, DistributionThreshold, PlatformConfig, CIRCLES};
 
 const CONTRACT_NAME: &str = "crates.io:safrimba-contract";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// API version for frontend capabilities: 1 = v1 (join + lock_deposit), 2 = v2 (join with funds, pending payouts, etc.)
 const CONTRACT_API_VERSION: u8 = 2;
+/// Acceptance window given to members converted from legacy AddPrivateMember seats on migrate.
+const MIGRATED_RESERVATION_WINDOW_SECS: u64 = 14 * 86400;
 
 #[entry_point]
 pub fn instantiate(
@@ -30,11 +40,16 @@ pub fn instantiate(
 
     // Validate platform address
     let platform_address = deps.api.addr_validate(msg.platform_address.as_str())?;
+    let attestation_registry = msg
+        .attestation_registry
+        .map(|addr| deps.api.addr_validate(addr.as_str()))
+        .transpose()?;
 
     // Store platform configuration
     let platform_config = PlatformConfig {
         platform_fee_percent: msg.platform_fee_percent,
         platform_address: platform_address.clone(),
+        attestation_registry,
     };
     crate::state::PLATFORM_CONFIG.save(deps.storage, &platform_config)?;
 
@@ -55,7 +70,12 @@ pub fn execute(
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    crate::execute::reply(deps, env, msg)
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let version = get_contract_version(deps.storage)?;
     set_contract_version(
         deps.storage,
@@ -74,6 +94,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
     let mut threshold_backfilled: u32 = 0;
     let mut status_healed: u32 = 0;
     let mut denomination_backfilled: u32 = 0;
+    let mut private_members_reserved: u32 = 0;
     for id in ids {
         let mut circle = CIRCLES.load(deps.storage, id)?;
         let mut dirty = false;
@@ -132,6 +153,57 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
             dirty = true;
         }
 
+        // 5) Members pushed straight into `members_list` by the old
+        // AddPrivateMember never posted a join lock. For circles that have not
+        // started yet, turn them back into seat reservations so they must
+        // accept with funds like everyone else. Running circles are left as-is:
+        // such members hold no MEMBER_LOCKED entry, so the lock-insufficient
+        // trigger in process_payout / advance_round ejects them on a miss.
+        if matches!(
+            circle.circle_status,
+            CircleStatus::Draft | CircleStatus::Open | CircleStatus::Full
+        ) {
+            let private_list = PRIVATE_MEMBER_LIST
+                .may_load(deps.storage, id)?
+                .unwrap_or_default();
+            let unlocked: Vec<_> = circle
+                .members_list
+                .iter()
+                .filter(|m| **m != circle.creator_address && private_list.contains(m))
+                .filter(|m| !MEMBER_LOCKED_AMOUNTS.has(deps.storage, (id, (*m).clone())))
+                .cloned()
+                .collect();
+            let reserved_here = !unlocked.is_empty();
+            for member in unlocked {
+                circle.members_list.retain(|m| m != member);
+                if !circle.pending_members.contains(&member) {
+                    circle.pending_members.push(member.clone());
+                }
+                let pseudonym = MEMBER_PSEUDONYMS.may_load(deps.storage, (id, member.clone()))?;
+                PRIVATE_RESERVATIONS.save(
+                    deps.storage,
+                    (id, member.clone()),
+                    &PrivateReservation {
+                        member: member.clone(),
+                        pseudonym,
+                        reserved_at: env.block.time,
+                        expires_at: env.block.time.plus_seconds(MIGRATED_RESERVATION_WINDOW_SECS),
+                    },
+                )?;
+                private_members_reserved += 1;
+                dirty = true;
+            }
+            if reserved_here {
+                circle.circle_status = if circle.members_list.len() as u32 >= circle.max_members {
+                    CircleStatus::Full
+                } else if circle.members_list.len() > 1 {
+                    CircleStatus::Open
+                } else {
+                    CircleStatus::Draft
+                };
+            }
+        }
+
         if dirty {
             CIRCLES.save(deps.storage, id, &circle)?;
         }
@@ -144,7 +216,8 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         .add_attribute("members_at_start_backfilled", members_at_start_backfilled.to_string())
         .add_attribute("distribution_threshold_backfilled", threshold_backfilled.to_string())
         .add_attribute("running_status_healed", status_healed.to_string())
-        .add_attribute("denomination_backfilled", denomination_backfilled.to_string()))
+        .add_attribute("denomination_backfilled", denomination_backfilled.to_string())
+        .add_attribute("private_members_reserved", private_members_reserved.to_string()))
 }
 
 #[entry_point]
@@ -215,12 +288,52 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetPrivateMembers { circle_id } => {
             cosmwasm_std::to_json_binary(&query_private_members(deps, env, circle_id)?)
         }
+        QueryMsg::GetPrivateReservations { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_private_reservations(deps, env, circle_id)?)
+        }
+        QueryMsg::GetWaitlist { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_waitlist(deps, env, circle_id)?)
+        }
+        QueryMsg::GetWindDownPreview { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_wind_down_preview(deps, env, circle_id)?)
+        }
+        QueryMsg::SimulateExit { circle_id, member } => {
+            cosmwasm_std::to_json_binary(&query_simulate_exit(deps, env, circle_id, member)?)
+        }
+        QueryMsg::SimulateEjection { circle_id, member } => {
+            cosmwasm_std::to_json_binary(&query_simulate_ejection(deps, env, circle_id, member)?)
+        }
+        QueryMsg::SimulateDeposit {
+            circle_id,
+            member,
+            rounds,
+        } => cosmwasm_std::to_json_binary(&query_simulate_deposit(
+            deps, env, circle_id, member, rounds,
+        )?),
+        QueryMsg::GetAutoDebitMembers { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_auto_debit_members(deps, env, circle_id)?)
+        }
+        QueryMsg::GetPauseHistory { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_pause_history(deps, env, circle_id)?)
+        }
+        QueryMsg::GetCreatorSuccession { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_creator_succession(deps, env, circle_id)?)
+        }
+        QueryMsg::GetInviteCodes { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_invite_codes(deps, env, circle_id)?)
+        }
         QueryMsg::GetDistributionCalendar { circle_id } => {
             cosmwasm_std::to_json_binary(&query_distribution_calendar(deps, env, circle_id)?)
         }
         QueryMsg::GetArchivedDate { circle_id } => {
             cosmwasm_std::to_json_binary(&query_archived_date(deps, env, circle_id)?)
         }
+        QueryMsg::GetMemberReputation { member } => {
+            cosmwasm_std::to_json_binary(&query_member_reputation(deps, env, member)?)
+        }
+        QueryMsg::GetMemberPosition { circle_id, member } => {
+            cosmwasm_std::to_json_binary(&query_member_position(deps, env, circle_id, member)?)
+        }
         QueryMsg::GetPendingPayout { circle_id, member } => {
             cosmwasm_std::to_json_binary(&query_pending_payout(deps, env, circle_id, member)?)
         }
@@ -234,6 +347,86 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 deps, env, circle_id, member,
             )?)
         }
+        QueryMsg::QuoteCreatorLock {
+            contribution_amount,
+            max_members,
+            creator_collateral_policy,
+        } => cosmwasm_std::to_json_binary(&query_quote_creator_lock(
+            deps,
+            env,
+            contribution_amount,
+            max_members,
+            creator_collateral_policy,
+        )?),
+        QueryMsg::PreviewCreateCircle {
+            max_members,
+            min_members_required,
+            contribution_amount,
+            denomination,
+            exit_penalty_percent,
+            late_fee_percent,
+            total_cycles,
+            cycle_duration_days,
+            cycle_duration_seconds,
+            start_date,
+            grace_period_hours,
+            grace_period_seconds,
+            payout_order_type,
+            payout_order_list,
+            visibility,
+            distribution_threshold,
+            creator_collateral_policy,
+            late_fee_policy,
+            join_requirements,
+            requires_attestation,
+            stake_locks,
+            reserve_premium_bps,
+            kind,
+            ..
+        } => cosmwasm_std::to_json_binary(&query_preview_create_circle(
+            deps,
+            env,
+            max_members,
+            min_members_required,
+            contribution_amount,
+            denomination,
+            exit_penalty_percent,
+            late_fee_percent,
+            total_cycles,
+            cycle_duration_days,
+            cycle_duration_seconds,
+            start_date,
+            grace_period_hours,
+            grace_period_seconds,
+            payout_order_type,
+            payout_order_list,
+            visibility,
+            distribution_threshold,
+            creator_collateral_policy,
+            late_fee_policy,
+            join_requirements,
+            requires_attestation,
+            stake_locks,
+            reserve_premium_bps,
+            kind,
+        )?),
+        QueryMsg::GetCollateralConfig {} => {
+            cosmwasm_std::to_json_binary(&query_collateral_config(deps, env)?)
+        }
+        QueryMsg::GetStakingConfig {} => {
+            cosmwasm_std::to_json_binary(&query_staking_config(deps, env)?)
+        }
+        QueryMsg::GetGuarantorExposure { guarantor } => {
+            cosmwasm_std::to_json_binary(&query_guarantor_exposure(deps, env, guarantor)?)
+        }
+        QueryMsg::GetLoans { circle_id } => {
+            cosmwasm_std::to_json_binary(&query_loans(deps, env, circle_id)?)
+        }
+        QueryMsg::GetTemplates {
+            owner,
+            start_after,
+            limit,
+        } => cosmwasm_std::to_json_binary(&query_templates(deps, env, owner, start_after, limit)?),
         QueryMsg::GetContractVersion {} => cosmwasm_std::to_json_binary(&ContractVersionResponse {
             api_version: CONTRACT_API_VERSION,
         }),
@@ -255,6 +448,7 @@ mod tests {
         let msg = InstantiateMsg {
             platform_fee_percent: 100, // 1%
             platform_address: Addr::unchecked("platform"),
+            attestation_registry: None,
         };
 
         let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
@@ -21,6 +21,33 @@ pub enum ContractError {
     #[error("Circle is invite-only")]
     InviteOnly { circle_id: u64 },
 
+    #[error("Invite code is invalid or has no uses left")]
+    InvalidInviteCode { circle_id: u64 },
+
+    #[error("Invite code expired at {expired_at}")]
+    InviteCodeExpired { circle_id: u64, expired_at: u64 },
+
+    #[error("Seat reservation expired at {expired_at}. Ask the creator to reserve again")]
+    ReservationExpired { circle_id: u64, expired_at: u64 },
+
+    #[error("Address {address} is not attested by the platform attestation registry")]
+    NotAttested { circle_id: u64, address: String },
+
+    #[error("Staked locks of circle {circle_id} are unbonding until {matures_at}; retry then")]
+    LockStakeUnbonding { circle_id: u64, matures_at: u64 },
+
+    #[error("Guarantor {guarantor} already backs the maximum of {max} members")]
+    GuarantorLimitReached { guarantor: String, max: u32 },
+
+    #[error("Join requirements not met: {reason}")]
+    JoinRequirementsNotMet { circle_id: u64, reason: String },
+
+    #[error("Collateral policy {kind} is not approved by the platform")]
+    CollateralPolicyNotAllowed { kind: String },
+
+    #[error("Creator lock {quoted} is below the platform minimum {minimum}")]
+    CollateralBelowMinimum { quoted: String, minimum: String },
+
     #[error("Member already joined")]
     AlreadyMember { address: String },
 
@@ -72,6 +99,6 @@ pub enum ContractError {
     #[error("Payout already processed for this cycle")]
     PayoutAlreadyProcessed { cycle: u32 },
 
-    #[error("Cannot cancel: at least one distribution has occurred. Circle must complete or use emergency procedures.")]
+    #[error("Cannot cancel: at least one distribution has occurred. Circle must complete or be settled with WindDownCircle.")]
     CancelNotAllowedAfterDistribution {},
 }
@@ -1,18 +1,24 @@
 use cosmwasm_std::{
-    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
-    Timestamp, Uint128,
+    Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, DistributionMsg, Env, MessageInfo, Order,
+    Reply, Response, StakingMsg, StdError, StdResult, Storage, SubMsg, SubMsgResult, Timestamp, Uint128,
 };
-use cw_utils::must_pay;
+use cw_utils::{may_pay, must_pay};
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::msg::ExecuteMsg;
+use crate::msg::{
+    AttestationQueryMsg, AttestationResponse, DepositQuoteResponse, EjectionQuoteResponse, ExecuteMsg, ExitQuoteResponse, CircleOverrides, WindDownPosition,
+};
 use crate::state::{
-    Circle, CircleStatus, DepositRecord, DistributionThreshold, EventLog, MemberMissedPayments,
-    PayoutOrderType, PayoutRecord, PenaltyRecord, RefundMode, Visibility, BLOCKED_MEMBERS,
-    CIRCLE_COUNTER, CIRCLES, CREATOR_REWARDS_CREDITED, DEPOSITS, EVENTS, EVENT_COUNTER,
-    MEMBER_ACCUMULATED_LATE_FEES, MEMBER_LAST_DEPOSITED_CYCLE, MEMBER_LOCKED_AMOUNTS,
-    MEMBER_MISSED_PAYMENTS, MEMBER_PSEUDONYMS, PAYOUTS, PENALTIES, PENDING_PAYOUTS,
-    PLATFORM_CONFIG, PRIVATE_MEMBER_LIST,
+    AutoDebitEnrollment, AutoDebitPull, Circle, CircleKind, CircleStatus, CircleTemplate, CircleTemplateParams, CollateralConfig, CollateralPolicy, CollateralPolicyKind, CreatorNomination, Loan, LoanApproval,
+    CircleReserve, DepositRecord, DistributionThreshold, EventLog, InviteCode, JoinRequirements, LateFeePolicy, LockStake,
+    Guarantee, GuarantorConfig, MemberHistory, MemberMissedPayments, PauseRecord, PayoutOrderType, PayoutRecord, PenaltyRecord, PrivateReservation,
+    RefundMode, SavingsPool, StakingConfig, ValidatorStake, Visibility, WaitlistEntry,
+    AUTO_DEBIT, AUTO_DEBIT_PULLS, AUTO_DEBIT_PULL_COUNTER, BLOCKED_MEMBERS, CIRCLE_COUNTER, CIRCLES, COLLATERAL_CONFIG, CREATOR_NOMINATIONS, CREATOR_VOTES, CREATOR_REWARDS_CREDITED, DEPOSITS, EVENTS,
+    EVENT_COUNTER, FORGIVENESS_VOTES, GUARANTEES, GUARANTOR_BACKINGS, GUARANTOR_CONFIG, GUARANTOR_NOMINATIONS, INVITE_CODES, MEMBER_ACCUMULATED_LATE_FEES, MEMBER_HISTORY, MEMBER_LAST_DEPOSITED_CYCLE, MEMBER_LOCKED_AMOUNTS,
+    MEMBER_MISSED_PAYMENTS, MEMBER_PSEUDONYMS, PAUSE_HISTORY, PAYOUT_COLLATERAL, PAYOUTS, PENALTIES, PENDING_PAYOUTS,
+    PARTIAL_DEPOSITS, PLATFORM_CONFIG, PREPAID_BALANCES, PRIVATE_MEMBER_LIST, PRIVATE_RESERVATIONS, STAKING_CONFIG,
+    LOANS, LOCK_BACKED_PENDING, MEMBER_SAVINGS, TEMPLATES, TEMPLATE_COUNTER, VALIDATOR_STAKES, WAITLIST,
 };
 
 /// First round index (within a savings cycle) where distribution may occur.
@@ -73,6 +79,14 @@ pub fn execute(
             visibility,
             show_member_identities,
             distribution_threshold,
+            creator_collateral_policy,
+            late_fee_policy,
+            join_requirements,
+            requires_attestation,
+            stake_locks,
+            reserve_premium_bps,
+            post_payout_collateral,
+            kind,
         } => execute_create_circle(
             deps,
             env,
@@ -106,17 +120,53 @@ pub fn execute(
             visibility,
             show_member_identities,
             distribution_threshold,
+            creator_collateral_policy,
+            late_fee_policy,
+            join_requirements,
+            requires_attestation,
+            stake_locks,
+            reserve_premium_bps,
+            post_payout_collateral,
+            kind,
         ),
-        ExecuteMsg::JoinCircle { circle_id } => execute_join_circle(deps, env, info, circle_id),
-        ExecuteMsg::AcceptInvite { circle_id } => execute_join_circle(deps, env, info, circle_id),
+        ExecuteMsg::JoinCircle {
+            circle_id,
+            invite_code,
+        } => execute_join_circle(deps, env, info, circle_id, invite_code),
+        ExecuteMsg::AcceptInvite { circle_id } => {
+            execute_join_circle(deps, env, info, circle_id, None)
+        }
         ExecuteMsg::InviteMember {
             circle_id,
             member_address,
         } => execute_invite_member(deps, env, info, circle_id, member_address),
+        ExecuteMsg::JoinWaitlist {
+            circle_id,
+            invite_code,
+        } => execute_join_waitlist(deps, env, info, circle_id, invite_code),
+        ExecuteMsg::LeaveWaitlist { circle_id } => {
+            execute_leave_waitlist(deps, env, info, circle_id)
+        }
+        ExecuteMsg::RegisterInviteCode {
+            circle_id,
+            code_hash,
+            max_uses,
+            expires_at,
+        } => execute_register_invite_code(deps, env, info, circle_id, code_hash, max_uses, expires_at),
+        ExecuteMsg::RevokeInviteCode {
+            circle_id,
+            code_hash,
+        } => execute_revoke_invite_code(deps, env, info, circle_id, code_hash),
         ExecuteMsg::ExitCircle { circle_id } => execute_exit_circle(deps, env, info, circle_id),
         ExecuteMsg::StartCircle { circle_id } => execute_start_circle(deps, env, info, circle_id),
-        ExecuteMsg::DepositContribution { circle_id } => {
-            execute_deposit_contribution(deps, env, info, circle_id)
+        ExecuteMsg::DepositContribution { circle_id, rounds } => {
+            execute_deposit_contribution(deps, env, info, circle_id, rounds)
+        }
+        ExecuteMsg::SetAutoDebit { circle_id, enabled } => {
+            execute_set_auto_debit(deps, env, info, circle_id, enabled)
+        }
+        ExecuteMsg::AutoDebitContributions { circle_id } => {
+            execute_auto_debit_contributions(deps, env, info, circle_id)
         }
         ExecuteMsg::ProcessPayout { circle_id } => {
             execute_process_payout(deps, env, info, circle_id)
@@ -126,6 +176,14 @@ pub fn execute(
         ExecuteMsg::CheckAndEject { circle_id } => {
             execute_check_and_eject(deps, env, info, circle_id)
         }
+        ExecuteMsg::ForgiveLateFees {
+            circle_id,
+            member,
+            amount,
+        } => execute_forgive_late_fees(deps, env, info, circle_id, member, amount),
+        ExecuteMsg::ReinstateMember { circle_id } => {
+            execute_reinstate_member(deps, env, info, circle_id)
+        }
         ExecuteMsg::PauseCircle { circle_id } => execute_pause_circle(deps, env, info, circle_id),
         ExecuteMsg::UnpauseCircle { circle_id } => {
             execute_unpause_circle(deps, env, info, circle_id)
@@ -161,7 +219,16 @@ pub fn execute(
             circle_id,
             member_address,
             pseudonym,
-        } => execute_add_private_member(deps, env, info, circle_id, member_address, pseudonym),
+            acceptance_window_seconds,
+        } => execute_add_private_member(
+            deps,
+            env,
+            info,
+            circle_id,
+            member_address,
+            pseudonym,
+            acceptance_window_seconds,
+        ),
         ExecuteMsg::UpdateMemberPseudonym {
             circle_id,
             member_address,
@@ -174,6 +241,67 @@ pub fn execute(
         ExecuteMsg::DistributeBlockedFunds { circle_id, cycle } => {
             execute_distribute_blocked_funds(deps, env, info, circle_id, cycle)
         }
+        ExecuteMsg::WindDownCircle { circle_id } => {
+            execute_wind_down_circle(deps, env, info, circle_id)
+        }
+        ExecuteMsg::TransferCreatorship {
+            circle_id,
+            new_creator,
+        } => execute_transfer_creatorship(deps, env, info, circle_id, new_creator),
+        ExecuteMsg::VoteCreatorTransfer { circle_id, nominee } => {
+            execute_vote_creator_transfer(deps, env, info, circle_id, nominee)
+        }
+        ExecuteMsg::AcceptCreatorship { circle_id } => {
+            execute_accept_creatorship(deps, env, info, circle_id)
+        }
+        ExecuteMsg::SetCollateralConfig {
+            allowed_policies,
+            min_lock_bps,
+        } => execute_set_collateral_config(deps, info, allowed_policies, min_lock_bps),
+        ExecuteMsg::SetAttestationRegistry { registry } => {
+            execute_set_attestation_registry(deps, info, registry)
+        }
+        ExecuteMsg::SetStakingConfig {
+            validators,
+            unbonding_period_secs,
+        } => execute_set_staking_config(deps, info, validators, unbonding_period_secs),
+        ExecuteMsg::SyncLockStake { circle_id } => execute_sync_lock_stake(deps, env, circle_id),
+        ExecuteMsg::SetGuarantorConfig { max_members_backed } => {
+            execute_set_guarantor_config(deps, info, max_members_backed)
+        }
+        ExecuteMsg::NominateGuarantor {
+            circle_id,
+            guarantor,
+        } => execute_nominate_guarantor(deps, env, info, circle_id, guarantor),
+        ExecuteMsg::GuaranteeMember { circle_id, member } => {
+            execute_guarantee_member(deps, env, info, circle_id, member)
+        }
+        ExecuteMsg::RevokeGuarantee { circle_id, member } => {
+            execute_revoke_guarantee(deps, env, info, circle_id, member)
+        }
+        ExecuteMsg::RenewCircle {
+            circle_id,
+            overrides,
+        } => execute_renew_circle(deps, env, info, circle_id, overrides),
+        ExecuteMsg::JoinRenewal { circle_id } => execute_join_renewal(deps, env, info, circle_id),
+        ExecuteMsg::SaveTemplate {
+            template_id,
+            template_name,
+            params,
+        } => execute_save_template(deps, env, info, template_id, template_name, params),
+        ExecuteMsg::DeleteTemplate { template_id } => execute_delete_template(deps, info, template_id),
+        ExecuteMsg::CreateCircleFromTemplate {
+            template_id,
+            overrides,
+        } => execute_create_circle_from_template(deps, env, info, template_id, overrides),
+        ExecuteMsg::RequestLoan { circle_id, amount } => {
+            execute_request_loan(deps, env, info, circle_id, amount)
+        }
+        ExecuteMsg::ApproveLoan {
+            circle_id,
+            borrower,
+        } => execute_approve_loan(deps, env, info, circle_id, borrower),
+        ExecuteMsg::RepayLoan { circle_id } => execute_repay_loan(deps, env, info, circle_id),
     }
 }
 
@@ -181,18 +309,62 @@ pub fn execute(
 // Helpers
 // ---------------------------------------------------------------------------
 
-/// Compute required creator lock: contribution_amount * 2
-fn compute_creator_lock(contribution: Uint128, _max_members: u32) -> Result<Uint128, ContractError> {
-    contribution
-        .checked_mul(Uint128::from(2u64))
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Creator lock overflow".to_string(),
-        })
+/// Compute required creator lock for a collateral policy (see `CollateralPolicy`)
+fn compute_creator_lock(
+    policy: &CollateralPolicy,
+    contribution: Uint128,
+    max_members: u32,
+) -> Result<Uint128, ContractError> {
+    let overflow = || ContractError::InvalidParameters {
+        msg: "Creator lock overflow".to_string(),
+    };
+    let (base, factor_bps) = match policy {
+        CollateralPolicy::FixedMultiple { multiple_bps } => (contribution, *multiple_bps),
+        CollateralPolicy::MemberScaled {
+            base_bps,
+            per_member_bps,
+        } => {
+            let factor = per_member_bps
+                .checked_mul(max_members as u64)
+                .and_then(|v| v.checked_add(*base_bps))
+                .ok_or_else(overflow)?;
+            (contribution, factor)
+        }
+        CollateralPolicy::PotPercent { pot_bps } => {
+            let pot = contribution
+                .checked_mul(Uint128::from(max_members))
+                .map_err(|_| overflow())?;
+            (pot, *pot_bps)
+        }
+    };
+    base.checked_multiply_ratio(factor_bps, 10000u64)
+        .map_err(|_| overflow())
 }
 
-/// Compute late fee per round: contribution * late_fee_percent / 10000
-fn compute_late_fee_per_round(contribution: Uint128, late_fee_percent: u64) -> Uint128 {
-    contribution.multiply_ratio(late_fee_percent, 10000u64)
+/// Creator lock for `policy`, checked against the platform's approved kinds and
+/// minimum. Returns `(creator_lock, platform_minimum)`. Shared by CreateCircle and
+/// the `QuoteCreatorLock` query so the quote always matches what creation requires.
+pub(crate) fn quote_creator_lock(
+    storage: &dyn Storage,
+    policy: &CollateralPolicy,
+    contribution: Uint128,
+    max_members: u32,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let config = COLLATERAL_CONFIG.may_load(storage)?.unwrap_or_default();
+    if !config.allowed_policies.contains(&policy.kind()) {
+        return Err(ContractError::CollateralPolicyNotAllowed {
+            kind: format!("{:?}", policy.kind()),
+        });
+    }
+    let lock = compute_creator_lock(policy, contribution, max_members)?;
+    let minimum = contribution.multiply_ratio(config.min_lock_bps, 10000u64);
+    if lock < minimum || lock.is_zero() {
+        return Err(ContractError::CollateralBelowMinimum {
+            quoted: lock.to_string(),
+            minimum: minimum.to_string(),
+        });
+    }
+    Ok((lock, minimum))
 }
 
 /// Compute exit penalty for a given locked amount: locked * exit_penalty_percent / 10000
@@ -200,6 +372,92 @@ fn compute_exit_penalty(locked: Uint128, exit_penalty_percent: u64) -> Uint128 {
     locked.multiply_ratio(exit_penalty_percent, 10000u64)
 }
 
+/// Started hours in a grace window (at least 1, so PerHour always charges something for a miss).
+fn grace_hours(grace_secs: u64) -> u64 {
+    grace_secs.div_ceil(3600).max(1)
+}
+
+/// Fee (bps of contribution) for the `miss_number`-th consecutive miss (1-based), before any cap.
+fn late_fee_bps_for_miss(
+    policy: &LateFeePolicy,
+    late_fee_percent: u64,
+    grace_secs: u64,
+    miss_number: u32,
+) -> u64 {
+    match policy {
+        LateFeePolicy::FlatPerRound | LateFeePolicy::Capped { .. } => late_fee_percent,
+        LateFeePolicy::Escalating { step_bps } => late_fee_percent
+            .saturating_add(step_bps.saturating_mul(miss_number.saturating_sub(1) as u64)),
+        LateFeePolicy::PerHour { bps_per_hour } => {
+            bps_per_hour.saturating_mul(grace_hours(grace_secs))
+        }
+    }
+}
+
+/// Apply the Capped policy's ceiling to a cumulative fee (bps); other policies pass through.
+fn cap_late_fee_bps(policy: &LateFeePolicy, total_bps: u64) -> u64 {
+    match policy {
+        LateFeePolicy::Capped { cap_bps } => total_bps.min(*cap_bps),
+        _ => total_bps,
+    }
+}
+
+/// Total late fees (bps of contribution) for `misses` consecutive missed rounds.
+fn cumulative_late_fee_bps(
+    policy: &LateFeePolicy,
+    late_fee_percent: u64,
+    grace_secs: u64,
+    misses: u32,
+) -> u64 {
+    let mut total = 0u64;
+    for n in 1..=misses {
+        let next = total.saturating_add(late_fee_bps_for_miss(policy, late_fee_percent, grace_secs, n));
+        total = cap_late_fee_bps(policy, next);
+        if total == cap_late_fee_bps(policy, u64::MAX) {
+            break;
+        }
+    }
+    total
+}
+
+/// Late fees owed for `misses` consecutive missed rounds under the circle's policy.
+pub(crate) fn late_fees_for_misses(circle: &Circle, misses: u32) -> Uint128 {
+    let bps = cumulative_late_fee_bps(
+        &circle.late_fee_policy,
+        circle.late_fee_percent,
+        circle.grace_period_secs(),
+        misses,
+    );
+    circle.contribution_amount.multiply_ratio(bps, 10000u64)
+}
+
+/// Fee added to a member's accumulated late fees when they reach their `miss_number`-th
+/// consecutive miss (difference of the cumulative totals, so caps are honoured).
+fn late_fee_for_nth_miss(circle: &Circle, miss_number: u32) -> Uint128 {
+    late_fees_for_misses(circle, miss_number)
+        .saturating_sub(late_fees_for_misses(circle, miss_number.saturating_sub(1)))
+}
+
+/// PerHour only: fee for a current-round deposit made after the round's deposit deadline
+/// (`next_payout_date`), one rate per started hour, capped at the grace window.
+pub(crate) fn grace_time_late_fee(circle: &Circle, now: Timestamp) -> Uint128 {
+    let LateFeePolicy::PerHour { bps_per_hour } = circle.late_fee_policy else {
+        return Uint128::zero();
+    };
+    let Some(deadline) = circle.next_payout_date else {
+        return Uint128::zero();
+    };
+    if now <= deadline {
+        return Uint128::zero();
+    }
+    let hours = (now.seconds() - deadline.seconds())
+        .div_ceil(3600)
+        .min(grace_hours(circle.grace_period_secs()));
+    circle
+        .contribution_amount
+        .multiply_ratio(bps_per_hour.saturating_mul(hours), 10000u64)
+}
+
 /// Base max_missed: floor((10000 - exit_penalty_percent) / late_fee_percent) from % penalty and late fee.
 /// Returns u32::MAX if late_fee_percent is 0 (no late fees configured).
 fn compute_max_missed_base(exit_penalty_percent: u64, late_fee_percent: u64) -> u32 {
@@ -210,14 +468,53 @@ fn compute_max_missed_base(exit_penalty_percent: u64, late_fee_percent: u64) ->
     (numerator / late_fee_percent) as u32
 }
 
-/// Compute max_missed scaled by active members. When members_at_start is None, returns base.
-fn compute_max_missed_scaled(
+/// Base max_missed for any late-fee policy: the most consecutive misses whose cumulative
+/// fees plus the exit penalty still fit in a one-contribution lock. This is the same point
+/// at which `should_eject_member`'s fee test fires, so both ejection triggers agree.
+/// Returns u32::MAX when fees stop growing before the lock is used up (zero rate, or a
+/// Capped ceiling below the budget) - the round-count cap then applies alone.
+fn compute_max_missed_for_policy(
+    policy: &LateFeePolicy,
     exit_penalty_percent: u64,
     late_fee_percent: u64,
+    grace_secs: u64,
+) -> u32 {
+    if *policy == LateFeePolicy::FlatPerRound {
+        return compute_max_missed_base(exit_penalty_percent, late_fee_percent);
+    }
+    let budget = 10000u64.saturating_sub(exit_penalty_percent);
+    let mut total = 0u64;
+    let mut misses = 0u32;
+    loop {
+        let fee = late_fee_bps_for_miss(policy, late_fee_percent, grace_secs, misses + 1);
+        let next = cap_late_fee_bps(policy, total.saturating_add(fee));
+        if next > budget {
+            return misses;
+        }
+        if next == total {
+            return if total >= budget { misses } else { u32::MAX };
+        }
+        total = next;
+        misses += 1;
+    }
+}
+
+/// Policy-aware base max_missed for an existing circle.
+pub(crate) fn max_missed_base_for_circle(circle: &Circle) -> u32 {
+    compute_max_missed_for_policy(
+        &circle.late_fee_policy,
+        circle.exit_penalty_percent,
+        circle.late_fee_percent,
+        circle.grace_period_secs(),
+    )
+}
+
+/// Scale a base max_missed by active members. When members_at_start is None, returns base.
+fn compute_max_missed_scaled(
+    base: u32,
     members_at_start: Option<u32>,
     current_active_members: u32,
 ) -> u32 {
-    let base = compute_max_missed_base(exit_penalty_percent, late_fee_percent);
     let Some(start_count) = members_at_start else {
         return base;
     };
@@ -235,1085 +532,1135 @@ fn cap_max_missed_by_rounds(max_missed: u32, total_rounds: u32) -> u32 {
     max_missed.min(cap)
 }
 
-/// Legacy alias for compute_max_missed_base (used at CreateCircle for preview).
-fn compute_max_missed(exit_penalty_percent: u64, late_fee_percent: u64) -> u32 {
-    compute_max_missed_base(exit_penalty_percent, late_fee_percent)
-}
+/// Default time a member has to accept an `AddPrivateMember` reservation.
+const DEFAULT_RESERVATION_WINDOW_SECS: u64 = 7 * 86400;
 
-/// Get the original locked amount for a member (what they deposited at join time).
-/// Creator's lock = creator_lock_amount; other members = contribution_amount.
-fn original_lock_for_member(circle: &Circle, member: &Addr) -> Uint128 {
-    if member == &circle.creator_address {
-        circle.creator_lock_amount
-    } else {
-        circle.contribution_amount
-    }
+/// Members holding an unexpired `AddPrivateMember` reservation for this circle.
+/// Expired reservations are pruned along with the pending_members entry they
+/// created, and a circle that was Full only through them reopens. Caller persists
+/// `circle`.
+fn live_reservations(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    now: Timestamp,
+) -> StdResult<Vec<Addr>> {
+    let (live, expired): (Vec<_>, Vec<_>) = PRIVATE_RESERVATIONS
+        .prefix(circle.circle_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .partition(|(_, r)| now < r.expires_at);
+    for (member, _) in &expired {
+        PRIVATE_RESERVATIONS.remove(storage, (circle.circle_id, member.clone()));
+        circle.pending_members.retain(|m| m != member);
+    }
+    let live: Vec<Addr> = live.into_iter().map(|(m, _)| m).collect();
+    refresh_seat_status(circle, live.len());
+    Ok(live)
 }
 
-/// Read the member's current `MEMBER_LOCKED_AMOUNTS` balance, treating
-/// "no entry" as zero. The creator has no entry (their lock lives in
-/// `circle.creator_lock_amount`), so this is for non-creator members only.
-fn current_member_lock(storage: &dyn Storage, circle_id: u64, member: &Addr) -> Uint128 {
-    MEMBER_LOCKED_AMOUNTS
-        .may_load(storage, (circle_id, member.clone()))
-        .unwrap_or(None)
-        .unwrap_or(Uint128::zero())
+/// Pre-start seat status: Full once members plus `reserved` live reservations
+/// take every seat; when a seat frees up, back to Open (Draft if only the
+/// creator is left).
+fn refresh_seat_status(circle: &mut Circle, reserved: usize) {
+    let taken = (circle.members_list.len() + reserved) as u32;
+    match circle.circle_status {
+        CircleStatus::Draft | CircleStatus::Open if taken >= circle.max_members => {
+            circle.circle_status = CircleStatus::Full;
+        }
+        CircleStatus::Full if taken < circle.max_members => {
+            circle.circle_status = if circle.members_list.len() > 1 {
+                CircleStatus::Open
+            } else {
+                CircleStatus::Draft
+            };
+        }
+        _ => {}
+    }
 }
 
-/// Apply Running-state bookkeeping to a circle: anchor calendar timestamps to
-/// `now`, set status / cycle index, snapshot `members_at_start`, and recompute
-/// `max_missed_payments_allowed` (scaled by current active members).
-///
-/// Used by both `execute_start_circle` (creator-triggered) and the
-/// `auto_start_when_full + by_members` branch in `execute_join_circle` so the
-/// two paths cannot drift.
-fn apply_running_state(circle: &mut Circle, now: Timestamp) {
-    circle.start_date = Some(now);
-    circle.first_cycle_date = Some(now);
-    circle.next_payout_date = Some(now);
-
-    // Round count is locked at start based on who actually joined, NOT the
-    // configured max. A 3-max-member circle that started with 2 members
-    // has 2 * total_cycles rounds, not 3 * total_cycles. Using max_members
-    // here caused circles to overshoot their calendar and never transition
-    // to Finalizing (the cron kept advancing past the last real round).
-    let members_at_start = circle.members_list.len() as u32;
-    let total_rounds = members_at_start * circle.total_cycles;
-    let total_duration_seconds = circle.cycle_duration_secs() * total_rounds as u64;
-    let end_timestamp = Timestamp::from_seconds(now.seconds() + total_duration_seconds);
-    circle.end_date = Some(end_timestamp);
-
-    circle.circle_status = CircleStatus::Running;
-    circle.current_cycle_index = 1;
-    circle.updated_at = now;
+/// Seat status once a member has been added before start: Full when every seat
+/// holds a member, auto-starting a `by_members` circle; otherwise Open, or Full
+/// when the `reserved` live reservations of others hold the remaining seats.
+fn settle_joined_status(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &mut Circle,
+    reserved: usize,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages = vec![];
+    if circle.members_list.len() as u32 >= circle.max_members {
+        circle.circle_status = CircleStatus::Full;
 
-    circle.members_at_start = Some(members_at_start);
-    circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
-        compute_max_missed_scaled(
-            circle.exit_penalty_percent,
-            circle.late_fee_percent,
-            Some(members_at_start),
-            members_at_start,
-        ),
-        total_rounds,
-    );
+        // by_members: auto-start only when the circle is full (last seat filled).
+        // Creator can still call StartCircle earlier via execute_start_circle once min_members_required is met.
+        if circle.auto_start_when_full && circle.auto_start_type.as_deref() == Some("by_members") {
+            generate_payout_order(circle, env);
+            // Use the same helper as `execute_start_circle` so
+            // members_at_start, end_date, and max_missed_payments_allowed
+            // are set consistently across both code paths.
+            apply_running_state(circle, env.block.time);
+            record_circle_joined(deps.storage, circle)?;
+            messages.extend(refund_waitlist(deps, env, circle)?);
+            messages.extend(start_lock_stake(deps, env, circle)?);
+        }
+    } else {
+        if circle.circle_status == CircleStatus::Draft {
+            circle.circle_status = CircleStatus::Open;
+        }
+        // The remaining seats may all be reserved: then no one else can join.
+        refresh_seat_status(circle, reserved);
+    }
+    Ok(messages)
 }
 
-/// Check if member meets ejection condition. A member is ejected when EITHER:
-///   1. Their `missed_count >= max_missed_payments_allowed` (the configured cap), OR
-///   2. `accumulated_late_fees + exit_penalty >= original_lock` (locked funds exhausted).
-///
-/// Uses `original_lock` (the amount the member deposited at join time) rather than the
-/// remaining locked balance, since locked funds may have been partially consumed to cover
-/// missed deposits via `use_locked_amount_for_member`.
-fn should_eject_member(
-    storage: &dyn Storage,
+/// Take `member`'s entry off the waitlist, if they have one.
+fn take_waitlist_entry(
+    storage: &mut dyn Storage,
     circle_id: u64,
     member: &Addr,
-    original_lock: Uint128,
-    exit_penalty_percent: u64,
-    max_missed_payments_allowed: u32,
-) -> bool {
-    if original_lock.is_zero() {
-        return false;
-    }
-    // Hard cap: missed payments at or above the configured max trigger ejection
-    // independently of the late-fee/penalty arithmetic. Without this, low %
-    // configs (e.g. 10% late fee + 20% exit penalty in a 6-round circle) never
-    // accumulate enough fees to reach the original_lock threshold within the
-    // circle's lifetime, so members can miss every round and stay in the circle.
-    if max_missed_payments_allowed > 0 {
-        let missed = MEMBER_MISSED_PAYMENTS
-            .may_load(storage, (circle_id, member.clone()))
-            .unwrap_or(None)
-            .map(|m| m.missed_count)
-            .unwrap_or(0);
-        if missed >= max_missed_payments_allowed {
-            return true;
-        }
+) -> StdResult<Option<WaitlistEntry>> {
+    let mut waitlist = WAITLIST.may_load(storage, circle_id)?.unwrap_or_default();
+    let Some(position) = waitlist.iter().position(|e| e.member == *member) else {
+        return Ok(None);
+    };
+    let entry = waitlist.remove(position);
+    if waitlist.is_empty() {
+        WAITLIST.remove(storage, circle_id);
+    } else {
+        WAITLIST.save(storage, circle_id, &waitlist)?;
     }
-    let accumulated = MEMBER_ACCUMULATED_LATE_FEES
-        .may_load(storage, (circle_id, member.clone()))
-        .unwrap_or(None)
-        .unwrap_or(Uint128::zero());
-    let exit_penalty = compute_exit_penalty(original_lock, exit_penalty_percent);
-    accumulated + exit_penalty >= original_lock
+    Ok(Some(entry))
 }
 
-/// Eject a member from a running circle: remove from members_list, record in BLOCKED_MEMBERS, keep locked funds in pool, emit event.
-fn eject_member_from_circle(
+/// Fill one freed pre-start seat from the head of the waitlist. A pre-locked
+/// entrant becomes a member immediately (their pre-lock becomes the join lock)
+/// and the seat status follows the end of `execute_join_circle`, auto-start
+/// included; otherwise they get a seat reservation and must AcceptInvite with
+/// funds. Entries that are already members, or that no longer pass the
+/// attestation and join requirements, are dropped and refunded. Caller persists
+/// `circle`.
+fn promote_from_waitlist(
     deps: &mut DepsMut,
     env: &Env,
     circle: &mut Circle,
-    member: &Addr,
-) -> Result<(), ContractError> {
-    circle.members_list.retain(|m| m != member);
-    BLOCKED_MEMBERS.save(
-        deps.storage,
-        (circle.circle_id, member.clone()),
-        &circle.current_cycle_index,
-    )?;
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let circle_id = circle.circle_id;
+    let mut waitlist = WAITLIST.may_load(deps.storage, circle_id)?.unwrap_or_default();
+    let reserved = live_reservations(deps.storage, circle, env.block.time)?.len();
+    if (circle.members_list.len() + reserved) as u32 >= circle.max_members {
+        return Ok(vec![]);
+    }
 
-    // Accumulate the exit penalty and remaining late fees as penalties collected
-    let locked = MEMBER_LOCKED_AMOUNTS
-        .may_load(deps.storage, (circle.circle_id, member.clone()))?
-        .unwrap_or(Uint128::zero());
-    let accumulated_fees = MEMBER_ACCUMULATED_LATE_FEES
-        .may_load(deps.storage, (circle.circle_id, member.clone()))?
-        .unwrap_or(Uint128::zero());
-    let exit_penalty = compute_exit_penalty(locked, circle.exit_penalty_percent);
-    let penalty_total = accumulated_fees + exit_penalty;
-    let actual_penalty = if penalty_total > locked {
-        locked
+    let mut messages = vec![];
+    let mut next = None;
+    while !waitlist.is_empty() {
+        let entry = waitlist.remove(0);
+        let already_member = circle.members_list.contains(&entry.member);
+        if already_member || !meets_join_gates(deps.as_ref(), env, circle, &entry.member)? {
+            if !already_member {
+                log_event(
+                    deps,
+                    env,
+                    circle_id,
+                    "waitlist_skipped",
+                    &format!(
+                        "{} skipped on the waitlist: join requirements no longer met (refund: {})",
+                        entry.member, entry.prelocked
+                    ),
+                )?;
+            }
+            messages.extend(safe_refund_or_queue(
+                deps.branch(),
+                env,
+                circle_id,
+                &entry.member,
+                entry.prelocked,
+                &circle.denomination,
+            )?);
+            continue;
+        }
+        next = Some(entry);
+        break;
+    }
+    if waitlist.is_empty() {
+        WAITLIST.remove(deps.storage, circle_id);
     } else {
-        penalty_total
+        WAITLIST.save(deps.storage, circle_id, &waitlist)?;
+    }
+    let Some(entry) = next else {
+        return Ok(messages);
     };
 
-    circle.total_penalties_collected = circle
-        .total_penalties_collected
-        .checked_add(actual_penalty)
-        .unwrap_or(circle.total_penalties_collected);
+    let admitted = !entry.prelocked.is_zero();
+    if admitted {
+        add_member_locked(
+            deps.storage,
+            circle_id,
+            &entry.member,
+            entry.prelocked,
+            &mut circle.total_amount_locked,
+        )?;
+        circle.members_list.push(entry.member.clone());
+        circle.pending_members.retain(|m| *m != entry.member);
+        messages.extend(settle_joined_status(deps, env, circle, reserved)?);
+    } else {
+        PRIVATE_RESERVATIONS.save(
+            deps.storage,
+            (circle_id, entry.member.clone()),
+            &PrivateReservation {
+                member: entry.member.clone(),
+                pseudonym: None,
+                reserved_at: env.block.time,
+                expires_at: env.block.time.plus_seconds(DEFAULT_RESERVATION_WINDOW_SECS),
+            },
+        )?;
+        if !circle.pending_members.contains(&entry.member) {
+            circle.pending_members.push(entry.member.clone());
+        }
+        refresh_seat_status(circle, reserved + 1);
+    }
 
-    // Clear per-member fee tracking
-    MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle.circle_id, member.clone()));
+    log_event(
+        deps,
+        env,
+        circle_id,
+        "waitlist_promoted",
+        &format!(
+            "{} promoted from waitlist ({})",
+            entry.member,
+            if admitted { "admitted with pre-lock" } else { "seat reserved" }
+        ),
+    )?;
 
-    // Record missed payments for stats
-    let mut missed = MEMBER_MISSED_PAYMENTS
-        .may_load(deps.storage, (circle.circle_id, member.clone()))?
-        .unwrap_or(MemberMissedPayments {
-            member: member.clone(),
-            missed_count: 0,
-            last_missed_cycle: None,
-            last_fee_round: None,
-        });
-    missed.last_missed_cycle = Some(circle.current_cycle_index);
-    MEMBER_MISSED_PAYMENTS.save(deps.storage, (circle.circle_id, member.clone()), &missed)?;
+    Ok(messages)
+}
 
-    // Recompute max_missed_payments_allowed (dynamic from % penalty and late fee, scaled by active members).
-    // Round count is based on members_at_start (locked when circle started) NOT max_members — see apply_running_state.
-    let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
-    let total_rounds = members_basis * circle.total_cycles;
-    circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
-        compute_max_missed_scaled(
-            circle.exit_penalty_percent,
-            circle.late_fee_percent,
-            circle.members_at_start,
-            circle.members_list.len() as u32,
-        ),
-        total_rounds,
-    );
-
-    // Keep `payout_order_list` in sync with `members_list` so the calendar
-    // query (`get_distribution_calendar`) and the per-round recipient picker
-    // in `execute_process_payout` see the same active roster. Previously this
-    // was only done in the `check_and_eject` batch path, so an ejection that
-    // fired from inside `process_payout` / `advance_round` / `deposit` left a
-    // stale order with the blocked address, producing phantom calendar
-    // entries until the next `check_and_eject` swept it.
-    if let Some(ref mut order) = circle.payout_order_list {
-        order.retain(|m| m != member);
-    }
-
-    // Display-only field: keep it consistent with the new active roster size
-    // so the UI doesn't show a payout target that no longer matches reality.
-    // Actual on-chain payout math is recomputed inside `execute_process_payout`.
-    circle.payout_amount = circle
-        .contribution_amount
-        .checked_mul(Uint128::from(circle.members_list.len() as u128))
-        .unwrap_or(circle.payout_amount);
-
-    log_event(
-        deps,
-        env,
-        circle.circle_id,
-        "member_ejected",
-        &format!(
-            "Member {} ejected at cycle {} (locked: {}, fees: {}, penalty: {})",
-            member, circle.current_cycle_index, locked, accumulated_fees, exit_penalty
-        ),
-    )?;
-
-    // Re-emit the distribution calendar so off-chain consumers (frontend,
-    // server sync, scheduler) can re-render without waiting for the next
-    // full sync. `members_at_start` is intentionally NOT changed (it locks
-    // the schedule), so total_rounds stays put — only the per-cycle round
-    // recipients shift.
-    let start_ts = circle.first_cycle_date.unwrap_or(env.block.time);
-    let rebuilt = build_distribution_calendar(circle, start_ts);
-    log_event(
-        deps,
-        env,
-        circle.circle_id,
-        "calendar_rebuilt",
-        &format!(
-            "{{reason:\"ejection\",active_members:{},calendar:[{}]}}",
-            circle.members_list.len(),
-            rebuilt
-        ),
-    )?;
-
-    // Soft warning if the roster has fallen below the configured minimum.
-    // We do not auto-cancel — that's a product decision left to the creator
-    // via CancelCircle — but the warning surfaces in the event feed so the
-    // UI can flag the circle for attention.
-    if (circle.members_list.len() as u32) < circle.min_members_required {
-        log_event(
-            deps,
+/// Clear the waitlist and refund every pre-lock. Used when the circle starts
+/// or is cancelled without the remaining entrants.
+fn refund_waitlist(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &Circle,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let waitlist = WAITLIST.may_load(deps.storage, circle.circle_id)?.unwrap_or_default();
+    let mut messages = vec![];
+    for entry in &waitlist {
+        messages.extend(safe_refund_or_queue(
+            deps.branch(),
             env,
             circle.circle_id,
-            "min_members_breach",
-            &format!(
-                "Active members ({}) below min_members_required ({}) after ejection",
-                circle.members_list.len(),
-                circle.min_members_required
-            ),
-        )?;
+            &entry.member,
+            entry.prelocked,
+            &circle.denomination,
+        )?);
     }
-
-    Ok(())
+    WAITLIST.remove(deps.storage, circle.circle_id);
+    Ok(messages)
 }
 
-// ---------------------------------------------------------------------------
-// Create Circle
-// ---------------------------------------------------------------------------
+/// SHA-256 of the plaintext invite code, lowercase hex. Matches what clients
+/// compute off-chain before calling `RegisterInviteCode`.
+fn hash_invite_code(code: &str) -> String {
+    Sha256::digest(code.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
 
-#[allow(clippy::too_many_arguments)]
-fn execute_create_circle(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_name: String,
-    circle_description: String,
-    circle_image: Option<String>,
-    max_members: u32,
-    min_members_required: u32,
-    invite_only: bool,
-    contribution_amount: Uint128,
-    denomination: Option<String>,
-    exit_penalty_percent: u64,
-    late_fee_percent: u64,
-    total_cycles: u32,
-    cycle_duration_days: u32,
-    cycle_duration_seconds: Option<u64>,
-    start_date: Option<Timestamp>,
-    grace_period_hours: u32,
-    grace_period_seconds: Option<u64>,
-    auto_start_when_full: bool,
-    auto_start_type: Option<String>,
-    auto_start_date: Option<Timestamp>,
-    payout_order_type: PayoutOrderType,
-    payout_order_list: Option<Vec<Addr>>,
-    auto_payout_enabled: bool,
-    manual_trigger_enabled: bool,
-    emergency_stop_enabled: bool,
-    auto_refund_if_min_not_met: bool,
-    strict_mode: bool,
-    visibility: Visibility,
-    show_member_identities: bool,
-    distribution_threshold: Option<DistributionThreshold>,
-) -> Result<Response, ContractError> {
-    if max_members == 0 || min_members_required == 0 {
-        return Err(ContractError::InvalidParameters {
-            msg: "max_members and min_members_required must be greater than 0".to_string(),
-        });
-    }
-    if min_members_required > max_members {
-        return Err(ContractError::InvalidParameters {
-            msg: "min_members_required cannot exceed max_members".to_string(),
-        });
-    }
-    if total_cycles == 0 {
-        return Err(ContractError::InvalidParameters {
-            msg: "total_cycles must be greater than 0".to_string(),
-        });
-    }
-    if contribution_amount.is_zero() {
-        return Err(ContractError::InvalidParameters {
-            msg: "contribution_amount must be greater than 0".to_string(),
-        });
-    }
-    if exit_penalty_percent > 9000 {
+/// Validate a client-supplied SHA-256 digest (64 hex chars) and lowercase it
+/// so lookups are case-insensitive.
+fn normalize_invite_code_hash(code_hash: &str) -> Result<String, ContractError> {
+    let trimmed = code_hash.trim();
+    if trimmed.len() != 64 || !trimmed.chars().all(|c| c.is_ascii_hexdigit()) {
         return Err(ContractError::InvalidParameters {
-            msg: "exit_penalty_percent cannot exceed 90% (9000 basis points)".to_string(),
+            msg: "code_hash must be a 64-character hex SHA-256 digest".to_string(),
         });
     }
-    if late_fee_percent == 0 || late_fee_percent > 5000 {
-        return Err(ContractError::InvalidParameters {
-            msg: "late_fee_percent must be between 1 and 5000 basis points".to_string(),
-        });
+    Ok(trimmed.to_ascii_lowercase())
+}
+
+/// Redeem one use of an invite code. Removes the code once its last use is
+/// consumed. Returns the matched hash for the response attributes.
+fn redeem_invite_code(
+    storage: &mut dyn Storage,
+    env: &Env,
+    circle_id: u64,
+    code: &str,
+) -> Result<String, ContractError> {
+    let code_hash = hash_invite_code(code);
+    let mut invite = INVITE_CODES
+        .may_load(storage, (circle_id, code_hash.clone()))?
+        .ok_or(ContractError::InvalidInviteCode { circle_id })?;
+
+    if let Some(expiry) = invite.expires_at {
+        if env.block.time >= expiry {
+            return Err(ContractError::InviteCodeExpired {
+                circle_id,
+                expired_at: expiry.seconds(),
+            });
+        }
     }
-    if exit_penalty_percent + late_fee_percent > 10000 {
-        return Err(ContractError::InvalidParameters {
-            msg: "exit_penalty_percent + late_fee_percent cannot exceed 100%".to_string(),
-        });
+    if invite.uses_remaining == 0 {
+        return Err(ContractError::InvalidInviteCode { circle_id });
     }
 
-    // Public circles are temporarily disabled at the contract level. Existing
-    // Public circles continue to operate; new ones are rejected. Re-enable by
-    // removing this guard once the public-circle UX is finalized.
-    if matches!(visibility, Visibility::Public) {
-        return Err(ContractError::InvalidParameters {
-            msg: "Public circles are temporarily disabled. Please create a Private (invite-only) circle.".to_string(),
-        });
+    invite.uses_remaining -= 1;
+    if invite.uses_remaining == 0 {
+        INVITE_CODES.remove(storage, (circle_id, code_hash.clone()));
+    } else {
+        INVITE_CODES.save(storage, (circle_id, code_hash.clone()), &invite)?;
     }
+    Ok(code_hash)
+}
 
-    // Validate the payment denomination against the allow-list.
-    // - "usaf"      → native SAF (6 decimals)
-    // - the Noble IBC USDC trace on Safrochain (6 decimals)
-    // Unknown denoms are rejected up-front so a typo can't trap funds in a circle
-    // members will never be able to deposit into.
-    const SAF_DENOM: &str = "usaf";
-    const USDC_DENOM: &str =
-        "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
-    let chosen_denom: String = match denomination.as_deref() {
-        None | Some("") => SAF_DENOM.to_string(),
-        Some(d) if d == SAF_DENOM || d == USDC_DENOM => d.to_string(),
-        Some(other) => {
-            return Err(ContractError::InvalidParameters {
-                msg: format!(
-                    "denomination '{}' is not supported. Allowed: '{}' or '{}'.",
-                    other, SAF_DENOM, USDC_DENOM
-                ),
-            });
-        }
-    };
-
-    // Force distribution_threshold = Total for Public circles
-    let effective_threshold = match visibility {
-        Visibility::Public => Some(DistributionThreshold::Total {}),
-        Visibility::Private => {
-            if let Some(DistributionThreshold::MinMembers { count }) = distribution_threshold {
-                if count == 0 {
-                    return Err(ContractError::InvalidParameters {
-                        msg: "distribution_threshold MinMembers count must be > 0".to_string(),
-                    });
-                }
-                if count > min_members_required {
-                    return Err(ContractError::InvalidParameters {
-                        msg: format!(
-                            "distribution_threshold MinMembers count ({}) cannot exceed min_members_required ({})",
-                            count, min_members_required
-                        ),
-                    });
-                }
-                Some(DistributionThreshold::MinMembers { count })
-            } else {
-                distribution_threshold
-            }
+/// Whether `member` passes the circle's attestation gate and `JoinRequirements`
+/// right now. Only a failed check is `false`; query or storage errors propagate.
+fn meets_join_gates(
+    deps: Deps,
+    env: &Env,
+    circle: &Circle,
+    member: &Addr,
+) -> Result<bool, ContractError> {
+    let checked = ensure_attested(deps, circle, member).and_then(|_| match circle.join_requirements {
+        Some(ref requirements) => {
+            check_join_requirements(deps, env, circle.circle_id, requirements, member)
         }
-    };
-
-    // Validate payout_order_list
-    if let Some(ref order_list) = payout_order_list {
-        if order_list.len() as u32 != max_members {
-            return Err(ContractError::InvalidParameters {
-                msg: "payout_order_list length must match max_members".to_string(),
-            });
+        None => Ok(()),
+    });
+    match checked {
+        Ok(()) => Ok(true),
+        Err(ContractError::NotAttested { .. } | ContractError::JoinRequirementsNotMet { .. }) => {
+            Ok(false)
         }
+        Err(err) => Err(err),
     }
+}
 
-    // Auto-calculate creator lock amount: contribution * (1 + max_members * 10%)
-    let required_creator_lock = compute_creator_lock(contribution_amount, max_members)?;
-
-    // Validate payment: creator must send exactly required_creator_lock
-    let payment = must_pay(&info, &chosen_denom).map_err(|_| ContractError::InsufficientFunds {
-        required: required_creator_lock.to_string(),
-        sent: "0".to_string(),
-    })?;
+/// Check a joiner against the circle's `JoinRequirements`: their cross-circle
+/// MEMBER_HISTORY first, then the attestation contract if one is configured.
+fn check_join_requirements(
+    deps: Deps,
+    env: &Env,
+    circle_id: u64,
+    requirements: &JoinRequirements,
+    member: &Addr,
+) -> Result<(), ContractError> {
+    let history = MEMBER_HISTORY
+        .may_load(deps.storage, member.clone())?
+        .unwrap_or_default();
+    let fail = |reason: String| Err(ContractError::JoinRequirementsNotMet { circle_id, reason });
 
-    if payment < required_creator_lock {
-        return Err(ContractError::InsufficientFunds {
-            required: required_creator_lock.to_string(),
-            sent: payment.to_string(),
-        });
+    if history.circles_completed < requirements.min_circles_completed {
+        return fail(format!(
+            "{} circles completed, {} required",
+            history.circles_completed, requirements.min_circles_completed
+        ));
     }
+    if let Some(max) = requirements.max_ejections {
+        if history.ejections > max {
+            return fail(format!("{} ejections on record, at most {} allowed", history.ejections, max));
+        }
+    }
+    if let (Some(window), Some(ejected_at)) =
+        (requirements.no_ejection_within_seconds, history.last_ejected_at)
+    {
+        if env.block.time.seconds() < ejected_at.seconds().saturating_add(window) {
+            return fail(format!("ejected at {}, within the last {}s", ejected_at.seconds(), window));
+        }
+    }
+    if let Some(min_ratio) = requirements.min_on_time_ratio_bps {
+        let ratio = history.on_time_ratio_bps();
+        if ratio < min_ratio {
+            return fail(format!("on-time ratio {} bps, {} required", ratio, min_ratio));
+        }
+    }
+    if let Some(ref contract) = requirements.attestation_contract {
+        if !is_attested(deps, contract, member)? {
+            return fail(format!("not attested by {}", contract));
+        }
+    }
+    Ok(())
+}
 
-    // Auto-calculate max_missed_payments_allowed; cap at total_rounds-1 so ejection happens before last round
-    let total_rounds = max_members * total_cycles;
-    let max_missed = cap_max_missed_by_rounds(
-        compute_max_missed(exit_penalty_percent, late_fee_percent),
-        total_rounds,
-    );
-
-    let circle_id = CIRCLE_COUNTER
-        .may_load(deps.storage)?
-        .unwrap_or(0)
-        .checked_add(1)
-        .ok_or_else(|| ContractError::InvalidParameters {
-            msg: "Circle ID overflow".to_string(),
-        })?;
-
-    let payout_amount = contribution_amount
-        .checked_mul(Uint128::from(max_members))
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Payout amount overflow".to_string(),
-        })?;
+/// Ask an attestation contract (`AttestationQueryMsg`) whether `member` is attested.
+fn is_attested(deps: Deps, contract: &Addr, member: &Addr) -> StdResult<bool> {
+    let res: AttestationResponse = deps.querier.query_wasm_smart(
+        contract,
+        &AttestationQueryMsg::Attestation {
+            address: member.clone(),
+        },
+    )?;
+    Ok(res.attested)
+}
 
-    let cycle_secs = cycle_duration_seconds
-        .filter(|&s| s > 0)
-        .unwrap_or(cycle_duration_days as u64 * 86400);
-    if cycle_secs == 0 {
-        return Err(ContractError::InvalidParameters {
-            msg: "cycle_duration_days must be > 0, or cycle_duration_seconds must be > 0".to_string(),
+/// For circles with `requires_attestation`, require `member` to be attested by the
+/// platform attestation registry. A cleared registry fails closed.
+fn ensure_attested(deps: Deps, circle: &Circle, member: &Addr) -> Result<(), ContractError> {
+    if !circle.requires_attestation {
+        return Ok(());
+    }
+    let attested = match PLATFORM_CONFIG.load(deps.storage)?.attestation_registry {
+        Some(registry) => is_attested(deps, &registry, member)?,
+        None => false,
+    };
+    if !attested {
+        return Err(ContractError::NotAttested {
+            circle_id: circle.circle_id,
+            address: member.to_string(),
         });
     }
+    Ok(())
+}
 
-    // Grace period must be strictly less than cycle duration. If they are equal,
-    // the round-end gate (block.time >= round_end) and the grace gate
-    // (block.time > grace_end) coincide, leaving a 1-tick window where
-    // advance_round / process_payout can still be rejected with
-    // "Grace period not ended" even though the calendar says the round ended.
-    // If grace > cycle, advance can never proceed when any member is missing.
-    let grace_secs = grace_period_seconds
-        .filter(|&s| s > 0)
-        .unwrap_or(grace_period_hours as u64 * 3600);
-    if grace_secs >= cycle_secs {
-        return Err(ContractError::InvalidParameters {
-            msg: format!(
-                "grace_period ({}s) must be strictly less than cycle_duration ({}s)",
-                grace_secs, cycle_secs
-            ),
-        });
+/// Get the original locked amount for a member (what they deposited at join time).
+/// Creator's lock = creator_lock_amount; other members = contribution_amount.
+pub(crate) fn original_lock_for_member(circle: &Circle, member: &Addr) -> Uint128 {
+    if member == &circle.creator_address {
+        circle.creator_lock_amount
+    } else {
+        circle.contribution_amount
     }
+}
 
-    let end_date = start_date.map(|start| {
-        Timestamp::from_seconds(
-            start.seconds() + (cycle_secs * max_members as u64 * total_cycles as u64),
-        )
-    });
+/// Read the member's current `MEMBER_LOCKED_AMOUNTS` balance, treating
+/// "no entry" as zero. The creator has no entry (their lock lives in
+/// `circle.creator_lock_amount`), so this is for non-creator members only.
+pub(crate) fn current_member_lock(storage: &dyn Storage, circle_id: u64, member: &Addr) -> Uint128 {
+    MEMBER_LOCKED_AMOUNTS
+        .may_load(storage, (circle_id, member.clone()))
+        .unwrap_or(None)
+        .unwrap_or(Uint128::zero())
+}
 
-    let final_payout_order = match payout_order_type {
-        PayoutOrderType::RandomOrder => None,
-        PayoutOrderType::PredefinedOrder => payout_order_list,
-    };
+/// Split the creator's `creator_lock_amount` equally among the remaining active
+/// members as pending payouts (remainder to the first) and zero it. Used when the
+/// creator exits after start or is ejected.
+fn forfeit_creator_lock_to_members(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+) -> Result<(), ContractError> {
+    let circle_id = circle.circle_id;
+    let creator_lock = circle.creator_lock_amount;
+    if creator_lock.is_zero() {
+        return Ok(());
+    }
+    let active: Vec<Addr> = circle
+        .members_list
+        .iter()
+        .filter(|m| **m != circle.creator_address)
+        .filter(|m| {
+            BLOCKED_MEMBERS
+                .may_load(storage, (circle_id, (*m).clone()))
+                .unwrap_or(None)
+                .map(|bc| bc > circle.current_cycle_index)
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect();
 
-    // manual_trigger_enabled only meaningful for Private circles
-    let effective_manual_trigger = match visibility {
-        Visibility::Public => false,
-        Visibility::Private => manual_trigger_enabled,
-    };
+    if !active.is_empty() {
+        let count = Uint128::from(active.len() as u128);
+        let per_member = creator_lock.multiply_ratio(1u128, count.u128());
+        let remainder = creator_lock
+            .checked_sub(per_member * count)
+            .unwrap_or(Uint128::zero());
 
-    let circle = Circle {
-        circle_id,
-        circle_name,
-        circle_description,
-        circle_image,
-        creator_address: info.sender.clone(),
-        created_at: env.block.time,
-        updated_at: env.block.time,
-        max_members,
-        min_members_required,
-        invite_only,
-        members_list: vec![info.sender.clone()],
-        pending_members: vec![],
-        contribution_amount,
-        denomination: chosen_denom.clone(),
-        payout_amount,
-        exit_penalty_percent,
-        late_fee_percent,
-        platform_fee_percent: PLATFORM_CONFIG.load(deps.storage)?.platform_fee_percent,
-        max_missed_payments_allowed: max_missed,
-        total_cycles,
-        cycle_duration_days,
-        cycle_duration_seconds: cycle_duration_seconds.unwrap_or(0),
-        start_date,
-        first_cycle_date: start_date,
-        next_payout_date: start_date,
-        end_date,
-        grace_period_hours,
-        grace_period_seconds: grace_period_seconds.unwrap_or(0),
-        auto_start_when_full,
-        auto_start_type,
-        auto_start_date,
-        payout_order_type,
-        payout_order_list: final_payout_order,
-        auto_payout_enabled,
-        manual_trigger_enabled: effective_manual_trigger,
-        emergency_stop_enabled,
-        emergency_stop_triggered: false,
-        auto_refund_if_min_not_met,
-        strict_mode,
-        escrow_address: Some(env.contract.address.clone()),
-        total_amount_locked: required_creator_lock, // creator lock already in contract
-        total_penalties_collected: Uint128::zero(),
-        total_platform_fees_collected: Uint128::zero(),
-        total_pending_payouts: Uint128::zero(),
-        withdrawal_lock: false,
-        refund_mode: RefundMode::FullRefund,
-        circle_status: CircleStatus::Draft,
-        current_cycle_index: 0,
-        cycles_completed: 0,
-        members_paid_this_cycle: vec![],
-        members_late_this_cycle: vec![],
-        visibility,
-        show_member_identities,
-        creator_lock_amount: required_creator_lock,
-        distribution_threshold: effective_threshold,
-        members_at_start: None, // Set at StartCircle when member count is known
-    };
+        for (idx, member) in active.iter().enumerate() {
+            let mut share = per_member;
+            if idx == 0 {
+                share = share.checked_add(remainder).unwrap_or(share);
+            }
+            credit_pending_payout(
+                storage,
+                circle_id,
+                member,
+                share,
+                &mut circle.total_pending_payouts,
+            )?;
+        }
+    }
+    circle.total_amount_locked = circle.total_amount_locked.saturating_sub(creator_lock);
+    circle.creator_lock_amount = Uint128::zero();
+    Ok(())
+}
 
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
-    CIRCLE_COUNTER.save(deps.storage, &circle_id)?;
+/// Strict majority of active non-creator members, needed for a VoteCreatorTransfer
+/// nomination.
+pub(crate) fn creator_votes_required(circle: &Circle) -> u32 {
+    let voters = circle
+        .members_list
+        .iter()
+        .filter(|m| **m != circle.creator_address)
+        .count() as u32;
+    voters / 2 + 1
+}
 
-    log_event(
-        &mut deps,
-        &env,
+/// Make `new_creator` the creator. Their join lock moves into `creator_lock_amount`
+/// (the creator never has a MEMBER_LOCKED entry) and `top_up` is added on top. The
+/// outgoing creator's lock must already be settled by the caller. Clears any pending
+/// nomination and succession votes.
+fn install_creator(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    new_creator: &Addr,
+    top_up: Uint128,
+) -> Result<(), ContractError> {
+    let circle_id = circle.circle_id;
+    let join_lock = current_member_lock(storage, circle_id, new_creator);
+    debit_member_locked(
+        storage,
         circle_id,
-        "circle_created",
-        &format!(
-            "Circle {} created by {} (creator_lock: {}, max_missed: {})",
-            circle_id, info.sender, required_creator_lock, max_missed
-        ),
+        new_creator,
+        join_lock,
+        &mut circle.total_amount_locked,
     )?;
+    let moved = join_lock
+        .checked_add(top_up)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Creator lock overflow".to_string(),
+        })?;
+    circle.creator_lock_amount = circle.creator_lock_amount.checked_add(moved).map_err(|_| {
+        ContractError::InvalidParameters {
+            msg: "Creator lock overflow".to_string(),
+        }
+    })?;
+    circle.total_amount_locked = circle.total_amount_locked.checked_add(moved).map_err(|_| {
+        ContractError::InvalidParameters {
+            msg: "Locked amount overflow".to_string(),
+        }
+    })?;
+    circle.creator_address = new_creator.clone();
 
-    Ok(Response::new()
-        .add_attribute("action", "create_circle")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("creator", info.sender)
-        .add_attribute("creator_lock_amount", required_creator_lock.to_string())
-        .add_attribute("max_missed_payments_allowed", max_missed.to_string()))
-}
-
-// ---------------------------------------------------------------------------
-// Join Circle (merges former LockJoinDeposit)
-// ---------------------------------------------------------------------------
-
-fn execute_join_circle(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
-
-    if !matches!(circle.circle_status, CircleStatus::Draft | CircleStatus::Open) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Draft or Open".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
+    CREATOR_NOMINATIONS.remove(storage, circle_id);
+    let voters: Vec<Addr> = CREATOR_VOTES
+        .prefix(circle_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for voter in voters {
+        CREATOR_VOTES.remove(storage, (circle_id, voter));
     }
+    Ok(())
+}
 
-    if circle.members_list.len() as u32 >= circle.max_members {
-        return Err(ContractError::CircleFull {
-            max: circle.max_members,
-        });
-    }
+/// Fallback succession after the creator left `members_list` (exit or ejection)
+/// with their lock already settled: the earliest-joined remaining member becomes
+/// creator, with their join lock as the new creator lock and no top-up.
+/// Caller persists `circle`.
+fn succeed_departed_creator(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &mut Circle,
+) -> Result<Option<Addr>, ContractError> {
+    let previous = circle.creator_address.clone();
+    let Some(successor) = circle.members_list.first().cloned() else {
+        return Ok(None);
+    };
+    install_creator(deps.storage, circle, &successor, Uint128::zero())?;
 
-    if circle.members_list.contains(&info.sender) {
-        return Err(ContractError::AlreadyMember {
-            address: info.sender.to_string(),
-        });
-    }
+    log_event(
+        deps,
+        env,
+        circle.circle_id,
+        "creatorship_transferred",
+        &format!(
+            "Creatorship passed from {} to {} (fallback succession, creator_lock: {})",
+            previous, successor, circle.creator_lock_amount
+        ),
+    )?;
+    Ok(Some(successor))
+}
 
-    // Invite/private check
-    if circle.invite_only || matches!(circle.visibility, Visibility::Private) {
-        if !circle.pending_members.contains(&info.sender) {
-            return Err(ContractError::InviteOnly { circle_id });
+/// Net position of every active member for WindDownCircle. A member owes
+/// `received + late_fees - paid_in` when positive (taken from their lock first)
+/// and is owed `paid_in - received - late_fees` otherwise. Creditors are paid from
+/// the pool of circle funds left after returning the remaining locks, pro-rata if
+/// the pool is short; any surplus is split equally. If the funds cannot cover the
+/// locks themselves, every returned lock is scaled down by the same ratio and
+/// creditors get nothing. `spendable` is the liquid balance plus the circle's
+/// staked locks (delegated or unbonding) not already committed to PENDING_PAYOUTS.
+pub(crate) fn compute_wind_down(
+    storage: &dyn Storage,
+    circle: &Circle,
+    spendable: Uint128,
+) -> Result<Vec<WindDownPosition>, ContractError> {
+    let circle_id = circle.circle_id;
+    let mut positions: Vec<WindDownPosition> = vec![];
+    let mut roster_collateral = Uint128::zero();
+    for member in &circle.members_list {
+        let mut paid_in = Uint128::zero();
+        let mut received = Uint128::zero();
+        for round in 1..=circle.current_cycle_index {
+            if let Some(deposit) = DEPOSITS.may_load(storage, (circle_id, member.clone(), round))? {
+                paid_in += deposit.amount;
+            }
+            if let Some(payout) = PAYOUTS.may_load(storage, (circle_id, round, member.clone()))? {
+                received += payout.amount;
+            }
         }
-        circle.pending_members.retain(|m| m != &info.sender);
-    }
-
-    // Require member to send contribution_amount as join deposit (locked security)
-    let payment = must_pay(&info, &circle.denomination).map_err(|_| {
-        ContractError::InsufficientFunds {
-            required: circle.contribution_amount.to_string(),
-            sent: "0".to_string(),
+        let late_fees = MEMBER_ACCUMULATED_LATE_FEES
+            .may_load(storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero());
+        let mut lock = current_member_lock(storage, circle_id, member);
+        if *member == circle.creator_address {
+            lock += circle.creator_lock_amount;
         }
-    })?;
+        // Post-payout collateral is seized like the lock.
+        let collateral = payout_collateral_of(storage, circle_id, member);
+        roster_collateral += collateral;
+        lock += collateral;
+
+        let owes = received + late_fees;
+        let (seized, shortfall, credit) = if owes > paid_in {
+            let debt = owes - paid_in;
+            let seized = debt.min(lock);
+            (seized, debt - seized, Uint128::zero())
+        } else {
+            (Uint128::zero(), Uint128::zero(), paid_in - owes)
+        };
+        let guarantor_refund = guarantor_share(storage, circle_id, member, lock - seized);
 
-    if payment < circle.contribution_amount {
-        return Err(ContractError::InsufficientFunds {
-            required: circle.contribution_amount.to_string(),
-            sent: payment.to_string(),
+        positions.push(WindDownPosition {
+            member: member.clone(),
+            paid_in,
+            received,
+            late_fees,
+            lock,
+            seized,
+            shortfall,
+            credit,
+            settlement: lock - seized - guarantor_refund,
+            guarantor_refund,
         });
     }
 
-    // Lock the join deposit
-    if MEMBER_LOCKED_AMOUNTS
-        .may_load(deps.storage, (circle_id, info.sender.clone()))?
-        .is_some()
-    {
-        return Err(ContractError::InvalidParameters {
-            msg: "Join deposit already locked".to_string(),
-        });
+    let locks_back: Uint128 = positions.iter().map(|p| p.settlement + p.guarantor_refund).sum();
+    let total_credit: Uint128 = positions.iter().map(|p| p.credit).sum();
+    let reserve = circle.reserve.as_ref().map(|r| r.balance).unwrap_or_default();
+    let holdings = circle
+        .total_amount_locked
+        .checked_add(circle.total_penalties_collected)
+        .and_then(|h| h.checked_add(reserve))
+        .and_then(|h| h.checked_add(roster_collateral))
+        .unwrap_or(circle.total_amount_locked);
+    let available = holdings.min(spendable);
+    if available < locks_back {
+        for p in positions.iter_mut() {
+            p.settlement = p.settlement.multiply_ratio(available, locks_back);
+            p.guarantor_refund = p.guarantor_refund.multiply_ratio(available, locks_back);
+        }
     }
+    let pool = available.saturating_sub(locks_back);
 
-    add_member_locked(
-        deps.storage,
-        circle_id,
-        &info.sender,
-        circle.contribution_amount,
-        &mut circle.total_amount_locked,
-    )?;
-
-    // Add member
-    circle.members_list.push(info.sender.clone());
-    circle.updated_at = env.block.time;
-
-    if circle.members_list.len() as u32 >= circle.max_members {
-        circle.circle_status = CircleStatus::Full;
+    let mut paid_credit = Uint128::zero();
+    for p in positions.iter_mut() {
+        let share = if total_credit <= pool {
+            p.credit
+        } else {
+            p.credit.multiply_ratio(pool, total_credit)
+        };
+        p.settlement += share;
+        paid_credit += share;
+    }
 
-        if circle.auto_start_when_full {
-            if let Some(ref auto_type) = circle.auto_start_type.clone() {
-                // by_members: auto-start only when the circle is full (last seat filled).
-                // We are already inside `members_list.len() >= max_members`.
-                // Creator can still call StartCircle earlier via execute_start_circle once min_members_required is met.
-                if auto_type == "by_members" {
-                    generate_payout_order(&mut circle, &env);
-                    // Use the same helper as `execute_start_circle` so
-                    // members_at_start, end_date, and max_missed_payments_allowed
-                    // are set consistently across both code paths.
-                    apply_running_state(&mut circle, env.block.time);
-                }
+    let surplus = pool.saturating_sub(paid_credit);
+    if !surplus.is_zero() && !positions.is_empty() {
+        let count = Uint128::from(positions.len() as u128);
+        let per_member = surplus.multiply_ratio(1u128, count.u128());
+        let remainder = surplus - per_member * count;
+        for (idx, p) in positions.iter_mut().enumerate() {
+            p.settlement += per_member;
+            if idx == 0 {
+                p.settlement += remainder;
             }
         }
-    } else if circle.circle_status == CircleStatus::Draft {
-        circle.circle_status = CircleStatus::Open;
     }
 
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    Ok(positions)
+}
 
-    let event_type = if matches!(circle.circle_status, CircleStatus::Running)
-        && circle.current_cycle_index == 1
-    {
-        "circle_auto_started"
-    } else {
-        "member_joined"
-    };
-
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        event_type,
-        &format!("Member {} joined circle {} (locked: {})", info.sender, circle_id, circle.contribution_amount),
-    )?;
+/// Seconds the schedule of `round` was pushed back by completed pauses. A pause
+/// that began during round `r` delays that round and every later one, matching
+/// the `next_payout_date` shift applied on unpause.
+pub(crate) fn pause_shift_for_round(pauses: &[PauseRecord], round: u32) -> u64 {
+    pauses
+        .iter()
+        .filter(|p| p.resumed_at.is_some() && p.round <= round)
+        .map(|p| p.duration_seconds)
+        .sum()
+}
 
-    Ok(Response::new()
-        .add_attribute("action", "join_circle")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", info.sender)
-        .add_attribute("locked_amount", circle.contribution_amount.to_string()))
+/// Open a pause record when a circle enters Paused. Caller sets the status.
+fn record_pause_start(
+    storage: &mut dyn Storage,
+    circle: &Circle,
+    now: Timestamp,
+    emergency: bool,
+) -> Result<(), ContractError> {
+    let mut pauses = PAUSE_HISTORY
+        .may_load(storage, circle.circle_id)?
+        .unwrap_or_default();
+    pauses.push(PauseRecord {
+        paused_at: now,
+        round: circle.current_cycle_index,
+        resumed_at: None,
+        duration_seconds: 0,
+        emergency,
+    });
+    PAUSE_HISTORY.save(storage, circle.circle_id, &pauses)?;
+    Ok(())
 }
 
-// ---------------------------------------------------------------------------
-// Invite Member
-// ---------------------------------------------------------------------------
+/// Apply Running-state bookkeeping to a circle: anchor calendar timestamps to
+/// `now`, set status / cycle index, snapshot `members_at_start`, and recompute
+/// `max_missed_payments_allowed` (scaled by current active members).
+///
+/// Used by both `execute_start_circle` (creator-triggered) and the
+/// `auto_start_when_full + by_members` branch in `execute_join_circle` so the
+/// two paths cannot drift.
+fn apply_running_state(circle: &mut Circle, now: Timestamp) {
+    circle.start_date = Some(now);
+    circle.first_cycle_date = Some(now);
+    circle.next_payout_date = Some(now);
 
-fn execute_invite_member(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-    member_address: Addr,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    // Round count is locked at start based on who actually joined, NOT the
+    // configured max. A 3-max-member circle that started with 2 members
+    // has 2 * total_cycles rounds, not 3 * total_cycles. Using max_members
+    // here caused circles to overshoot their calendar and never transition
+    // to Finalizing (the cron kept advancing past the last real round).
+    let members_at_start = circle.members_list.len() as u32;
+    let total_rounds = members_at_start * circle.total_cycles;
+    let total_duration_seconds = circle.cycle_duration_secs() * total_rounds as u64;
+    let end_timestamp = Timestamp::from_seconds(now.seconds() + total_duration_seconds);
+    circle.end_date = Some(end_timestamp);
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can invite members".to_string(),
-        });
-    }
+    circle.circle_status = CircleStatus::Running;
+    circle.current_cycle_index = 1;
+    circle.updated_at = now;
 
-    if !circle.invite_only {
-        return Err(ContractError::InvalidParameters {
-            msg: "Circle is not invite-only".to_string(),
-        });
-    }
+    circle.members_at_start = Some(members_at_start);
+    circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
+        compute_max_missed_scaled(
+            max_missed_base_for_circle(circle),
+            Some(members_at_start),
+            members_at_start,
+        ),
+        total_rounds,
+    );
+}
 
-    let validated_addr = deps.api.addr_validate(member_address.as_str())?;
+/// Late fees and exit penalty withheld from `locked` when a member leaves a running
+/// circle (exit or ejection). Returns `(late_fees, exit_penalty, kept)`, where `kept`
+/// is their sum capped at `locked`.
+fn withheld_on_leave(
+    storage: &dyn Storage,
+    circle: &Circle,
+    member: &Addr,
+    locked: Uint128,
+) -> StdResult<(Uint128, Uint128, Uint128)> {
+    let late_fees = MEMBER_ACCUMULATED_LATE_FEES
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let exit_penalty = compute_exit_penalty(locked, circle.exit_penalty_percent);
+    let kept = (late_fees + exit_penalty).min(locked);
+    Ok((late_fees, exit_penalty, kept))
+}
 
-    if circle.members_list.contains(&validated_addr) {
-        return Err(ContractError::AlreadyMember {
-            address: validated_addr.to_string(),
-        });
-    }
+/// Prepaid balance and current-round partial deposit held for a member: their own
+/// money, returned in full whenever they leave. Returns `(prepaid, partial)`.
+fn held_for_member(
+    storage: &dyn Storage,
+    circle: &Circle,
+    member: &Addr,
+) -> StdResult<(Uint128, Uint128)> {
+    let prepaid = PREPAID_BALANCES
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let partial = PARTIAL_DEPOSITS
+        .may_load(
+            storage,
+            (circle.circle_id, member.clone(), circle.current_cycle_index),
+        )?
+        .unwrap_or(Uint128::zero());
+    Ok((prepaid, partial))
+}
 
-    if circle.pending_members.contains(&validated_addr) {
-        return Err(ContractError::InvalidParameters {
-            msg: "Member already invited".to_string(),
-        });
+/// Check if member meets ejection condition. A member is ejected when EITHER:
+///   1. Their `missed_count >= max_missed_payments_allowed` (the configured cap), OR
+///   2. `accumulated_late_fees + exit_penalty >= original_lock` (locked funds exhausted).
+///
+/// Uses `original_lock` (the amount the member deposited at join time) rather than the
+/// remaining locked balance, since locked funds may have been partially consumed to cover
+/// missed deposits via `use_locked_amount_for_member`.
+///
+/// Both tests are policy-agnostic: accumulated fees already follow the circle's
+/// `LateFeePolicy`, and `max_missed_payments_allowed` comes from
+/// `compute_max_missed_for_policy`, which stops at the same fee total.
+fn should_eject_member(
+    storage: &dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    original_lock: Uint128,
+    exit_penalty_percent: u64,
+    max_missed_payments_allowed: u32,
+) -> bool {
+    if original_lock.is_zero() {
+        return false;
     }
+    // Hard cap: missed payments at or above the configured max trigger ejection
+    // independently of the late-fee/penalty arithmetic. Without this, low %
+    // configs (e.g. 10% late fee + 20% exit penalty in a 6-round circle) never
+    // accumulate enough fees to reach the original_lock threshold within the
+    // circle's lifetime, so members can miss every round and stay in the circle.
+    if max_missed_payments_allowed > 0 {
+        let missed = MEMBER_MISSED_PAYMENTS
+            .may_load(storage, (circle_id, member.clone()))
+            .unwrap_or(None)
+            .map(|m| m.missed_count)
+            .unwrap_or(0);
+        if missed >= max_missed_payments_allowed {
+            return true;
+        }
+    }
+    let accumulated = MEMBER_ACCUMULATED_LATE_FEES
+        .may_load(storage, (circle_id, member.clone()))
+        .unwrap_or(None)
+        .unwrap_or(Uint128::zero());
+    let exit_penalty = compute_exit_penalty(original_lock, exit_penalty_percent);
+    accumulated + exit_penalty >= original_lock
+}
 
-    circle.pending_members.push(validated_addr.clone());
-    circle.updated_at = env.block.time;
-
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
-
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "member_invited",
-        &format!("Member {} invited to circle {}", validated_addr, circle_id),
+/// Eject a member from a running circle: remove from members_list, record in BLOCKED_MEMBERS, keep locked funds in pool, emit event.
+fn eject_member_from_circle(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &mut Circle,
+    member: &Addr,
+) -> Result<(), ContractError> {
+    circle.members_list.retain(|m| m != member);
+    BLOCKED_MEMBERS.save(
+        deps.storage,
+        (circle.circle_id, member.clone()),
+        &circle.current_cycle_index,
     )?;
+    update_member_history(deps.storage, member, |h| {
+        h.ejections += 1;
+        h.last_ejected_at = Some(env.block.time);
+    })?;
 
-    Ok(Response::new()
-        .add_attribute("action", "invite_member")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", validated_addr))
-}
+    // Accumulate the exit penalty and remaining late fees as penalties collected
+    let locked = current_member_lock(deps.storage, circle.circle_id, member);
+    let (accumulated_fees, exit_penalty, actual_penalty) =
+        withheld_on_leave(deps.storage, circle, member, locked)?;
 
-// ---------------------------------------------------------------------------
-// Exit Circle
-// ---------------------------------------------------------------------------
+    // Post-payout collateral stays with the circle: it backed rounds the
+    // member will no longer pay.
+    let collateral = take_payout_collateral(deps.storage, circle, member);
+    circle.total_penalties_collected = circle
+        .total_penalties_collected
+        .checked_add(actual_penalty + collateral)
+        .unwrap_or(circle.total_penalties_collected);
+    // A guarantor's stake absorbs the penalty before the member's own part of the lock.
+    let guarantor_drawn = draw_guarantor_stake(deps.storage, circle.circle_id, member, actual_penalty)?;
 
-fn execute_exit_circle(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    // Clear per-member fee tracking
+    MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle.circle_id, member.clone()));
 
-    if circle.circle_status == CircleStatus::Cancelled {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Draft, Open, Running or Paused".to_string(),
-            actual: "Cancelled".to_string(),
-        });
-    }
+    // Prepaid future rounds and an unfinished partial deposit are not
+    // forfeited; they go back via Withdraw.
+    let prepaid = take_prepaid(deps.storage, circle, member)?;
+    credit_pending_payout(
+        deps.storage,
+        circle.circle_id,
+        member,
+        prepaid,
+        &mut circle.total_pending_payouts,
+    )?;
+    refund_partial_deposits(deps.storage, circle, std::slice::from_ref(member))?;
 
-    if !circle.members_list.contains(&info.sender) {
-        return Err(ContractError::Unauthorized {
-            msg: "Not a member of this circle".to_string(),
+    // Record missed payments for stats
+    let mut missed = MEMBER_MISSED_PAYMENTS
+        .may_load(deps.storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(MemberMissedPayments {
+            member: member.clone(),
+            missed_count: 0,
+            last_missed_cycle: None,
+            last_fee_round: None,
         });
-    }
+    missed.last_missed_cycle = Some(circle.current_cycle_index);
+    MEMBER_MISSED_PAYMENTS.save(deps.storage, (circle.circle_id, member.clone()), &missed)?;
 
-    let started = matches!(
-        circle.circle_status,
-        CircleStatus::Running | CircleStatus::Paused
+    // Recompute max_missed_payments_allowed (dynamic from % penalty and late fee, scaled by active members).
+    // Round count is based on members_at_start (locked when circle started) NOT max_members — see apply_running_state.
+    let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
+    let total_rounds = members_basis * circle.total_cycles;
+    circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
+        compute_max_missed_scaled(
+            max_missed_base_for_circle(circle),
+            circle.members_at_start,
+            circle.members_list.len() as u32,
+        ),
+        total_rounds,
     );
 
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let mut refund_amount = Uint128::zero();
+    // Keep `payout_order_list` in sync with `members_list` so the calendar
+    // query (`get_distribution_calendar`) and the per-round recipient picker
+    // in `execute_process_payout` see the same active roster. Previously this
+    // was only done in the `check_and_eject` batch path, so an ejection that
+    // fired from inside `process_payout` / `advance_round` / `deposit` left a
+    // stale order with the blocked address, producing phantom calendar
+    // entries until the next `check_and_eject` swept it.
+    if let Some(ref mut order) = circle.payout_order_list {
+        order.retain(|m| m != member);
+    }
 
-    if !started {
-        // Before start: full refund of locked amount, no penalty
-        if let Ok(Some(locked)) =
-            MEMBER_LOCKED_AMOUNTS.may_load(deps.storage, (circle_id, info.sender.clone()))
-        {
-            refund_amount = locked;
-            debit_member_locked(
-                deps.storage,
-                circle_id,
-                &info.sender,
-                locked,
-                &mut circle.total_amount_locked,
-            )?;
+    // Display-only field: keep it consistent with the new active roster size
+    // so the UI doesn't show a payout target that no longer matches reality.
+    // Actual on-chain payout math is recomputed inside `execute_process_payout`.
+    circle.payout_amount = circle
+        .contribution_amount
+        .checked_mul(Uint128::from(circle.members_list.len() as u128))
+        .unwrap_or(circle.payout_amount);
 
-            if !refund_amount.is_zero() {
-                let refund_msgs = safe_refund_or_queue(
-                    deps.branch(),
-                    &env,
-                    circle_id,
-                    &info.sender,
-                    refund_amount,
-                    &circle.denomination,
-                )?;
-                messages.extend(refund_msgs);
-            }
-        }
-
-        // Update status
-        circle.members_list.retain(|m| m != &info.sender);
-        circle.updated_at = env.block.time;
-
-        if (circle.members_list.len() as u32) < circle.max_members
-            && circle.circle_status == CircleStatus::Full
-        {
-            circle.circle_status = CircleStatus::Open;
-        }
-
-        if (circle.members_list.len() as u32) < circle.min_members_required
-            && circle.auto_refund_if_min_not_met
-        {
-            circle.circle_status = CircleStatus::Cancelled;
-            let locked_entries: Vec<(Addr, Uint128)> = MEMBER_LOCKED_AMOUNTS
-                .prefix(circle_id)
-                .range(deps.storage, None, None, Order::Ascending)
-                .filter_map(|res| res.ok().map(|(m, a)| (m, a)))
-                .collect();
-            for (member, amount) in locked_entries {
-                if !amount.is_zero() {
-                    let refund_msgs = safe_refund_or_queue(
-                        deps.branch(),
-                        &env,
-                        circle_id,
-                        &member,
-                        amount,
-                        &circle.denomination,
-                    )?;
-                    messages.extend(refund_msgs);
-                    debit_member_locked(
-                        deps.storage,
-                        circle_id,
-                        &member,
-                        amount,
-                        &mut circle.total_amount_locked,
-                    )?;
-                }
-            }
-            if !circle.creator_lock_amount.is_zero() {
-                let creator_amount = circle.creator_lock_amount;
-                let refund_msgs = safe_refund_or_queue(
-                    deps.branch(),
-                    &env,
-                    circle_id,
-                    &circle.creator_address,
-                    creator_amount,
-                    &circle.denomination,
-                )?;
-                messages.extend(refund_msgs);
-            }
-        }
-
-        if circle.members_list.len() == 1 && circle.circle_status == CircleStatus::Open {
-            circle.circle_status = CircleStatus::Draft;
-        }
-    } else {
-        // After start: only allowed if strict_mode = false
-        if circle.strict_mode {
-            return Err(ContractError::StrictModeNoExit { circle_id });
-        }
-
-        let locked = MEMBER_LOCKED_AMOUNTS
-            .may_load(deps.storage, (circle_id, info.sender.clone()))?
-            .unwrap_or(Uint128::zero());
-
-        let accumulated_fees = MEMBER_ACCUMULATED_LATE_FEES
-            .may_load(deps.storage, (circle_id, info.sender.clone()))?
-            .unwrap_or(Uint128::zero());
-
-        let exit_penalty = compute_exit_penalty(locked, circle.exit_penalty_percent);
-        let total_deduction = accumulated_fees + exit_penalty;
-        let refund = if total_deduction >= locked {
-            Uint128::zero()
-        } else {
-            locked - total_deduction
-        };
-
-        // The accumulated fees + exit penalty stay in the pool
-        let penalty_kept = if total_deduction > locked {
-            locked
-        } else {
-            total_deduction
-        };
-        circle.total_penalties_collected = circle
-            .total_penalties_collected
-            .checked_add(penalty_kept)
-            .unwrap_or(circle.total_penalties_collected);
-
-        if !refund.is_zero() {
-            let refund_msgs = safe_refund_or_queue(
-                deps.branch(),
-                &env,
-                circle_id,
-                &info.sender,
-                refund,
-                &circle.denomination,
-            )?;
-            messages.extend(refund_msgs);
-        }
-
-        refund_amount = refund;
-
-        // Clean up: debit full locked amount from member and aggregate
-        debit_member_locked(
-            deps.storage,
-            circle_id,
-            &info.sender,
-            locked,
-            &mut circle.total_amount_locked,
+    log_event(
+        deps,
+        env,
+        circle.circle_id,
+        "member_ejected",
+        &format!(
+            "Member {} ejected at cycle {} (locked: {}, fees: {}, penalty: {})",
+            member, circle.current_cycle_index, locked, accumulated_fees, exit_penalty
+        ),
+    )?;
+    if !guarantor_drawn.is_zero() {
+        log_event(
+            deps,
+            env,
+            circle.circle_id,
+            "guarantor_stake_drawn",
+            &format!("{} of the penalty for {} drawn from their guarantor's stake", guarantor_drawn, member),
         )?;
-        MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, info.sender.clone()));
-
-        // Recalculate payout order without this member
-        circle.members_list.retain(|m| m != &info.sender);
-        circle.updated_at = env.block.time;
-
-        // Recompute max_missed_payments_allowed (dynamic from % penalty and late fee, scaled by active members)
-        let total_rounds = circle.max_members * circle.total_cycles;
-        circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
-            compute_max_missed_scaled(
-                circle.exit_penalty_percent,
-                circle.late_fee_percent,
-                circle.members_at_start,
-                circle.members_list.len() as u32,
-            ),
-            total_rounds,
-        );
-
-        // Remove from payout order for future rounds
-        if let Some(ref mut order) = circle.payout_order_list {
-            order.retain(|m| m != &info.sender);
-        }
-
-        // Recalculate payout_amount
-        circle.payout_amount = circle
-            .contribution_amount
-            .checked_mul(Uint128::from(circle.members_list.len() as u128))
-            .unwrap_or(circle.payout_amount);
-
-        // Creator exit: forfeit creator_lock_amount to remaining active members
-        if info.sender == circle.creator_address && !circle.creator_lock_amount.is_zero() {
-            let active: Vec<Addr> = circle
-                .members_list
-                .iter()
-                .filter(|m| *m != &circle.creator_address)
-                .filter(|m| {
-                    BLOCKED_MEMBERS
-                        .may_load(deps.storage, (circle_id, (*m).clone()))
-                        .unwrap_or(None)
-                        .map(|bc| bc > circle.current_cycle_index)
-                        .unwrap_or(true)
-                })
-                .cloned()
-                .collect();
-
-            if !active.is_empty() {
-                let count = Uint128::from(active.len() as u128);
-                let per_member = circle.creator_lock_amount.multiply_ratio(1u128, count.u128());
-                let remainder = circle
-                    .creator_lock_amount
-                    .checked_sub(per_member * count)
-                    .unwrap_or(Uint128::zero());
-
-                for (idx, member) in active.iter().enumerate() {
-                    let mut share = per_member;
-                    if idx == 0 {
-                        share = share.checked_add(remainder).unwrap_or(share);
-                    }
-                    if !share.is_zero() {
-                        credit_pending_payout(
-                            deps.storage,
-                            circle_id,
-                            member,
-                            share,
-                            &mut circle.total_pending_payouts,
-                        )?;
-                    }
-                }
-            }
-            circle.creator_lock_amount = Uint128::zero();
-        }
     }
 
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
-
+    // Re-emit the distribution calendar so off-chain consumers (frontend,
+    // server sync, scheduler) can re-render without waiting for the next
+    // full sync. `members_at_start` is intentionally NOT changed (it locks
+    // the schedule), so total_rounds stays put — only the per-cycle round
+    // recipients shift.
+    let start_ts = circle.first_cycle_date.unwrap_or(env.block.time);
+    let pauses = PAUSE_HISTORY
+        .may_load(deps.storage, circle.circle_id)?
+        .unwrap_or_default();
+    let rebuilt = build_distribution_calendar(circle, start_ts, &pauses);
     log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "member_exited",
+        deps,
+        env,
+        circle.circle_id,
+        "calendar_rebuilt",
         &format!(
-            "Member {} exited circle {} (started: {}, refund: {})",
-            info.sender, circle_id, started, refund_amount
+            "{{reason:\"ejection\",active_members:{},calendar:[{}]}}",
+            circle.members_list.len(),
+            rebuilt
         ),
     )?;
 
-    let resp = Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "exit_circle")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", info.sender)
-        .add_attribute("refund_amount", refund_amount.to_string());
-    Ok(resp)
-}
-
-// ---------------------------------------------------------------------------
-// Start Circle
-// ---------------------------------------------------------------------------
-
-fn execute_start_circle(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    // An ejected creator forfeits their lock like a voluntary exit, and the
+    // circle must not be left without an admin: hand over to the successor.
+    if *member == circle.creator_address {
+        forfeit_creator_lock_to_members(deps.storage, circle)?;
+        succeed_departed_creator(deps, env, circle)?;
+    }
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can start circle".to_string(),
-        });
+    // Soft warning if the roster has fallen below the configured minimum.
+    // We do not auto-cancel — that's a product decision left to the creator
+    // via CancelCircle — but the warning surfaces in the event feed so the
+    // UI can flag the circle for attention.
+    if (circle.members_list.len() as u32) < circle.min_members_required {
+        log_event(
+            deps,
+            env,
+            circle.circle_id,
+            "min_members_breach",
+            &format!(
+                "Active members ({}) below min_members_required ({}) after ejection",
+                circle.members_list.len(),
+                circle.min_members_required
+            ),
+        )?;
     }
 
-    if !matches!(
+    Ok(())
+}
+
+/// Itemized refund for `member` exiting now, with the same checks as ExitCircle.
+/// `execute_exit_circle` settles from this quote, so `SimulateExit` is exact.
+pub(crate) fn quote_exit(
+    storage: &dyn Storage,
+    circle: &Circle,
+    member: &Addr,
+) -> Result<ExitQuoteResponse, ContractError> {
+    if matches!(
         circle.circle_status,
-        CircleStatus::Open | CircleStatus::Full
+        CircleStatus::Cancelled | CircleStatus::WoundDown
     ) {
         return Err(ContractError::InvalidCircleStatus {
-            expected: "Open or Full".to_string(),
+            expected: "Draft, Open, Running or Paused".to_string(),
             actual: format!("{:?}", circle.circle_status),
         });
     }
 
-    if (circle.members_list.len() as u32) < circle.min_members_required {
-        return Err(ContractError::MinMembersNotMet {
-            required: circle.min_members_required,
-            current: circle.members_list.len() as u32,
+    if !circle.members_list.contains(member) {
+        return Err(ContractError::Unauthorized {
+            msg: "Not a member of this circle".to_string(),
         });
     }
 
-    generate_payout_order(&mut circle, &env);
+    let started = matches!(
+        circle.circle_status,
+        CircleStatus::Running | CircleStatus::Paused
+    );
+    if started && circle.strict_mode {
+        return Err(ContractError::StrictModeNoExit {
+            circle_id: circle.circle_id,
+        });
+    }
+    let has_loan = LOANS
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .map(|loan| loan.disbursed_at.is_some())
+        .unwrap_or(false);
+    if has_loan {
+        return Err(ContractError::InvalidParameters {
+            msg: "Repay the open loan before leaving the circle".to_string(),
+        });
+    }
 
-    // Always use the actual on-chain time as the start — not the pre-set start_date
-    // which may have been set to a future date at creation time.
-    let start_timestamp = env.block.time;
-    apply_running_state(&mut circle, start_timestamp);
+    let locked = current_member_lock(storage, circle.circle_id, member);
+    let is_creator = *member == circle.creator_address;
 
-    let total_rounds = circle.max_members * circle.total_cycles;
-    let end_timestamp = circle.end_date.unwrap_or(start_timestamp);
-    let archived_timestamp = Timestamp::from_seconds(
-        end_timestamp.seconds() + circle.grace_period_secs(),
-    );
-
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    if !started {
+        // Nothing is withheld before start. The creator lock comes back when a
+        // successor takes over, or through the auto-refund if the circle cancels.
+        let remaining = circle.members_list.len() as u32 - 1;
+        let creator_lock_refund = if is_creator
+            && (remaining > 0
+                || (circle.auto_refund_if_min_not_met && remaining < circle.min_members_required))
+        {
+            circle.creator_lock_amount
+        } else {
+            Uint128::zero()
+        };
+        let guarantor_refund = guarantor_share(storage, circle.circle_id, member, locked);
+        return Ok(ExitQuoteResponse {
+            member: member.clone(),
+            started,
+            locked,
+            late_fees_withheld: Uint128::zero(),
+            exit_penalty: Uint128::zero(),
+            penalty_kept: Uint128::zero(),
+            lock_refund: locked,
+            prepaid_refund: Uint128::zero(),
+            partial_refund: Uint128::zero(),
+            creator_lock_refund,
+            creator_lock_forfeited: Uint128::zero(),
+            collateral_forfeited: Uint128::zero(),
+            guarantor_refund,
+            total_refund: locked - guarantor_refund + creator_lock_refund,
+        });
+    }
 
-    let calendar_data = build_distribution_calendar(&circle, start_timestamp);
+    let (late_fees, exit_penalty, kept) = withheld_on_leave(storage, circle, member, locked)?;
+    let (prepaid, partial) = held_for_member(storage, circle, member)?;
+    let lock_refund = locked - kept;
+    let guarantor_refund = guarantor_share(storage, circle.circle_id, member, lock_refund);
+    Ok(ExitQuoteResponse {
+        member: member.clone(),
+        started,
+        locked,
+        late_fees_withheld: late_fees,
+        exit_penalty,
+        penalty_kept: kept,
+        lock_refund,
+        prepaid_refund: prepaid,
+        partial_refund: partial,
+        creator_lock_refund: Uint128::zero(),
+        creator_lock_forfeited: if is_creator {
+            circle.creator_lock_amount
+        } else {
+            Uint128::zero()
+        },
+        collateral_forfeited: payout_collateral_of(storage, circle.circle_id, member),
+        guarantor_refund,
+        total_refund: lock_refund - guarantor_refund + prepaid + partial,
+    })
+}
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "circle_started",
-        &format!("Circle {} started at {}", circle_id, start_timestamp.seconds()),
-    )?;
+/// What ejecting `member` now would settle, using the same helpers as
+/// `eject_member_from_circle`. `eligible` is the CheckAndEject test.
+pub(crate) fn quote_ejection(
+    storage: &dyn Storage,
+    circle: &Circle,
+    member: &Addr,
+) -> Result<EjectionQuoteResponse, ContractError> {
+    if !matches!(circle.circle_status, CircleStatus::Running | CircleStatus::Paused) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "distribution_calendar",
-        &format!(
-            "{{start_date:{},end_date:{},archived_date:{},calendar:[{}]}}",
-            start_timestamp.seconds(),
-            end_timestamp.seconds(),
-            archived_timestamp.seconds(),
-            calendar_data
-        ),
-    )?;
+    if !circle.members_list.contains(member) {
+        return Err(ContractError::Unauthorized {
+            msg: "Not a member of this circle".to_string(),
+        });
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "start_circle")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("start_date", start_timestamp.seconds().to_string())
-        .add_attribute("end_date", end_timestamp.seconds().to_string())
-        .add_attribute("archived_date", archived_timestamp.seconds().to_string())
-        .add_attribute("total_rounds", total_rounds.to_string()))
+    let eligible = should_eject_member(
+        storage,
+        circle.circle_id,
+        member,
+        original_lock_for_member(circle, member),
+        circle.exit_penalty_percent,
+        circle.max_missed_payments_allowed,
+    );
+    let missed_count = MEMBER_MISSED_PAYMENTS
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .map(|m| m.missed_count)
+        .unwrap_or(0);
+    let locked = current_member_lock(storage, circle.circle_id, member);
+    let (late_fees, exit_penalty, kept) = withheld_on_leave(storage, circle, member, locked)?;
+    let (prepaid, partial) = held_for_member(storage, circle, member)?;
+
+    Ok(EjectionQuoteResponse {
+        member: member.clone(),
+        eligible,
+        missed_count,
+        max_missed_payments_allowed: circle.max_missed_payments_allowed,
+        locked,
+        late_fees_withheld: late_fees,
+        exit_penalty,
+        penalty_kept: kept,
+        lock_held: locked - kept,
+        prepaid_credited: prepaid,
+        partial_credited: partial,
+        creator_lock_forfeited: if *member == circle.creator_address {
+            circle.creator_lock_amount
+        } else {
+            Uint128::zero()
+        },
+    })
 }
 
-// ---------------------------------------------------------------------------
-// Deposit Contribution
-// ---------------------------------------------------------------------------
-
-fn execute_deposit_contribution(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+/// Breakdown of what DepositContribution would charge `member` at `now`, with the
+/// handler's own checks. The handler charges from this quote; `missed_rounds` at or
+/// above `max_missed_payments_allowed` is left to the caller (the handler ejects).
+pub(crate) fn quote_deposit(
+    storage: &dyn Storage,
+    circle: &Circle,
+    member: &Addr,
+    now: Timestamp,
+    rounds: Option<u32>,
+) -> Result<DepositQuoteResponse, ContractError> {
+    let circle_id = circle.circle_id;
 
     if !matches!(circle.circle_status, CircleStatus::Running) {
         return Err(ContractError::InvalidCircleStatus {
@@ -1322,15 +1669,13 @@ fn execute_deposit_contribution(
         });
     }
 
-    if !circle.members_list.contains(&info.sender) {
+    if !circle.members_list.contains(member) {
         return Err(ContractError::Unauthorized {
             msg: "Not a member of this circle".to_string(),
         });
     }
 
-    if let Ok(Some(blocked_from_cycle)) =
-        BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, info.sender.clone()))
-    {
+    if let Some(blocked_from_cycle) = BLOCKED_MEMBERS.may_load(storage, (circle_id, member.clone()))? {
         if blocked_from_cycle <= circle.current_cycle_index {
             return Err(ContractError::InvalidParameters {
                 msg: format!("Member is blocked from cycle {} onwards", blocked_from_cycle),
@@ -1338,74 +1683,83 @@ fn execute_deposit_contribution(
         }
     }
 
-    if DEPOSITS
-        .may_load(
-            deps.storage,
-            (circle_id, info.sender.clone(), circle.current_cycle_index),
-        )?
-        .is_some()
-    {
+    if rounds == Some(0) {
+        return Err(ContractError::InvalidParameters {
+            msg: "rounds must be at least 1".to_string(),
+        });
+    }
+
+    let current_deposited = DEPOSITS
+        .may_load(storage, (circle_id, member.clone(), circle.current_cycle_index))?
+        .is_some();
+    if current_deposited && rounds.is_none() {
         return Err(ContractError::AlreadyDeposited {
-            address: info.sender.to_string(),
+            address: member.to_string(),
             cycle: circle.current_cycle_index,
         });
     }
 
-    // Last cycle member deposited for (from MEMBER_LAST_DEPOSITED_CYCLE or scan DEPOSITS for backward compat)
-    let last_deposited_cycle = MEMBER_LAST_DEPOSITED_CYCLE
-        .may_load(deps.storage, (circle_id, info.sender.clone()))?
-        .or_else(|| {
-            DEPOSITS
-                .prefix((circle_id, info.sender.clone()))
-                .range(deps.storage, None, None, Order::Descending)
-                .next()
-                .and_then(|r| r.ok())
-                .map(|(c, _)| c)
-        })
-        .unwrap_or(0);
+    // PREPAYMENT
+    // ----------
+    // `rounds` counts every round this call pays for. The current round is
+    // always settled first (with the usual catch-up below); the rest go into
+    // PREPAID_BALANCES and are consumed one per round by ProcessPayout /
+    // AdvanceRound. When the current round is already paid, all of `rounds`
+    // is prepaid. Never prepay past the last round of the schedule.
+    let prepay_rounds = if current_deposited {
+        rounds.unwrap_or(1)
+    } else {
+        rounds.unwrap_or(1) - 1
+    };
+    let prepay_amount = circle
+        .contribution_amount
+        .checked_mul(Uint128::from(prepay_rounds as u128))
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Prepayment overflow".to_string(),
+        })?;
+    if prepay_rounds > 0 {
+        let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
+        let rounds_left = (members_basis * circle.total_cycles)
+            .saturating_sub(circle.current_cycle_index);
+        let already_prepaid = prepaid_rounds(storage, circle, member)?;
+        if already_prepaid + prepay_rounds > rounds_left {
+            return Err(ContractError::InvalidParameters {
+                msg: format!(
+                    "Cannot prepay {} rounds: {} already prepaid, {} rounds left after the current one",
+                    prepay_rounds, already_prepaid, rounds_left
+                ),
+            });
+        }
+    }
+
+    if current_deposited {
+        return Ok(DepositQuoteResponse {
+            member: member.clone(),
+            cycle: circle.current_cycle_index,
+            contribution_amount: Uint128::zero(),
+            missed_rounds: 0,
+            missed_round_fees: Uint128::zero(),
+            grace_late_fee: Uint128::zero(),
+            lock_refill: Uint128::zero(),
+            prepay_rounds,
+            prepay_amount,
+            required_amount: prepay_amount,
+            partial_deposited: Uint128::zero(),
+            remaining_amount: prepay_amount,
+            on_time: true,
+        });
+    }
 
     // Rounds missed = cycles between last_deposited+1 and current-1 (inclusive)
     let rounds_missed = circle
         .current_cycle_index
-        .saturating_sub(last_deposited_cycle)
+        .saturating_sub(last_deposited_cycle(storage, circle_id, member)?)
         .saturating_sub(1);
 
-    let late_fee_per_round =
-        compute_late_fee_per_round(circle.contribution_amount, circle.late_fee_percent);
-
-    // If missed >= max_missed: add late fees for missed rounds, then eject, cannot deposit
-    if rounds_missed >= circle.max_missed_payments_allowed {
-        let late_fee_total = late_fee_per_round * Uint128::from(rounds_missed as u128);
-        let mut accumulated = MEMBER_ACCUMULATED_LATE_FEES
-            .may_load(deps.storage, (circle_id, info.sender.clone()))?
-            .unwrap_or(Uint128::zero());
-        accumulated = accumulated
-            .checked_add(late_fee_total)
-            .unwrap_or(accumulated);
-        MEMBER_ACCUMULATED_LATE_FEES.save(
-            deps.storage,
-            (circle_id, info.sender.clone()),
-            &accumulated,
-        )?;
-
-        let orig_lock = original_lock_for_member(&circle, &info.sender);
-        if should_eject_member(
-            deps.storage,
-            circle_id,
-            &info.sender,
-            orig_lock,
-            circle.exit_penalty_percent,
-            circle.max_missed_payments_allowed,
-        ) {
-            eject_member_from_circle(&mut deps, &env, &mut circle, &info.sender)?;
-            CIRCLES.save(deps.storage, circle_id, &circle)?;
-        }
-        return Err(ContractError::MaxMissedPaymentsExceeded {
-            max: circle.max_missed_payments_allowed,
-        });
-    }
-
-    let late_fee_total = late_fee_per_round * Uint128::from(rounds_missed as u128);
+    // Missed rounds under the circle's late-fee policy, plus (PerHour only) the
+    // hours this deposit is past the current round's deadline.
+    let missed_round_fees = late_fees_for_misses(circle, rounds_missed);
+    let grace_fee = grace_time_late_fee(circle, now);
 
     // CATCH-UP DEPOSIT
     // ----------------
@@ -1418,1289 +1772,1038 @@ fn execute_deposit_contribution(
     //      original_lock - if missed rounds drained 3 × C, they need 3 × C
     //      to restore. If their lock was 2 × C (creator) we still cap at the
     //      original limit so they can't accidentally over-fund.
-    //   3. Pay the accumulated late fees (1 × late_fee_per_round per miss)
-    //
-    // After a successful catch-up the member is "fresh": missed_count and
-    // accumulated_late_fees both reset to zero, lock back to original.
+    //   3. Pay the accumulated late fees (per the circle's LateFeePolicy)
     //
     // For non-creator members only - creator lock is in `creator_lock_amount`
     // and is never drained by the round-cover path.
-    let original_lock = original_lock_for_member(&circle, &info.sender);
-    let current_lock = if info.sender == circle.creator_address {
-        Uint128::zero() // creator has no MEMBER_LOCKED entry
-    } else {
-        current_member_lock(deps.storage, circle_id, &info.sender)
-    };
-    let lock_refill_needed = if info.sender == circle.creator_address {
+    let lock_refill = if *member == circle.creator_address {
         Uint128::zero()
     } else {
-        original_lock.saturating_sub(current_lock)
+        original_lock_for_member(circle, member)
+            .saturating_sub(current_member_lock(storage, circle_id, member))
     };
 
     let required_amount = circle
         .contribution_amount
-        .checked_add(lock_refill_needed)
-        .and_then(|v| v.checked_add(late_fee_total))
+        .checked_add(lock_refill)
+        .and_then(|v| v.checked_add(missed_round_fees))
+        .and_then(|v| v.checked_add(grace_fee))
+        .and_then(|v| v.checked_add(prepay_amount))
         .map_err(|_| ContractError::InvalidParameters {
             msg: "Required amount overflow".to_string(),
         })?;
+    let partial = PARTIAL_DEPOSITS
+        .may_load(storage, (circle_id, member.clone(), circle.current_cycle_index))?
+        .unwrap_or(Uint128::zero());
 
-    let payment = must_pay(&info, &circle.denomination).map_err(|_| {
-        ContractError::InsufficientFunds {
-            required: required_amount.to_string(),
-            sent: "0".to_string(),
-        }
-    })?;
+    Ok(DepositQuoteResponse {
+        member: member.clone(),
+        cycle: circle.current_cycle_index,
+        contribution_amount: circle.contribution_amount,
+        missed_rounds: rounds_missed,
+        missed_round_fees,
+        grace_late_fee: grace_fee,
+        lock_refill,
+        prepay_rounds,
+        prepay_amount,
+        required_amount,
+        partial_deposited: partial,
+        remaining_amount: required_amount.saturating_sub(partial),
+        // on_time is decided at completion, not when the first partial arrived.
+        on_time: rounds_missed == 0 && grace_fee.is_zero(),
+    })
+}
 
-    if payment < required_amount {
-        return Err(ContractError::InsufficientFunds {
-            required: required_amount.to_string(),
-            sent: payment.to_string(),
-        });
-    }
+// ---------------------------------------------------------------------------
+// Create Circle
+// ---------------------------------------------------------------------------
 
-    let is_late = rounds_missed > 0;
+/// Parameters of a new circle as CreateCircle validates and derives them.
+pub(crate) struct CircleTerms {
+    pub denomination: String,
+    pub distribution_threshold: Option<DistributionThreshold>,
+    pub collateral_policy: CollateralPolicy,
+    pub creator_lock: Uint128,
+    pub late_fee_policy: LateFeePolicy,
+    pub payout_amount: Uint128,
+    pub cycle_secs: u64,
+    pub max_missed_payments_allowed: u32,
+    pub end_date: Option<Timestamp>,
+}
 
-    // Add late fees (paid in tokens) to pool
-    if !late_fee_total.is_zero() {
-        circle.total_penalties_collected = circle
-            .total_penalties_collected
-            .checked_add(late_fee_total)
-            .map_err(|_| ContractError::InvalidParameters {
-                msg: "Penalties overflow".to_string(),
-            })?;
+/// Run every CreateCircle parameter check, collecting all failures in order instead
+/// of stopping at the first. `execute_create_circle` returns the first one, the
+/// `PreviewCreateCircle` query reports them all. Derived values are best-effort
+/// (zero) where a check they depend on failed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn check_circle_terms(
+    storage: &dyn Storage,
+    max_members: u32,
+    min_members_required: u32,
+    total_cycles: u32,
+    contribution_amount: Uint128,
+    denomination: Option<&str>,
+    exit_penalty_percent: u64,
+    late_fee_percent: u64,
+    late_fee_policy: Option<LateFeePolicy>,
+    cycle_duration_days: u32,
+    cycle_duration_seconds: Option<u64>,
+    grace_period_hours: u32,
+    grace_period_seconds: Option<u64>,
+    start_date: Option<Timestamp>,
+    visibility: &Visibility,
+    distribution_threshold: Option<DistributionThreshold>,
+    payout_order_list: Option<&[Addr]>,
+    creator_collateral_policy: Option<CollateralPolicy>,
+    join_requirements: Option<&JoinRequirements>,
+    requires_attestation: bool,
+    stake_locks: bool,
+    reserve_premium_bps: Option<u64>,
+    kind: &CircleKind,
+) -> (CircleTerms, Vec<ContractError>) {
+    let mut errors: Vec<ContractError> = vec![];
+    let mut reject = |msg: String| errors.push(ContractError::InvalidParameters { msg });
 
-        PENALTIES.save(
-            deps.storage,
-            (circle_id, info.sender.clone(), circle.current_cycle_index),
-            &PenaltyRecord {
-                member: info.sender.clone(),
-                cycle: circle.current_cycle_index,
-                amount: late_fee_total,
-                reason: format!(
-                    "Late deposit: {} rounds missed ({}% per round paid in tokens)",
-                    rounds_missed, circle.late_fee_percent / 100
-                ),
-                timestamp: env.block.time,
-            },
-        )?;
+    if max_members == 0 || min_members_required == 0 {
+        reject("max_members and min_members_required must be greater than 0".to_string());
     }
-
-    // Refill the lock that was drained covering this member's misses, and
-    // reset the per-member miss counters so they get a clean slate.
-    if !lock_refill_needed.is_zero() {
-        add_member_locked(
-            deps.storage,
-            circle_id,
-            &info.sender,
-            lock_refill_needed,
-            &mut circle.total_amount_locked,
-        )?;
+    if min_members_required > max_members {
+        reject("min_members_required cannot exceed max_members".to_string());
     }
-    if rounds_missed > 0 {
-        MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, info.sender.clone()));
-        MEMBER_MISSED_PAYMENTS.remove(deps.storage, (circle_id, info.sender.clone()));
+    if total_cycles == 0 {
+        reject("total_cycles must be greater than 0".to_string());
+    }
+    if contribution_amount.is_zero() {
+        reject("contribution_amount must be greater than 0".to_string());
+    }
+    if exit_penalty_percent > 9000 {
+        reject("exit_penalty_percent cannot exceed 90% (9000 basis points)".to_string());
+    }
+    if late_fee_percent == 0 || late_fee_percent > 5000 {
+        reject("late_fee_percent must be between 1 and 5000 basis points".to_string());
+    }
+    if exit_penalty_percent + late_fee_percent > 10000 {
+        reject("exit_penalty_percent + late_fee_percent cannot exceed 100%".to_string());
+    }
+    let late_fee_policy = late_fee_policy.unwrap_or_default();
+    match late_fee_policy {
+        LateFeePolicy::FlatPerRound => {}
+        LateFeePolicy::Escalating { step_bps } if step_bps > 5000 => {
+            reject("Escalating step_bps cannot exceed 5000 basis points".to_string());
+        }
+        LateFeePolicy::PerHour { bps_per_hour } if bps_per_hour == 0 || bps_per_hour > 5000 => {
+            reject("PerHour bps_per_hour must be between 1 and 5000 basis points".to_string());
+        }
+        LateFeePolicy::Capped { cap_bps } if cap_bps < late_fee_percent => {
+            reject("Capped cap_bps must be at least late_fee_percent".to_string());
+        }
+        _ => {}
     }
 
-    // Record deposit (only `contribution_amount` is the deposit proper;
-    // the refill goes into MEMBER_LOCKED above, the late fees into PENALTIES).
-    DEPOSITS.save(
-        deps.storage,
-        (circle_id, info.sender.clone(), circle.current_cycle_index),
-        &DepositRecord {
-            member: info.sender.clone(),
-            cycle: circle.current_cycle_index,
-            amount: circle.contribution_amount,
-            timestamp: env.block.time,
-            on_time: !is_late,
-        },
-    )?;
+    // Public circles are temporarily disabled at the contract level. Existing
+    // Public circles continue to operate; new ones are rejected. Re-enable by
+    // removing this guard once the public-circle UX is finalized.
+    if matches!(visibility, Visibility::Public) {
+        reject("Public circles are temporarily disabled. Please create a Private (invite-only) circle.".to_string());
+    }
 
-    MEMBER_LAST_DEPOSITED_CYCLE.save(
-        deps.storage,
-        (circle_id, info.sender.clone()),
-        &circle.current_cycle_index,
-    )?;
+    // Validate the payment denomination against the allow-list.
+    // - "usaf"      → native SAF (6 decimals)
+    // - the Noble IBC USDC trace on Safrochain (6 decimals)
+    // Unknown denoms are rejected up-front so a typo can't trap funds in a circle
+    // members will never be able to deposit into.
+    const SAF_DENOM: &str = "usaf";
+    const USDC_DENOM: &str =
+        "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let chosen_denom: String = match denomination {
+        None | Some("") => SAF_DENOM.to_string(),
+        Some(d) if d == SAF_DENOM || d == USDC_DENOM => d.to_string(),
+        Some(other) => {
+            reject(format!(
+                "denomination '{}' is not supported. Allowed: '{}' or '{}'.",
+                other, SAF_DENOM, USDC_DENOM
+            ));
+            other.to_string()
+        }
+    };
+    if stake_locks {
+        if chosen_denom != SAF_DENOM {
+            reject(format!("stake_locks is only available for '{}' circles", SAF_DENOM));
+        }
+        let validators = STAKING_CONFIG
+            .may_load(storage)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .validators;
+        if validators.is_empty() {
+            reject("stake_locks needs at least one platform-approved validator".to_string());
+        }
+    }
 
-    circle.total_amount_locked = circle
-        .total_amount_locked
-        .checked_add(circle.contribution_amount)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Total amount overflow".to_string(),
-        })?;
+    // Accumulating circles bank every round; no round distributes a pot.
+    if matches!(kind, CircleKind::Accumulating { .. }) && distribution_threshold.is_some() {
+        reject("distribution_threshold does not apply to accumulating circles".to_string());
+    }
 
-    if is_late {
-        circle.members_late_this_cycle.push(info.sender.clone());
-    } else {
-        circle.members_paid_this_cycle.push(info.sender.clone());
+    // Force distribution_threshold = Total for Public circles
+    let effective_threshold = match visibility {
+        Visibility::Public => Some(DistributionThreshold::Total {}),
+        Visibility::Private => {
+            if let Some(DistributionThreshold::MinMembers { count }) = distribution_threshold {
+                if count == 0 {
+                    reject("distribution_threshold MinMembers count must be > 0".to_string());
+                }
+                if count > min_members_required {
+                    reject(format!(
+                        "distribution_threshold MinMembers count ({}) cannot exceed min_members_required ({})",
+                        count, min_members_required
+                    ));
+                }
+                Some(DistributionThreshold::MinMembers { count })
+            } else {
+                distribution_threshold
+            }
+        }
+    };
+
+    // Validate payout_order_list
+    if let Some(order_list) = payout_order_list {
+        if order_list.len() as u32 != max_members {
+            reject("payout_order_list length must match max_members".to_string());
+        }
     }
 
-    let deposited_cycle = circle.current_cycle_index;
+    if let Some(min_ratio) = join_requirements.and_then(|r| r.min_on_time_ratio_bps) {
+        if min_ratio > 10000 {
+            reject("join_requirements min_on_time_ratio_bps cannot exceed 10000".to_string());
+        }
+    }
+    if reserve_premium_bps.map(|bps| bps > 2000).unwrap_or(false) {
+        reject("reserve_premium_bps cannot exceed 20% (2000 basis points)".to_string());
+    }
+    if let CircleKind::Accumulating {
+        interest_bps_per_round,
+        max_loan_bps,
+        ..
+    } = kind
+    {
+        if *interest_bps_per_round > 2000 {
+            reject("interest_bps_per_round cannot exceed 20% (2000 basis points)".to_string());
+        }
+        if *max_loan_bps == 0 || *max_loan_bps > 30000 {
+            reject("max_loan_bps must be between 1 and 30000 (3x savings)".to_string());
+        }
+    }
+    if requires_attestation {
+        let registry = PLATFORM_CONFIG
+            .may_load(storage)
+            .ok()
+            .flatten()
+            .and_then(|c| c.attestation_registry);
+        if registry.is_none() {
+            reject("requires_attestation needs a platform attestation registry".to_string());
+        }
+    }
 
-    // Rounds advance by calendar (AdvanceRound/ProcessPayout), not by deposit
+    // Creator lock from the chosen (platform-approved) collateral policy
+    let collateral_policy = creator_collateral_policy.unwrap_or_default();
+    let creator_lock =
+        match quote_creator_lock(storage, &collateral_policy, contribution_amount, max_members) {
+            Ok((lock, _)) => lock,
+            Err(err) => {
+                errors.push(err);
+                Uint128::zero()
+            }
+        };
+    let mut reject = |msg: String| errors.push(ContractError::InvalidParameters { msg });
 
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    let payout_amount = contribution_amount
+        .checked_mul(Uint128::from(max_members))
+        .unwrap_or_else(|_| {
+            reject("Payout amount overflow".to_string());
+            Uint128::zero()
+        });
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "contribution_deposited",
-        &format!(
-            "Member {} deposited {} for cycle {} (on_time: {})",
-            info.sender, circle.contribution_amount, deposited_cycle, !is_late
-        ),
-    )?;
+    let cycle_secs = cycle_duration_seconds
+        .filter(|&s| s > 0)
+        .unwrap_or(cycle_duration_days as u64 * 86400);
+    if cycle_secs == 0 {
+        reject("cycle_duration_days must be > 0, or cycle_duration_seconds must be > 0".to_string());
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "deposit_contribution")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", info.sender)
-        .add_attribute("cycle", deposited_cycle.to_string())
-        .add_attribute("amount", circle.contribution_amount.to_string())
-        .add_attribute("on_time", (!is_late).to_string()))
+    // Grace period must be strictly less than cycle duration. If they are equal,
+    // the round-end gate (block.time >= round_end) and the grace gate
+    // (block.time > grace_end) coincide, leaving a 1-tick window where
+    // advance_round / process_payout can still be rejected with
+    // "Grace period not ended" even though the calendar says the round ended.
+    // If grace > cycle, advance can never proceed when any member is missing.
+    let grace_secs = grace_period_seconds
+        .filter(|&s| s > 0)
+        .unwrap_or(grace_period_hours as u64 * 3600);
+    if cycle_secs > 0 && grace_secs >= cycle_secs {
+        reject(format!(
+            "grace_period ({}s) must be strictly less than cycle_duration ({}s)",
+            grace_secs, cycle_secs
+        ));
+    }
+
+    // Auto-calculate max_missed_payments_allowed; cap at total_rounds-1 so ejection happens before last round
+    let total_rounds = max_members.saturating_mul(total_cycles);
+    let max_missed = cap_max_missed_by_rounds(
+        compute_max_missed_for_policy(&late_fee_policy, exit_penalty_percent, late_fee_percent, grace_secs),
+        total_rounds,
+    );
+
+    let end_date = start_date.map(|start| {
+        Timestamp::from_seconds(
+            start.seconds() + (cycle_secs * max_members as u64 * total_cycles as u64),
+        )
+    });
+
+    let terms = CircleTerms {
+        denomination: chosen_denom,
+        distribution_threshold: effective_threshold,
+        collateral_policy,
+        creator_lock,
+        late_fee_policy,
+        payout_amount,
+        cycle_secs,
+        max_missed_payments_allowed: max_missed,
+        end_date,
+    };
+    (terms, errors)
 }
 
-// ---------------------------------------------------------------------------
-// Process Payout — stores to PENDING_PAYOUTS instead of direct send
-// ---------------------------------------------------------------------------
 
-fn execute_process_payout(
+#[allow(clippy::too_many_arguments)]
+fn execute_create_circle(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    circle_id: u64,
+    circle_name: String,
+    circle_description: String,
+    circle_image: Option<String>,
+    max_members: u32,
+    min_members_required: u32,
+    invite_only: bool,
+    contribution_amount: Uint128,
+    denomination: Option<String>,
+    exit_penalty_percent: u64,
+    late_fee_percent: u64,
+    total_cycles: u32,
+    cycle_duration_days: u32,
+    cycle_duration_seconds: Option<u64>,
+    start_date: Option<Timestamp>,
+    grace_period_hours: u32,
+    grace_period_seconds: Option<u64>,
+    auto_start_when_full: bool,
+    auto_start_type: Option<String>,
+    auto_start_date: Option<Timestamp>,
+    payout_order_type: PayoutOrderType,
+    payout_order_list: Option<Vec<Addr>>,
+    auto_payout_enabled: bool,
+    manual_trigger_enabled: bool,
+    emergency_stop_enabled: bool,
+    auto_refund_if_min_not_met: bool,
+    strict_mode: bool,
+    visibility: Visibility,
+    show_member_identities: bool,
+    distribution_threshold: Option<DistributionThreshold>,
+    creator_collateral_policy: Option<CollateralPolicy>,
+    late_fee_policy: Option<LateFeePolicy>,
+    join_requirements: Option<JoinRequirements>,
+    requires_attestation: bool,
+    stake_locks: bool,
+    reserve_premium_bps: Option<u64>,
+    post_payout_collateral: bool,
+    kind: CircleKind,
 ) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
-
-    if !matches!(circle.circle_status, CircleStatus::Running) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Running".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
+    // Every parameter check (shared with the PreviewCreateCircle query); the
+    // first failure is returned.
+    let (terms, errors) = check_circle_terms(
+        deps.storage,
+        max_members,
+        min_members_required,
+        total_cycles,
+        contribution_amount,
+        denomination.as_deref(),
+        exit_penalty_percent,
+        late_fee_percent,
+        late_fee_policy,
+        cycle_duration_days,
+        cycle_duration_seconds,
+        grace_period_hours,
+        grace_period_seconds,
+        start_date,
+        &visibility,
+        distribution_threshold,
+        payout_order_list.as_deref(),
+        creator_collateral_policy,
+        join_requirements.as_ref(),
+        requires_attestation,
+        stake_locks,
+        reserve_premium_bps,
+        &kind,
+    );
+    if let Some(err) = errors.into_iter().next() {
+        return Err(err);
     }
+    let CircleTerms {
+        denomination: chosen_denom,
+        distribution_threshold: effective_threshold,
+        collateral_policy,
+        creator_lock: required_creator_lock,
+        late_fee_policy,
+        payout_amount,
+        max_missed_payments_allowed: max_missed,
+        end_date,
+        ..
+    } = terms;
 
-    // Idempotency: reject if this cycle was already processed (prevents double trigger)
-    let already_processed = PAYOUTS
-        .prefix((circle_id, circle.current_cycle_index))
-        .range(deps.storage, None, None, Order::Ascending)
-        .next()
-        .is_some();
-    if already_processed {
-        return Err(ContractError::PayoutAlreadyProcessed {
-            cycle: circle.current_cycle_index,
-        });
-    }
+    // Validate payment: creator must send exactly required_creator_lock
+    let payment = must_pay(&info, &chosen_denom).map_err(|_| ContractError::InsufficientFunds {
+        required: required_creator_lock.to_string(),
+        sent: "0".to_string(),
+    })?;
 
-    // Authorization: manual_trigger_enabled means only creator can call
-    if circle.manual_trigger_enabled && info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can trigger payout (manual_trigger_enabled)".to_string(),
+    if payment < required_creator_lock {
+        return Err(ContractError::InsufficientFunds {
+            required: required_creator_lock.to_string(),
+            sent: payment.to_string(),
         });
     }
 
-    // Time gate (end-of-round): prevent back-to-back transitions that can skip rounds.
-    // `next_payout_date` is the round start; only allow payout after the round has ended.
-    if let Some(next_payout) = circle.next_payout_date {
-        let round_end = next_payout.plus_seconds(circle.cycle_duration_secs());
-        if env.block.time < round_end {
-            return Err(ContractError::CycleNotReady {
-                next_date: round_end.seconds(),
-            });
-        }
-    }
-
-    // Active members (not blocked)
-    let mut active_members: Vec<Addr> = circle
-        .members_list
-        .iter()
-        .filter(|m| {
-            BLOCKED_MEMBERS
-                .may_load(deps.storage, (circle_id, (*m).clone()))
-                .unwrap_or(None)
-                .map(|bc| bc > circle.current_cycle_index)
-                .unwrap_or(true)
-        })
-        .cloned()
-        .collect();
+    let circle_id = CIRCLE_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or_else(|| ContractError::InvalidParameters {
+            msg: "Circle ID overflow".to_string(),
+        })?;
 
-    let missing_members: Vec<Addr> = active_members
-        .iter()
-        .filter(|m| {
-            DEPOSITS
-                .may_load(deps.storage, (circle_id, (*m).clone(), circle.current_cycle_index))
-                .unwrap_or(None)
-                .is_none()
-        })
-        .cloned()
-        .collect();
+    let final_payout_order = match payout_order_type {
+        PayoutOrderType::RandomOrder => None,
+        PayoutOrderType::PredefinedOrder => payout_order_list,
+    };
 
-    // If any missing and grace period still active, block payout
-    if let Some(next_payout) = circle.next_payout_date {
-        let grace_end = next_payout.plus_seconds(circle.grace_period_secs());
-        if env.block.time <= grace_end && !missing_members.is_empty() {
-            return Err(ContractError::InvalidParameters {
-                msg: "Grace period not ended for all missing members".to_string(),
-            });
-        }
-    }
+    // manual_trigger_enabled only meaningful for Private circles
+    let effective_manual_trigger = match visibility {
+        Visibility::Public => false,
+        Visibility::Private => manual_trigger_enabled,
+    };
 
-    let mut locked_used_total = Uint128::zero();
+    let circle = Circle {
+        circle_id,
+        circle_name,
+        circle_description,
+        circle_image,
+        creator_address: info.sender.clone(),
+        created_at: env.block.time,
+        updated_at: env.block.time,
+        max_members,
+        min_members_required,
+        invite_only,
+        members_list: vec![info.sender.clone()],
+        pending_members: vec![],
+        contribution_amount,
+        denomination: chosen_denom.clone(),
+        payout_amount,
+        exit_penalty_percent,
+        late_fee_percent,
+        platform_fee_percent: PLATFORM_CONFIG.load(deps.storage)?.platform_fee_percent,
+        max_missed_payments_allowed: max_missed,
+        total_cycles,
+        cycle_duration_days,
+        cycle_duration_seconds: cycle_duration_seconds.unwrap_or(0),
+        start_date,
+        first_cycle_date: start_date,
+        next_payout_date: start_date,
+        end_date,
+        grace_period_hours,
+        grace_period_seconds: grace_period_seconds.unwrap_or(0),
+        auto_start_when_full,
+        auto_start_type,
+        auto_start_date,
+        payout_order_type,
+        payout_order_list: final_payout_order,
+        auto_payout_enabled,
+        manual_trigger_enabled: effective_manual_trigger,
+        emergency_stop_enabled,
+        emergency_stop_triggered: false,
+        auto_refund_if_min_not_met,
+        strict_mode,
+        escrow_address: Some(env.contract.address.clone()),
+        total_amount_locked: required_creator_lock, // creator lock already in contract
+        total_penalties_collected: Uint128::zero(),
+        total_platform_fees_collected: Uint128::zero(),
+        total_pending_payouts: Uint128::zero(),
+        withdrawal_lock: false,
+        refund_mode: RefundMode::FullRefund,
+        circle_status: CircleStatus::Draft,
+        current_cycle_index: 0,
+        cycles_completed: 0,
+        members_paid_this_cycle: vec![],
+        members_late_this_cycle: vec![],
+        visibility,
+        show_member_identities,
+        creator_lock_amount: required_creator_lock,
+        creator_collateral_policy: collateral_policy,
+        late_fee_policy,
+        distribution_threshold: effective_threshold,
+        join_requirements,
+        requires_attestation,
+        stake_locks,
+        lock_stake: None,
+        reserve: reserve_premium_bps.map(|premium_bps| CircleReserve {
+            premium_bps,
+            ..CircleReserve::default()
+        }),
+        post_payout_collateral,
+        total_payout_collateral: Uint128::zero(),
+        predecessor_id: None, // Linked by RenewCircle
+        successor_id: None,
+        savings: match kind {
+            CircleKind::Accumulating { .. } => Some(SavingsPool::default()),
+            CircleKind::Rotating => None,
+        },
+        kind,
+        total_paused_seconds: 0,
+        total_prepaid: Uint128::zero(),
+        total_partial_deposits: Uint128::zero(),
+        members_at_start: None, // Set at StartCircle when member count is known
+    };
 
-    // Handle missing members (past grace): accumulate late fees, check ejection, use locked funds
-    for member in &missing_members {
-        let late_fee_per_round =
-            compute_late_fee_per_round(circle.contribution_amount, circle.late_fee_percent);
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    CIRCLE_COUNTER.save(deps.storage, &circle_id)?;
 
-        let mut accumulated = MEMBER_ACCUMULATED_LATE_FEES
-            .may_load(deps.storage, (circle_id, member.clone()))?
-            .unwrap_or(Uint128::zero());
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "circle_created",
+        &format!(
+            "Circle {} created by {} (creator_lock: {}, max_missed: {})",
+            circle_id, info.sender, required_creator_lock, max_missed
+        ),
+    )?;
 
-        // Only count once per cycle
-        let mut missed = MEMBER_MISSED_PAYMENTS
-            .may_load(deps.storage, (circle_id, member.clone()))?
-            .unwrap_or(MemberMissedPayments {
-                member: member.clone(),
-                missed_count: 0,
-                last_missed_cycle: None,
-                last_fee_round: None,
-            });
+    Ok(Response::new()
+        .add_attribute("action", "create_circle")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("creator", info.sender)
+        .add_attribute("creator_lock_amount", required_creator_lock.to_string())
+        .add_attribute("max_missed_payments_allowed", max_missed.to_string()))
+}
 
-        if missed.last_missed_cycle != Some(circle.current_cycle_index) {
-            missed.missed_count += 1;
-            missed.last_missed_cycle = Some(circle.current_cycle_index);
-            accumulated = accumulated
-                .checked_add(late_fee_per_round)
-                .unwrap_or(accumulated);
+// ---------------------------------------------------------------------------
+// Join Circle (merges former LockJoinDeposit)
+// ---------------------------------------------------------------------------
 
-            MEMBER_ACCUMULATED_LATE_FEES.save(
-                deps.storage,
-                (circle_id, member.clone()),
-                &accumulated,
-            )?;
-            MEMBER_MISSED_PAYMENTS.save(
-                deps.storage,
-                (circle_id, member.clone()),
-                &missed,
-            )?;
-        }
+fn execute_join_circle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    invite_code: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    let reservation = PRIVATE_RESERVATIONS.may_load(deps.storage, (circle_id, info.sender.clone()))?;
+    // Pruning expired reservations may reopen a Full circle.
+    let live = live_reservations(deps.storage, &mut circle, env.block.time)?;
+
+    // A circle Full only through reservations still admits their holders.
+    let accepting = matches!(circle.circle_status, CircleStatus::Draft | CircleStatus::Open)
+        || (circle.circle_status == CircleStatus::Full && reservation.is_some());
+    if !accepting {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft or Open".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
 
-        // Determine if this member should be ejected NOW.
-        //
-        // Two independent ejection triggers, by design:
-        //   (a) `should_eject_member` - the standard "missed too many" or
-        //       "accumulated fees exhausted the security" check.
-        //   (b) NEW: their remaining lock can no longer cover this round's
-        //       contribution. Without this guard, a member whose
-        //       MEMBER_LOCKED was depleted by earlier misses (but who hasn't
-        //       yet hit `max_missed_payments_allowed`) would block the round
-        //       forever - `use_locked_amount_for_member` returns 0, no
-        //       synthetic deposit is written, and the post-loop
-        //       `deposits_count < active_members.len()` check fails.
-        //
-        // Per the security contract: a member's lock IS their commitment;
-        // when it can't cover a round, they've effectively defaulted, and
-        // the circle MUST keep running for the remaining members.
-        let orig_lock = original_lock_for_member(&circle, member);
-        let standard_eject = should_eject_member(
-            deps.storage,
-            circle_id,
-            member,
-            orig_lock,
-            circle.exit_penalty_percent,
-            circle.max_missed_payments_allowed,
-        );
-        let lock_insufficient = if member == &circle.creator_address {
-            // Creator's lock is in `creator_lock_amount`; can't be drained
-            // per-miss the way MEMBER_LOCKED is. Skip this trigger for them
-            // (creator handling is via cancel/finalize, not per-round eject).
-            false
-        } else {
-            current_member_lock(deps.storage, circle_id, member) < circle.contribution_amount
-        };
+    if circle.members_list.contains(&info.sender) {
+        return Err(ContractError::AlreadyMember {
+            address: info.sender.to_string(),
+        });
+    }
 
-        if standard_eject || lock_insufficient {
-            eject_member_from_circle(&mut deps, &env, &mut circle, member)?;
-            // Ejected: their MEMBER_LOCKED stays in the circle (becomes part
-            // of `total_penalties_collected` per eject logic). No synthetic
-            // deposit for this round - active_members.len() will drop on
-            // the post-loop recount and the round proceeds with the rest.
-            continue;
-        }
+    ensure_attested(deps.as_ref(), &circle, &info.sender)?;
+    if let Some(ref requirements) = circle.join_requirements {
+        check_join_requirements(deps.as_ref(), &env, circle_id, requirements, &info.sender)?;
+    }
 
-        // Use locked funds to cover missed deposit. Creator's lock is never
-        // touched (lives in creator_lock_amount, not MEMBER_LOCKED).
-        let used = if member == &circle.creator_address {
-            Uint128::zero()
-        } else {
-            use_locked_amount_for_member(
-                deps.storage,
+    // A seat reserved via AddPrivateMember must be accepted before its deadline.
+    // The expired reservation itself is pruned by `live_reservations` on the next
+    // successful join, AddPrivateMember or waitlist call.
+    if let Some(ref r) = reservation {
+        if env.block.time >= r.expires_at {
+            return Err(ContractError::ReservationExpired {
                 circle_id,
-                member,
-                circle.contribution_amount,
-            )?
-        };
-        if !used.is_zero() {
-            locked_used_total = locked_used_total
-                .checked_add(used)
-                .map_err(|_| ContractError::InvalidParameters {
-                    msg: "Locked funds overflow".to_string(),
-                })?;
-            // Record synthetic deposit so this round remains auditable (no perceived "skipped" round).
-            DEPOSITS.save(
-                deps.storage,
-                (circle_id, member.clone(), circle.current_cycle_index),
-                &DepositRecord {
-                    member: member.clone(),
-                    cycle: circle.current_cycle_index,
-                    amount: used,
-                    timestamp: env.block.time,
-                    on_time: false,
-                },
-            )?;
+                expired_at: r.expires_at.seconds(),
+            });
         }
+        PRIVATE_RESERVATIONS.remove(deps.storage, (circle_id, info.sender.clone()));
     }
 
-    // Also use locked funds from previously blocked members
-    let blocked_members_list: Vec<Addr> = BLOCKED_MEMBERS
-        .prefix(circle_id)
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|(m, _)| m))
-        .collect();
+    // Seats held by other members' unexpired reservations are not available.
+    let held_by_others = live.iter().filter(|m| **m != info.sender).count();
+    if (circle.members_list.len() + held_by_others) as u32 >= circle.max_members {
+        return Err(ContractError::CircleFull {
+            max: circle.max_members,
+        });
+    }
 
-    for blocked_member in blocked_members_list {
-        if missing_members.iter().any(|m| m == &blocked_member) {
-            continue;
-        }
-        if let Ok(Some(bc)) =
-            BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, blocked_member.clone()))
-        {
-            if bc <= circle.current_cycle_index {
-                let used = use_locked_amount_for_member(
-                    deps.storage,
-                    circle_id,
-                    &blocked_member,
-                    circle.contribution_amount,
-                )?;
-                if !used.is_zero() {
-                    locked_used_total = locked_used_total
-                        .checked_add(used)
-                        .map_err(|_| ContractError::InvalidParameters {
-                            msg: "Locked funds overflow".to_string(),
-                        })?;
-                    // Record synthetic deposit for blocked member coverage in this round.
-                    DEPOSITS.save(
-                        deps.storage,
-                        (circle_id, blocked_member.clone(), circle.current_cycle_index),
-                        &DepositRecord {
-                            member: blocked_member.clone(),
-                            cycle: circle.current_cycle_index,
-                            amount: used,
-                            timestamp: env.block.time,
-                            on_time: false,
-                        },
-                    )?;
-                }
-            }
+    // Invite/private check. A direct invite (pending_members) takes precedence;
+    // otherwise a shareable invite code is redeemed and one use consumed.
+    let mut redeemed_code: Option<String> = None;
+    if circle.invite_only || matches!(circle.visibility, Visibility::Private) {
+        if circle.pending_members.contains(&info.sender) {
+            circle.pending_members.retain(|m| m != &info.sender);
+        } else if let Some(code) = invite_code {
+            redeemed_code = Some(redeem_invite_code(deps.storage, &env, circle_id, &code)?);
+        } else {
+            return Err(ContractError::InviteOnly { circle_id });
         }
     }
 
-    // Recompute active members after ejections
-    active_members = circle
-        .members_list
-        .iter()
-        .filter(|m| {
-            BLOCKED_MEMBERS
-                .may_load(deps.storage, (circle_id, (*m).clone()))
-                .unwrap_or(None)
-                .map(|bc| bc > circle.current_cycle_index)
-                .unwrap_or(true)
-        })
-        .cloned()
-        .collect();
-
-    let deposits_count = active_members
-        .iter()
-        .filter(|m| {
-            DEPOSITS
-                .may_load(deps.storage, (circle_id, (*m).clone(), circle.current_cycle_index))
-                .unwrap_or(None)
-                .is_some()
-        })
-        .count();
+    // Require member to send contribution_amount as join deposit (locked security),
+    // less whatever their guarantor has posted.
+    let guarantee = GUARANTEES.may_load(deps.storage, (circle_id, info.sender.clone()))?;
+    let required = circle.contribution_amount
+        - guarantee.as_ref().map(|g| g.posted).unwrap_or_default();
+    let payment = if required.is_zero() {
+        Uint128::zero()
+    } else {
+        must_pay(&info, &circle.denomination).map_err(|_| ContractError::InsufficientFunds {
+            required: required.to_string(),
+            sent: "0".to_string(),
+        })?
+    };
 
-    // Distribution threshold check (based on active members)
-    let active_count = active_members.len() as u32;
-    if active_count == 0 {
-        return Err(ContractError::InvalidParameters {
-            msg: "No active members for distribution".to_string(),
+    if payment < required {
+        return Err(ContractError::InsufficientFunds {
+            required: required.to_string(),
+            sent: payment.to_string(),
         });
     }
-    let round_in_cycle = ((circle.current_cycle_index - 1) % active_count) + 1;
-    let min_round_for_distribution =
-        distribution_min_round_for_active(&circle.distribution_threshold, active_count);
 
-    if round_in_cycle < min_round_for_distribution {
+    // Lock the join deposit
+    if MEMBER_LOCKED_AMOUNTS
+        .may_load(deps.storage, (circle_id, info.sender.clone()))?
+        .is_some()
+    {
         return Err(ContractError::InvalidParameters {
-            msg: format!(
-                "Distribution only from round {} (current round in cycle: {})",
-                min_round_for_distribution, round_in_cycle
-            ),
+            msg: "Join deposit already locked".to_string(),
         });
     }
 
-    // All active members must have a real-or-synthetic deposit for this round.
-    //
-    // `deposits_count` is recomputed AFTER the missing-member loop, so it
-    // includes the synthetic DEPOSITS rows just written for members whose
-    // locked join-deposit was consumed to cover the round.
-    //
-    // The previous formulation `deposits_count + |missing_members ∩ active|`
-    // was tautological — since `missing_members ⊆ active_members`, the sum
-    // always equalled `active_members.len()` and the contract therefore
-    // accepted process_payout even when a missing member had ZERO locked
-    // funds left and no synthetic deposit was created. That over-credited
-    // the pool by the missing contribution amount, which is the root cause
-    // of the "PENDING_PAYOUTS > contract bank balance" symptom on Withdraw.
-    if deposits_count < active_members.len() {
-        return Err(ContractError::InvalidParameters {
-            msg: format!(
-                "Not all active members have contributed: need {}, have {} (some missing members had no locked funds left to cover this round)",
-                active_members.len(),
-                deposits_count
-            ),
-        });
+    add_member_locked(
+        deps.storage,
+        circle_id,
+        &info.sender,
+        circle.contribution_amount,
+        &mut circle.total_amount_locked,
+    )?;
+    if let Some(mut guarantee) = guarantee {
+        guarantee.joined = true;
+        GUARANTEES.save(deps.storage, (circle_id, info.sender.clone()), &guarantee)?;
     }
 
-    // Total threshold at last round of cycle: split equally among ALL active members
-    let is_total_at_last_round = is_total_style_threshold(&circle.distribution_threshold)
-        && round_in_cycle == min_round_for_distribution;
+    // Add member
+    circle.members_list.push(info.sender.clone());
+    circle.updated_at = env.block.time;
 
-    // Calculate payout amount (total pool)
-    // For Total threshold at last round: sum from ALL rounds in the cycle. Each round: active_count * contribution
-    // (everyone contributed either by deposit or locked). Otherwise: only current round (one recipient per round).
-    let base_payout = if is_total_at_last_round {
-        let rounds_in_cycle = active_count; // Total threshold: min_round = active_count
-        let round_amount = circle
-            .contribution_amount
-            .checked_mul(Uint128::from(active_count as u128))
-            .map_err(|_| ContractError::InvalidParameters {
-                msg: "Payout amount overflow".to_string(),
-            })?;
-        round_amount
-            .checked_mul(Uint128::from(rounds_in_cycle as u128))
-            .map_err(|_| ContractError::InvalidParameters {
-                msg: "Payout amount overflow".to_string(),
-            })?
+    // Joining directly takes the member off the waitlist; their pre-lock goes back.
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(entry) = take_waitlist_entry(deps.storage, circle_id, &info.sender)? {
+        messages.extend(safe_refund_or_queue(
+            deps.branch(),
+            &env,
+            circle_id,
+            &info.sender,
+            entry.prelocked,
+            &circle.denomination,
+        )?);
+    }
+    messages.extend(settle_joined_status(&mut deps, &env, &mut circle, held_by_others)?);
+
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    let event_type = if matches!(circle.circle_status, CircleStatus::Running)
+        && circle.current_cycle_index == 1
+    {
+        "circle_auto_started"
     } else {
-        circle
-            .contribution_amount
-            .checked_mul(Uint128::from(deposits_count as u128))
-            .map_err(|_| ContractError::InvalidParameters {
-                msg: "Payout amount overflow".to_string(),
-            })?
+        "member_joined"
     };
 
-    let mut payout_amount = base_payout
-        .checked_add(locked_used_total)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Payout amount overflow".to_string(),
-        })?;
-
-    let platform_fee = payout_amount.multiply_ratio(circle.platform_fee_percent, 10000u64);
-    payout_amount = payout_amount
-        .checked_sub(platform_fee)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Payout amount underflow".to_string(),
-        })?;
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        event_type,
+        &format!("Member {} joined circle {} (locked: {})", info.sender, circle_id, circle.contribution_amount),
+    )?;
 
-    circle.total_platform_fees_collected = circle
-        .total_platform_fees_collected
-        .checked_add(platform_fee)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Platform fees overflow".to_string(),
-        })?;
+    let mut resp = Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "join_circle")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("locked_amount", circle.contribution_amount.to_string());
+    if let Some(code_hash) = redeemed_code {
+        resp = resp.add_attribute("invite_code_hash", code_hash);
+    }
+    Ok(resp)
+}
 
-    let liquid_balance = deps
-        .querier
-        .query_balance(&env.contract.address, &circle.denomination)
-        .map_err(|e| ContractError::InvalidParameters {
-            msg: format!("Balance query failed: {}", e),
-        })?;
-    let available = liquid_balance.amount;
+// ---------------------------------------------------------------------------
+// Invite Member
+// ---------------------------------------------------------------------------
 
-    // Any PENDING_PAYOUTS already credited (from earlier rounds, not yet
-    // withdrawn) must remain backed by bank balance — we cannot credit so
-    // much new payout that the contract can no longer satisfy outstanding
-    // withdrawals. Subtract them from the spendable budget for this TX.
-    let spendable_for_new_credits = available
-        .checked_sub(circle.total_pending_payouts)
-        .unwrap_or(Uint128::zero());
+fn execute_invite_member(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member_address: Addr,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    // Compute total we will add to PENDING_PAYOUTS and verify contract has sufficient balance
-    let mut total_to_credit = payout_amount;
-    let mut creator_refund_amount = Uint128::zero();
-    // Total rounds = ACTIVE roster at start × total_cycles. Using `max_members`
-    // here was wrong for circles that auto-started with fewer members than
-    // the cap: e.g. a circle configured `max_members=3, total_cycles=2` that
-    // actually started with 2 members has 4 real rounds, not 6. With the old
-    // formula, `current_cycle_index=4 >= 6` was false on the FINAL round,
-    // the pre-computation block was skipped, `creator_refund_amount` stayed
-    // at zero, and the finalize block below (which uses the correct
-    // `members_at_start` formula) zeroed `creator_lock_amount` without ever
-    // crediting the creator's refund - silently stranding it on the bank.
-    // Must mirror the second `total_rounds` computation further down.
-    let members_basis_pre = circle.members_at_start.unwrap_or(circle.max_members);
-    let total_rounds = members_basis_pre * circle.total_cycles;
-    if circle.current_cycle_index >= total_rounds {
-        let locked_sum: Uint128 = MEMBER_LOCKED_AMOUNTS
-            .prefix(circle_id)
-            .range(deps.storage, None, None, Order::Ascending)
-            .filter_map(|r| r.ok())
-            .map(|(_, amt)| amt)
-            .fold(Uint128::zero(), |a, b| a.saturating_add(b));
-        // Add creator lock only when creator has no MEMBER_LOCKED_AMOUNTS entry.
-        // For old circles where creator lock may have been partially consumed, cap refund by available funds.
-        let creator_has_locked_entry = MEMBER_LOCKED_AMOUNTS
-            .may_load(deps.storage, (circle_id, circle.creator_address.clone()))?
-            .is_some();
-        total_to_credit = total_to_credit
-            .checked_add(locked_sum)
-            .unwrap_or(total_to_credit);
-        total_to_credit = total_to_credit
-            .checked_add(circle.total_penalties_collected)
-            .unwrap_or(total_to_credit);
-        if !creator_has_locked_entry && !circle.creator_lock_amount.is_zero() {
-            // Refund at most the still-spendable funds AFTER honouring
-            // outstanding pending payouts and the mandatory final credits.
-            // Using `available` alone (the prior behaviour) over-credited the
-            // creator when earlier cycles had already committed funds to
-            // PENDING_PAYOUTS, producing the bank-vs-pending mismatch.
-            creator_refund_amount = spendable_for_new_credits
-                .checked_sub(total_to_credit)
-                .unwrap_or(Uint128::zero());
-            if creator_refund_amount > circle.creator_lock_amount {
-                creator_refund_amount = circle.creator_lock_amount;
-            }
-            total_to_credit = total_to_credit
-                .checked_add(creator_refund_amount)
-                .unwrap_or(total_to_credit);
-        }
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can invite members".to_string(),
+        });
     }
-    if spendable_for_new_credits < total_to_credit {
-        return Err(ContractError::InsufficientContractBalance {
-            required: total_to_credit.to_string(),
-            available: available.to_string(),
+
+    if !circle.invite_only {
+        return Err(ContractError::InvalidParameters {
+            msg: "Circle is not invite-only".to_string(),
         });
     }
 
-    // Capture single-recipient address for logging (none for Total threshold final-round split).
-    let mut single_recipient: Option<Addr> = None;
+    let validated_addr = deps.api.addr_validate(member_address.as_str())?;
 
-    // Outbound bank messages produced by this transaction (e.g. platform fee
-    // drain at finalization). Withdrawals are still pulled via Withdraw — this
-    // only carries amounts the contract autonomously sends out (platform fees,
-    // dust). Building it here keeps the response self-contained.
-    let mut outbound_messages: Vec<CosmosMsg> = Vec::new();
-    let mut platform_fees_sent: Uint128 = Uint128::zero();
+    if circle.members_list.contains(&validated_addr) {
+        return Err(ContractError::AlreadyMember {
+            address: validated_addr.to_string(),
+        });
+    }
 
-    if is_total_at_last_round {
-        // Split payout_amount equally among all active members
-        let member_count = active_members.len() as u128;
-        if member_count == 0 {
-            return Err(ContractError::InvalidParameters {
-                msg: "No active members for distribution".to_string(),
-            });
-        }
-        let amount_per_member = payout_amount
-            .checked_div(Uint128::from(member_count))
-            .unwrap_or(Uint128::zero());
-        let remainder = payout_amount
-            .checked_sub(amount_per_member * Uint128::from(member_count))
-            .unwrap_or(Uint128::zero());
+    if circle.pending_members.contains(&validated_addr) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Member already invited".to_string(),
+        });
+    }
 
-        for (idx, member) in active_members.iter().enumerate() {
-            let mut amt = amount_per_member;
-            if idx == 0 {
-                amt = amt.checked_add(remainder).unwrap_or(amt);
-            }
-            if amt.is_zero() {
-                continue;
-            }
+    circle.pending_members.push(validated_addr.clone());
+    circle.updated_at = env.block.time;
 
-            PAYOUTS.save(
-                deps.storage,
-                (circle_id, circle.current_cycle_index, member.clone()),
-                &PayoutRecord {
-                    cycle: circle.current_cycle_index,
-                    recipient: member.clone(),
-                    amount: amt,
-                    timestamp: env.block.time,
-                    transaction_hash: None,
-                },
-            )?;
-
-            credit_pending_payout(
-                deps.storage,
-                circle_id,
-                member,
-                amt,
-                &mut circle.total_pending_payouts,
-            )?;
-        }
-    } else {
-        // One recipient per round (MinMembers or None)
-        let recipient = if let Some(ref order_list) = circle.payout_order_list {
-            let active_order: Vec<Addr> = order_list
-                .iter()
-                .filter(|m| active_members.iter().any(|a| a == *m))
-                .cloned()
-                .collect();
-            if active_order.is_empty() {
-                return Err(ContractError::InvalidParameters {
-                    msg: "No active members in payout order".to_string(),
-                });
-            }
-            let index = (round_in_cycle as usize - 1) % active_order.len();
-            active_order[index].clone()
-        } else {
-            return Err(ContractError::InvalidParameters {
-                msg: "Payout order not set".to_string(),
-            });
-        };
-        single_recipient = Some(recipient.clone());
-
-        PAYOUTS.save(
-            deps.storage,
-            (circle_id, circle.current_cycle_index, recipient.clone()),
-            &PayoutRecord {
-                cycle: circle.current_cycle_index,
-                recipient: recipient.clone(),
-                amount: payout_amount,
-                timestamp: env.block.time,
-                transaction_hash: None,
-            },
-        )?;
-
-        credit_pending_payout(
-            deps.storage,
-            circle_id,
-            &recipient,
-            payout_amount,
-            &mut circle.total_pending_payouts,
-        )?;
-    }
-
-    // Subtract the contributed deposits from total_amount_locked (excluding locked join deposits used above — those were already deducted by use_locked_amount_for_member)
-    let deposit_outflow = base_payout
-        .checked_add(locked_used_total)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Outflow overflow".to_string(),
-        })?;
-
-    circle.total_amount_locked = circle
-        .total_amount_locked
-        .checked_sub(deposit_outflow)
-        .unwrap_or(Uint128::zero());
-
-    circle.cycles_completed += 1;
-    circle.members_paid_this_cycle.clear();
-    circle.members_late_this_cycle.clear();
-
-    // Check if last round across all cycles. Round count is based on the
-    // active member count locked at start (members_at_start), NOT the
-    // configured cap (max_members). Using max_members caused circles that
-    // started with fewer members than the cap to keep advancing past their
-    // calendar's last round — status never transitioned to Finalizing, the
-    // cron kept queuing actions, and the UI displayed phantom rounds with
-    // no recipient.
-    let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
-    let total_rounds = members_basis * circle.total_cycles;
-    if circle.current_cycle_index >= total_rounds {
-        // Set Finalizing — Completed only when all have withdrawn (contract balance = 0)
-        circle.circle_status = CircleStatus::Finalizing;
-
-        // Final distribution (automatic): (1) Creator gets creator_lock back (if not in MEMBER_LOCKED); (2) Each member gets their join-deposit lock back; (3) Penalties split equally
-        let mut total_distributed = Uint128::zero();
-
-        // 1. Creator gets creator lock back (capped for old circles by actual available funds).
-        if !creator_refund_amount.is_zero() && active_members.iter().any(|m| m == &circle.creator_address) {
-            credit_pending_payout(
-                deps.storage,
-                circle_id,
-                &circle.creator_address,
-                creator_refund_amount,
-                &mut circle.total_pending_payouts,
-            )?;
-            total_distributed = total_distributed
-                .checked_add(creator_refund_amount)
-                .unwrap_or(total_distributed);
-        }
-        circle.creator_lock_amount = Uint128::zero();
-
-        // 2. Each member gets their own join-deposit lock back (from MEMBER_LOCKED_AMOUNTS)
-        let locked_entries: Vec<(Addr, Uint128)> = MEMBER_LOCKED_AMOUNTS
-            .prefix(circle_id)
-            .range(deps.storage, None, None, Order::Ascending)
-            .filter_map(|res| res.ok())
-            .collect();
-        for (member, locked) in &locked_entries {
-            if locked.is_zero() {
-                continue;
-            }
-            credit_pending_payout(
-                deps.storage,
-                circle_id,
-                member,
-                *locked,
-                &mut circle.total_pending_payouts,
-            )?;
-            total_distributed = total_distributed.checked_add(*locked).unwrap_or(total_distributed);
-        }
-        for (m, _) in &locked_entries {
-            MEMBER_LOCKED_AMOUNTS.remove(deps.storage, (circle_id, m.clone()));
-        }
-
-        // 3. Penalties split equally among all active members
-        let pool = circle.total_penalties_collected;
-
-        if !pool.is_zero() && !active_members.is_empty() {
-            let member_count = Uint128::from(active_members.len() as u128);
-            let amount_per_member = pool
-                .checked_div(member_count)
-                .unwrap_or(Uint128::zero());
-            let remainder = pool
-                .checked_sub(amount_per_member * member_count)
-                .unwrap_or(Uint128::zero());
-
-            for (idx, member) in active_members.iter().enumerate() {
-                let mut amt = amount_per_member;
-                if idx == 0 {
-                    amt = amt.checked_add(remainder).unwrap_or(amt);
-                }
-                if !amt.is_zero() {
-                    credit_pending_payout(
-                        deps.storage,
-                        circle_id,
-                        member,
-                        amt,
-                        &mut circle.total_pending_payouts,
-                    )?;
-                }
-            }
-        }
-
-        circle.total_amount_locked = Uint128::zero();
-        circle.total_penalties_collected = Uint128::zero();
-
-        // Drain accumulated platform fees to the platform address. Before this
-        // fix, `total_platform_fees_collected` was a pure accumulator with no
-        // outflow path — the corresponding native coins remained on the
-        // contract bank balance after finalization, breaking the documented
-        // invariant in state.rs ("Becomes Completed when contract balance = 0").
-        // We send the fees automatically as part of finalization so new circles
-        // are clean by construction.
-        let fees = circle.total_platform_fees_collected;
-        if !fees.is_zero() {
-            let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
-            outbound_messages.push(CosmosMsg::Bank(BankMsg::Send {
-                to_address: platform_addr.to_string(),
-                amount: vec![Coin {
-                    denom: circle.denomination.clone(),
-                    amount: fees,
-                }],
-            }));
-            platform_fees_sent = fees;
-            circle.total_platform_fees_collected = Uint128::zero();
-        }
-
-        log_event(
-            &mut deps,
-            &env,
-            circle_id,
-            "circle_completed",
-            &format!(
-                "Circle {} completed. All payouts stored in PENDING_PAYOUTS. Platform fees drained: {} usaf.",
-                circle_id, platform_fees_sent
-            ),
-        )?;
-    } else {
-        // Round-progression invariant: current_cycle_index must advance by
-        // exactly 1 per transaction. Catches accidental skips from refactors.
-        let prev_round = circle.current_cycle_index;
-        circle.current_cycle_index = prev_round.checked_add(1).ok_or_else(|| {
-            ContractError::InvalidParameters {
-                msg: "current_cycle_index overflow".to_string(),
-            }
-        })?;
-        if circle.current_cycle_index != prev_round + 1 {
-            return Err(ContractError::InvalidParameters {
-                msg: format!(
-                    "Round progression invariant violated: {} -> {} (must be +1)",
-                    prev_round, circle.current_cycle_index
-                ),
-            });
-        }
-        if let Some(current_date) = circle.next_payout_date {
-            circle.next_payout_date = Some(Timestamp::from_seconds(
-                current_date.seconds() + circle.cycle_duration_secs(),
-            ));
-        }
-    }
-
-    circle.updated_at = env.block.time;
     CIRCLES.save(deps.storage, circle_id, &circle)?;
 
-    let recipient_attr = if is_total_at_last_round {
-        format!("{} members", active_members.len())
-    } else {
-        single_recipient
-            .as_ref()
-            .map(|r| r.to_string())
-            .unwrap_or_else(|| "unknown".to_string())
-    };
-    let log_msg = if is_total_at_last_round {
-        format!(
-            "Payout processed for round {} to all {} members ({} usaf total pending withdrawal)",
-            circle.cycles_completed,
-            active_members.len(),
-            payout_amount
-        )
-    } else {
-        format!(
-            "Payout processed for round {} to {} ({} usaf pending withdrawal)",
-            circle.cycles_completed, recipient_attr, payout_amount
-        )
-    };
-
     log_event(
         &mut deps,
         &env,
         circle_id,
-        "payout_processed",
-        &log_msg,
+        "member_invited",
+        &format!("Member {} invited to circle {}", validated_addr, circle_id),
     )?;
 
     Ok(Response::new()
-        .add_messages(outbound_messages)
-        .add_attribute("action", "process_payout")
+        .add_attribute("action", "invite_member")
         .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("cycle", circle.cycles_completed.to_string())
-        .add_attribute("recipient", recipient_attr)
-        .add_attribute("amount", payout_amount.to_string())
-        .add_attribute("platform_fees_sent", platform_fees_sent.to_string())
-        .add_attribute("pending_withdrawal", "true"))
+        .add_attribute("member", validated_addr))
 }
 
 // ---------------------------------------------------------------------------
-// Advance Round — move to next round without payout (for Total/MinMembers threshold)
+// Invite Codes — shareable links redeemed by hash preimage in JoinCircle
 // ---------------------------------------------------------------------------
 
-fn execute_advance_round(
+fn execute_register_invite_code(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     circle_id: u64,
+    code_hash: String,
+    max_uses: u32,
+    expires_at: Option<Timestamp>,
 ) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    if !matches!(circle.circle_status, CircleStatus::Running) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Running".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
-    }
-
-    // Authorization: manual_trigger_enabled means only creator can call (same as ProcessPayout)
-    if circle.manual_trigger_enabled && info.sender != circle.creator_address {
+    if info.sender != circle.creator_address {
         return Err(ContractError::Unauthorized {
-            msg: "Only creator can advance round (manual_trigger_enabled)".to_string(),
+            msg: "Only creator can register invite codes".to_string(),
         });
     }
 
-    // Time gate (end-of-round): prevent back-to-back transitions that can skip rounds.
-    // `next_payout_date` is the round start; only allow advancing after the round has ended.
-    if let Some(next_payout) = circle.next_payout_date {
-        let round_end = next_payout.plus_seconds(circle.cycle_duration_secs());
-        if env.block.time < round_end {
-            return Err(ContractError::CycleNotReady {
-                next_date: round_end.seconds(),
-            });
-        }
-    }
-
-    // Active members (not blocked) — used for round_in_cycle and min_round
-    let active_members: Vec<Addr> = circle
-        .members_list
-        .iter()
-        .filter(|m| {
-            BLOCKED_MEMBERS
-                .may_load(deps.storage, (circle_id, (*m).clone()))
-                .unwrap_or(None)
-                .map(|bc| bc > circle.current_cycle_index)
-                .unwrap_or(true)
-        })
-        .cloned()
-        .collect();
-
-    if active_members.is_empty() {
+    if !(circle.invite_only || matches!(circle.visibility, Visibility::Private)) {
         return Err(ContractError::InvalidParameters {
-            msg: "No active members".to_string(),
+            msg: "Invite codes are only used by invite-only or private circles".to_string(),
         });
     }
 
-    let active_count = active_members.len() as u32;
-    let round_in_cycle = ((circle.current_cycle_index - 1) % active_count) + 1;
-    let min_round_for_distribution =
-        distribution_min_round_for_active(&circle.distribution_threshold, active_count);
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Draft | CircleStatus::Open | CircleStatus::Full
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft, Open or Full".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
 
-    if round_in_cycle >= min_round_for_distribution {
+    if max_uses == 0 {
         return Err(ContractError::InvalidParameters {
-            msg: format!(
-                "AdvanceRound only when round_in_cycle < {} (current: {}). Use ProcessPayout for distribution round.",
-                min_round_for_distribution, round_in_cycle
-            ),
+            msg: "max_uses must be greater than 0".to_string(),
         });
     }
 
-    // Process missing members: add late fees, check ejection, use locked funds (calendar-based advance)
-    let missing_members: Vec<Addr> = active_members
-        .iter()
-        .filter(|m| {
-            DEPOSITS
-                .may_load(deps.storage, (circle_id, (*m).clone(), circle.current_cycle_index))
-                .unwrap_or(None)
-                .is_none()
-        })
-        .cloned()
-        .collect();
-
-    // Match ProcessPayout semantics: if members are still missing, do not apply
-    // missed-payment late fees before due date + grace period.
-    if let Some(next_payout) = circle.next_payout_date {
-        let grace_end = next_payout.plus_seconds(circle.grace_period_secs());
-        if env.block.time <= grace_end && !missing_members.is_empty() {
+    if let Some(expiry) = expires_at {
+        if expiry <= env.block.time {
             return Err(ContractError::InvalidParameters {
-                msg: "Grace period not ended for all missing members".to_string(),
+                msg: "expires_at must be in the future".to_string(),
             });
         }
     }
 
-    let mut locked_used_in_advance = Uint128::zero();
-    for member in &missing_members {
-        let late_fee_per_round =
-            compute_late_fee_per_round(circle.contribution_amount, circle.late_fee_percent);
-
-        let mut accumulated = MEMBER_ACCUMULATED_LATE_FEES
-            .may_load(deps.storage, (circle_id, member.clone()))?
-            .unwrap_or(Uint128::zero());
-
-        let mut missed = MEMBER_MISSED_PAYMENTS
-            .may_load(deps.storage, (circle_id, member.clone()))?
-            .unwrap_or(MemberMissedPayments {
-                member: member.clone(),
-                missed_count: 0,
-                last_missed_cycle: None,
-                last_fee_round: None,
-            });
-
-        if missed.last_missed_cycle != Some(circle.current_cycle_index) {
-            missed.missed_count += 1;
-            missed.last_missed_cycle = Some(circle.current_cycle_index);
-            accumulated = accumulated
-                .checked_add(late_fee_per_round)
-                .unwrap_or(accumulated);
-
-            MEMBER_ACCUMULATED_LATE_FEES.save(
-                deps.storage,
-                (circle_id, member.clone()),
-                &accumulated,
-            )?;
-            MEMBER_MISSED_PAYMENTS.save(
-                deps.storage,
-                (circle_id, member.clone()),
-                &missed,
-            )?;
-        }
-
-        // Force-eject when lock is insufficient to cover this round - same
-        // rationale as in execute_process_payout's missing-members loop:
-        // rounds must always progress, the member's MEMBER_LOCKED is their
-        // commitment, and a depleted lock means they've defaulted on the
-        // protocol contract. See the longer comment in process_payout.
-        let orig_lock = original_lock_for_member(&circle, member);
-        let standard_eject = should_eject_member(
-            deps.storage,
-            circle_id,
-            member,
-            orig_lock,
-            circle.exit_penalty_percent,
-            circle.max_missed_payments_allowed,
-        );
-        let lock_insufficient = if member == &circle.creator_address {
-            false
-        } else {
-            current_member_lock(deps.storage, circle_id, member) < circle.contribution_amount
-        };
-        if standard_eject || lock_insufficient {
-            eject_member_from_circle(&mut deps, &env, &mut circle, member)?;
-            continue;
-        }
-
-        // Never use creator's lock - creator lock must stay intact
-        if member != &circle.creator_address {
-            let used = use_locked_amount_for_member(
-                deps.storage,
-                circle_id,
-                member,
-                circle.contribution_amount,
-            )?;
-            locked_used_in_advance = locked_used_in_advance
-                .checked_add(used)
-                .unwrap_or(locked_used_in_advance);
-            if !used.is_zero() {
-                // Record synthetic deposit to preserve round-by-round audit trail.
-                DEPOSITS.save(
-                    deps.storage,
-                    (circle_id, member.clone(), circle.current_cycle_index),
-                    &DepositRecord {
-                        member: member.clone(),
-                        cycle: circle.current_cycle_index,
-                        amount: used,
-                        timestamp: env.block.time,
-                        on_time: false,
-                    },
-                )?;
-            }
-        }
+    let code_hash = normalize_invite_code_hash(&code_hash)?;
+    if INVITE_CODES.has(deps.storage, (circle_id, code_hash.clone())) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Invite code already registered".to_string(),
+        });
     }
 
-    // Also use locked from previously blocked members for this round
-    let blocked_members_list: Vec<Addr> = BLOCKED_MEMBERS
-        .prefix(circle_id)
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|(m, _)| m))
-        .collect();
+    INVITE_CODES.save(
+        deps.storage,
+        (circle_id, code_hash.clone()),
+        &InviteCode {
+            code_hash: code_hash.clone(),
+            uses_remaining: max_uses,
+            expires_at,
+            created_at: env.block.time,
+        },
+    )?;
 
-    for blocked_member in blocked_members_list {
-        if missing_members.iter().any(|m| m == &blocked_member) {
-            continue;
-        }
-        if let Ok(Some(bc)) =
-            BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, blocked_member.clone()))
-        {
-            if bc <= circle.current_cycle_index {
-                let used = use_locked_amount_for_member(
-                    deps.storage,
-                    circle_id,
-                    &blocked_member,
-                    circle.contribution_amount,
-                )?;
-                locked_used_in_advance = locked_used_in_advance
-                    .checked_add(used)
-                    .unwrap_or(locked_used_in_advance);
-                if !used.is_zero() {
-                    // Record synthetic deposit for blocked member coverage in this round.
-                    DEPOSITS.save(
-                        deps.storage,
-                        (circle_id, blocked_member.clone(), circle.current_cycle_index),
-                        &DepositRecord {
-                            member: blocked_member.clone(),
-                            cycle: circle.current_cycle_index,
-                            amount: used,
-                            timestamp: env.block.time,
-                            on_time: false,
-                        },
-                    )?;
-                }
-            }
-        }
-    }
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "invite_code_registered",
+        &format!("Invite code {} registered ({} uses)", code_hash, max_uses),
+    )?;
 
-    // Keep total_amount_locked in sync: locked used for missed deposits is no longer "locked"
-    if !locked_used_in_advance.is_zero() {
-        circle.total_amount_locked = circle
-            .total_amount_locked
-            .checked_sub(locked_used_in_advance)
-            .unwrap_or(Uint128::zero());
-    }
+    Ok(Response::new()
+        .add_attribute("action", "register_invite_code")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("code_hash", code_hash)
+        .add_attribute("max_uses", max_uses.to_string()))
+}
 
-    let total_rounds = circle.max_members * circle.total_cycles;
-    if circle.current_cycle_index >= total_rounds {
-        return Err(ContractError::InvalidParameters {
-            msg: "Circle has no more rounds to advance".to_string(),
-        });
-    }
+fn execute_revoke_invite_code(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    code_hash: String,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    // Round-progression invariant: current_cycle_index must advance by
-    // exactly 1 per transaction. Catches accidental skips from refactors.
-    let prev_round = circle.current_cycle_index;
-    circle.current_cycle_index = prev_round.checked_add(1).ok_or_else(|| {
-        ContractError::InvalidParameters {
-            msg: "current_cycle_index overflow".to_string(),
-        }
-    })?;
-    if circle.current_cycle_index != prev_round + 1 {
-        return Err(ContractError::InvalidParameters {
-            msg: format!(
-                "Round progression invariant violated: {} -> {} (must be +1)",
-                prev_round, circle.current_cycle_index
-            ),
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can revoke invite codes".to_string(),
         });
     }
-    if let Some(current_date) = circle.next_payout_date {
-        circle.next_payout_date = Some(Timestamp::from_seconds(
-            current_date.seconds() + circle.cycle_duration_secs(),
-        ));
-    }
 
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    let code_hash = normalize_invite_code_hash(&code_hash)?;
+    if !INVITE_CODES.has(deps.storage, (circle_id, code_hash.clone())) {
+        return Err(ContractError::InvalidInviteCode { circle_id });
+    }
+    INVITE_CODES.remove(deps.storage, (circle_id, code_hash.clone()));
 
     log_event(
         &mut deps,
         &env,
         circle_id,
-        "round_advanced",
-        &format!(
-            "Advanced to round {} (no payout — distribution at round {})",
-            circle.current_cycle_index, min_round_for_distribution
-        ),
+        "invite_code_revoked",
+        &format!("Invite code {} revoked", code_hash),
     )?;
 
     Ok(Response::new()
-        .add_attribute("action", "advance_round")
+        .add_attribute("action", "revoke_invite_code")
         .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("new_cycle_index", circle.current_cycle_index.to_string()))
+        .add_attribute("code_hash", code_hash))
 }
 
 // ---------------------------------------------------------------------------
-// Withdraw — member claims all pending payouts at once
+// Waitlist — FIFO queue for circles with no free seat, backfilled on exit
 // ---------------------------------------------------------------------------
 
-fn execute_withdraw(
+fn execute_join_waitlist(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     circle_id: u64,
+    invite_code: Option<String>,
 ) -> Result<Response, ContractError> {
-    // Validate circle exists
     let mut circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    let pending = debit_pending_payout(
-        deps.storage,
-        circle_id,
-        &info.sender,
-        &mut circle.total_pending_payouts,
-    )?;
-
-    if pending.is_zero() {
-        return Err(ContractError::NoPendingPayouts {});
-    }
-
-    // When Finalizing and this is the last withdrawal, only flip to Completed
-    // if the documented invariant in state.rs actually holds: contract bank
-    // balance, AFTER the BankMsg::Send queued below settles, must be zero AND
-    // no undrained platform fees remain. The previous check (only
-    // `total_pending_payouts.is_zero()`) let the status flip to Completed
-    // while real native funds were still held by the contract (the "Completed
-    // with 26 SAF" bug). Anything residual is recoverable via
-    // `WithdrawPlatformFees` and `SweepDust`.
-    if circle.circle_status == CircleStatus::Finalizing
-        && circle.total_pending_payouts.is_zero()
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Draft | CircleStatus::Open | CircleStatus::Full
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft, Open or Full".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    if circle.members_list.contains(&info.sender) {
+        return Err(ContractError::AlreadyMember {
+            address: info.sender.to_string(),
+        });
+    }
+
+    ensure_attested(deps.as_ref(), &circle, &info.sender)?;
+    if let Some(ref requirements) = circle.join_requirements {
+        check_join_requirements(deps.as_ref(), &env, circle_id, requirements, &info.sender)?;
+    }
+
+    let reserved = live_reservations(deps.storage, &mut circle, env.block.time)?;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    if reserved.contains(&info.sender) {
+        return Err(ContractError::InvalidParameters {
+            msg: "You hold a seat reservation; use AcceptInvite instead".to_string(),
+        });
+    }
+    if ((circle.members_list.len() + reserved.len()) as u32) < circle.max_members {
+        return Err(ContractError::InvalidParameters {
+            msg: "Circle has a free seat; use JoinCircle instead".to_string(),
+        });
+    }
+
+    let mut waitlist = WAITLIST.may_load(deps.storage, circle_id)?.unwrap_or_default();
+    if waitlist.iter().any(|e| e.member == info.sender) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Already on the waitlist".to_string(),
+        });
+    }
+    if waitlist.len() as u32 >= circle.max_members {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Waitlist is full ({} entries)", waitlist.len()),
+        });
+    }
+
+    // Same invite gate as JoinCircle, so a promoted entrant never needs a second invite.
+    if (circle.invite_only || matches!(circle.visibility, Visibility::Private))
+        && !circle.pending_members.contains(&info.sender)
     {
-        let bal = deps
-            .querier
-            .query_balance(&env.contract.address, &circle.denomination)?;
-        let post_send = bal.amount.saturating_sub(pending);
-        if post_send.is_zero() && circle.total_platform_fees_collected.is_zero() {
-            circle.circle_status = CircleStatus::Completed;
+        match invite_code {
+            Some(code) => {
+                redeem_invite_code(deps.storage, &env, circle_id, &code)?;
+            }
+            None => return Err(ContractError::InviteOnly { circle_id }),
         }
     }
 
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    // Optional pre-lock: exactly contribution_amount, or nothing.
+    let prelocked = if info.funds.is_empty() {
+        Uint128::zero()
+    } else {
+        let payment = must_pay(&info, &circle.denomination).map_err(|_| {
+            ContractError::InsufficientFunds {
+                required: circle.contribution_amount.to_string(),
+                sent: "0".to_string(),
+            }
+        })?;
+        if payment < circle.contribution_amount {
+            return Err(ContractError::InsufficientFunds {
+                required: circle.contribution_amount.to_string(),
+                sent: payment.to_string(),
+            });
+        }
+        if payment > circle.contribution_amount {
+            return Err(ContractError::InvalidParameters {
+                msg: format!(
+                    "Pre-lock must be exactly contribution_amount ({})",
+                    circle.contribution_amount
+                ),
+            });
+        }
+        payment
+    };
+
+    waitlist.push(WaitlistEntry {
+        member: info.sender.clone(),
+        prelocked,
+        joined_at: env.block.time,
+    });
+    let position = waitlist.len();
+    WAITLIST.save(deps.storage, circle_id, &waitlist)?;
 
     log_event(
         &mut deps,
         &env,
         circle_id,
-        "withdrawal",
-        &format!("Member {} withdrew {} usaf", info.sender, pending),
+        "waitlist_joined",
+        &format!(
+            "{} joined waitlist at position {} (prelocked: {})",
+            info.sender, position, prelocked
+        ),
     )?;
 
     Ok(Response::new()
-        .add_message(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: vec![Coin {
-                denom: circle.denomination.clone(),
-                amount: pending,
-            }],
-        })
-        .add_attribute("action", "withdraw")
+        .add_attribute("action", "join_waitlist")
         .add_attribute("circle_id", circle_id.to_string())
         .add_attribute("member", info.sender)
-        .add_attribute("amount", pending.to_string()))
+        .add_attribute("position", position.to_string())
+        .add_attribute("prelocked", prelocked.to_string()))
 }
 
-// ---------------------------------------------------------------------------
-// Check And Eject — permissionless ejection check
-// ---------------------------------------------------------------------------
-
-fn execute_check_and_eject(
+fn execute_leave_waitlist(
     mut deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     circle_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
-
-    if !matches!(circle.circle_status, CircleStatus::Running | CircleStatus::Paused) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Running or Paused".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
-    }
-
-    let members_snapshot: Vec<Addr> = circle.members_list.clone();
-    let mut ejected_count = 0u32;
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    for member in &members_snapshot {
-        let orig_lock = original_lock_for_member(&circle, member);
-        if should_eject_member(
-            deps.storage,
-            circle_id,
-            member,
-            orig_lock,
-            circle.exit_penalty_percent,
-            circle.max_missed_payments_allowed,
-        ) {
-            eject_member_from_circle(&mut deps, &env, &mut circle, member)?;
-            ejected_count += 1;
+    let entry = take_waitlist_entry(deps.storage, circle_id, &info.sender)?.ok_or_else(|| {
+        ContractError::InvalidParameters {
+            msg: "Not on the waitlist".to_string(),
         }
-    }
+    })?;
 
-    if ejected_count > 0 {
-        // `payout_order_list`, `payout_amount`, the `calendar_rebuilt` event,
-        // and the min-members breach warning are all already handled per-eject
-        // inside `eject_member_from_circle`. Just persist the aggregated state
-        // and bump `updated_at` so the off-chain sync notices the change.
-        circle.updated_at = env.block.time;
-        CIRCLES.save(deps.storage, circle_id, &circle)?;
-    }
+    let messages = safe_refund_or_queue(
+        deps.branch(),
+        &env,
+        circle_id,
+        &info.sender,
+        entry.prelocked,
+        &circle.denomination,
+    )?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "waitlist_left",
+        &format!("{} left waitlist (refund: {})", info.sender, entry.prelocked),
+    )?;
 
     Ok(Response::new()
-        .add_attribute("action", "check_and_eject")
+        .add_messages(messages)
+        .add_attribute("action", "leave_waitlist")
         .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("ejected_count", ejected_count.to_string()))
+        .add_attribute("member", info.sender)
+        .add_attribute("refund_amount", entry.prelocked.to_string()))
 }
 
 // ---------------------------------------------------------------------------
-// Cancel Circle — extended to allow running circles (creator forfeits lock)
+// Exit Circle
 // ---------------------------------------------------------------------------
 
-fn execute_cancel_circle(
+fn execute_exit_circle(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
@@ -2708,208 +2811,240 @@ fn execute_cancel_circle(
 ) -> Result<Response, ContractError> {
     let mut circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can cancel circle".to_string(),
-        });
-    }
+    // Checks and the itemized refund; `SimulateExit` runs the same.
+    let quote = quote_exit(deps.storage, &circle, &info.sender)?;
+    let started = quote.started;
+    // A loan request not yet approved goes with the member.
+    LOANS.remove(deps.storage, (circle_id, info.sender.clone()));
 
-    let is_running = matches!(
-        circle.circle_status,
-        CircleStatus::Running | CircleStatus::Paused
-    );
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut refund_amount = Uint128::zero();
 
-    if !matches!(
-        circle.circle_status,
-        CircleStatus::Draft | CircleStatus::Open | CircleStatus::Full | CircleStatus::Running | CircleStatus::Paused
-    ) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Draft, Open, Full, Running or Paused".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
-    }
+    if !started {
+        // Before start: full refund of locked amount, no penalty
+        if let Ok(Some(locked)) =
+            MEMBER_LOCKED_AMOUNTS.may_load(deps.storage, (circle_id, info.sender.clone()))
+        {
+            refund_amount = refund_guaranteed_lock(deps.storage, &mut circle, &info.sender, locked)?;
+            debit_member_locked(
+                deps.storage,
+                circle_id,
+                &info.sender,
+                locked,
+                &mut circle.total_amount_locked,
+            )?;
 
-    // After at least one distribution, cancel is not allowed — circle must complete or use emergency procedures
-    if is_running {
-        let max_cycle = circle
-            .current_cycle_index
-            .max(circle.cycles_completed)
-            .max(1);
-        let mut has_distributed = false;
-        for cycle in 1..=max_cycle {
-            if PAYOUTS
-                .prefix((circle_id, cycle))
-                .range(deps.storage, None, None, Order::Ascending)
-                .next()
-                .is_some()
-            {
-                has_distributed = true;
-                break;
+            if !refund_amount.is_zero() {
+                let refund_msgs = safe_refund_or_queue(
+                    deps.branch(),
+                    &env,
+                    circle_id,
+                    &info.sender,
+                    refund_amount,
+                    &circle.denomination,
+                )?;
+                messages.extend(refund_msgs);
             }
         }
-        if has_distributed {
-            return Err(ContractError::CancelNotAllowedAfterDistribution {});
-        }
-    }
-
-    circle.circle_status = CircleStatus::Cancelled;
-    circle.updated_at = env.block.time;
 
-    let mut messages: Vec<CosmosMsg> = Vec::new();
+        // Update status
+        circle.members_list.retain(|m| m != &info.sender);
+        circle.updated_at = env.block.time;
 
-    if is_running {
-        // Cancelling a running circle is the creator's choice — so members
-        // should be made whole. The old code path (a) deducted an exit penalty
-        // from each member's lock refund (unfair: the creator cancelled, not
-        // the member), (b) never refunded the current-cycle deposits made by
-        // anyone, (c) never zeroed `creator_lock_amount`, and (d) used direct
-        // BankMsg sends for the lock refund — so the creator (no
-        // MEMBER_LOCKED_AMOUNTS entry) got NOTHING and the UI had no Withdraw
-        // button. This rewrite credits everything via PENDING_PAYOUTS so a
-        // single Withdraw call by each member recovers what they're owed,
-        // and balances out to bank balance == sum(pending) on success.
+        // Backfill the freed seat from the head of the waitlist.
+        messages.extend(promote_from_waitlist(&mut deps, &env, &mut circle)?);
 
-        let active_members: Vec<Addr> = circle
-            .members_list
-            .iter()
-            .filter(|m| m.as_ref() != circle.creator_address.as_str())
-            .filter(|m| {
-                BLOCKED_MEMBERS
-                    .may_load(deps.storage, (circle_id, (*m).clone()))
-                    .unwrap_or(None)
-                    .is_none()
-            })
-            .cloned()
-            .collect();
+        // Creator leaving before start: nothing is at stake yet, so the
+        // creator lock is refunded and the successor takes over.
+        if info.sender == circle.creator_address && !circle.members_list.is_empty() {
+            let creator_amount = circle.creator_lock_amount;
+            circle.total_amount_locked = circle.total_amount_locked.saturating_sub(creator_amount);
+            circle.creator_lock_amount = Uint128::zero();
+            refund_amount += creator_amount;
+            messages.extend(safe_refund_or_queue(
+                deps.branch(),
+                &env,
+                circle_id,
+                &info.sender,
+                creator_amount,
+                &circle.denomination,
+            )?);
+            succeed_departed_creator(&mut deps, &env, &mut circle)?;
+        }
 
-        // 1. Creator forfeits creator_lock_amount → distributed to active
-        //    non-creator members via PENDING_PAYOUTS. Cancellation penalty.
-        let creator_lock = circle.creator_lock_amount;
-        if !creator_lock.is_zero() && !active_members.is_empty() {
-            let count = Uint128::from(active_members.len() as u128);
-            let per_member = creator_lock.multiply_ratio(1u128, count.u128());
-            let remainder = creator_lock
-                .checked_sub(per_member * count)
-                .unwrap_or(Uint128::zero());
+        if (circle.members_list.len() as u32) < circle.max_members
+            && circle.circle_status == CircleStatus::Full
+        {
+            circle.circle_status = CircleStatus::Open;
+        }
 
-            for (idx, member) in active_members.iter().enumerate() {
-                let mut share = per_member;
-                if idx == 0 {
-                    share = share.checked_add(remainder).unwrap_or(share);
-                }
-                if !share.is_zero() {
-                    credit_pending_payout(
+        if (circle.members_list.len() as u32) < circle.min_members_required
+            && circle.auto_refund_if_min_not_met
+        {
+            circle.circle_status = CircleStatus::Cancelled;
+            let locked_entries: Vec<(Addr, Uint128)> = MEMBER_LOCKED_AMOUNTS
+                .prefix(circle_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter_map(|res| res.ok().map(|(m, a)| (m, a)))
+                .collect();
+            for (member, amount) in locked_entries {
+                if !amount.is_zero() {
+                    let own = refund_guaranteed_lock(deps.storage, &mut circle, &member, amount)?;
+                    let refund_msgs = safe_refund_or_queue(
+                        deps.branch(),
+                        &env,
+                        circle_id,
+                        &member,
+                        own,
+                        &circle.denomination,
+                    )?;
+                    messages.extend(refund_msgs);
+                    debit_member_locked(
                         deps.storage,
                         circle_id,
-                        member,
-                        share,
-                        &mut circle.total_pending_payouts,
+                        &member,
+                        amount,
+                        &mut circle.total_amount_locked,
                     )?;
                 }
             }
-        }
-        // Always zero out, whether or not there were members to receive it —
-        // otherwise the field stays stale and future invariant checks lie.
-        circle.creator_lock_amount = Uint128::zero();
-
-        // 2. Refund each non-creator member's FULL locked join-deposit via
-        //    PENDING_PAYOUTS (no exit penalty when the creator is the one
-        //    cancelling). Creator has no MEMBER_LOCKED entry — their initial
-        //    "lock" is `creator_lock_amount`, already handled above.
-        let member_list_snapshot: Vec<Addr> = circle.members_list.clone();
-        for member in &member_list_snapshot {
-            let locked = MEMBER_LOCKED_AMOUNTS
-                .may_load(deps.storage, (circle_id, member.clone()))?
-                .unwrap_or(Uint128::zero());
-            if !locked.is_zero() {
-                credit_pending_payout(
-                    deps.storage,
-                    circle_id,
-                    member,
-                    locked,
-                    &mut circle.total_pending_payouts,
-                )?;
-                debit_member_locked(
-                    deps.storage,
+            close_guarantees(deps.storage, &mut circle)?;
+            if !circle.creator_lock_amount.is_zero() {
+                let creator_amount = circle.creator_lock_amount;
+                let refund_msgs = safe_refund_or_queue(
+                    deps.branch(),
+                    &env,
                     circle_id,
-                    member,
-                    locked,
-                    &mut circle.total_amount_locked,
+                    &circle.creator_address,
+                    creator_amount,
+                    &circle.denomination,
                 )?;
+                messages.extend(refund_msgs);
             }
-            MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, member.clone()));
+            messages.extend(refund_waitlist(&mut deps, &env, &circle)?);
         }
 
-        // 3. Refund every deposit already made in the current (unfinished)
-        //    cycle. Without this the funds sit on the contract forever — the
-        //    cycle's payout will never happen, but `total_amount_locked` and
-        //    the bank balance still hold them. Crediting via PENDING_PAYOUTS
-        //    lets each depositor Withdraw the exact amount they put in.
-        //
-        //    DEPOSITS is keyed `(circle_id, member, cycle)`. The codebase
-        //    loads it by exact key per-member rather than iterating with
-        //    `prefix()`, so do the same here.
-        let unfinished_cycle = circle.current_cycle_index;
-        if unfinished_cycle > 0 {
-            for depositor in &member_list_snapshot {
-                let deposit_record = DEPOSITS.may_load(
-                    deps.storage,
-                    (circle_id, depositor.clone(), unfinished_cycle),
-                )?;
-                let amount = deposit_record.map(|r| r.amount).unwrap_or(Uint128::zero());
-                if amount.is_zero() {
-                    continue;
-                }
+        if circle.members_list.len() == 1 && circle.circle_status == CircleStatus::Open {
+            circle.circle_status = CircleStatus::Draft;
+        }
+    } else {
+        // After start (strict mode was rejected by `quote_exit`)
+        let locked = quote.locked;
+
+        // The accumulated fees + exit penalty stay in the pool, and so does any
+        // post-payout collateral (it backed the rounds the member walks away from).
+        take_payout_collateral(deps.storage, &mut circle, &info.sender);
+        circle.total_penalties_collected = circle
+            .total_penalties_collected
+            .checked_add(quote.penalty_kept + quote.collateral_forfeited)
+            .unwrap_or(circle.total_penalties_collected);
+
+        // Unused prepaid rounds and an unfinished partial deposit are the
+        // member's own money, returned in full.
+        take_prepaid(deps.storage, &mut circle, &info.sender)?;
+        take_partial_deposit(deps.storage, &mut circle, &info.sender)?;
+        let guarantor = GUARANTEES
+            .may_load(deps.storage, (circle_id, info.sender.clone()))?
+            .filter(|g| g.joined)
+            .map(|g| g.guarantor);
+        let own_lock =
+            refund_guaranteed_lock(deps.storage, &mut circle, &info.sender, quote.lock_refund)?;
+        let refund = quote.total_refund;
+        let delegated_before = circle
+            .lock_stake
+            .as_ref()
+            .map(|stake| stake.delegated)
+            .unwrap_or_default();
+
+        if !refund.is_zero() {
+            if circle.lock_stake.is_some() {
+                // The lock may still be delegated: settle through Withdraw,
+                // which holds the lock-backed part until the undelegation
+                // below matures.
                 credit_pending_payout(
                     deps.storage,
                     circle_id,
-                    depositor,
-                    amount,
+                    &info.sender,
+                    refund,
                     &mut circle.total_pending_payouts,
                 )?;
-            }
-        }
-    } else {
-        // Before start: refund all join deposits
-        let locked_entries: Vec<(Addr, Uint128)> = MEMBER_LOCKED_AMOUNTS
-            .prefix(circle_id)
-            .range(deps.storage, None, None, Order::Ascending)
-            .filter_map(|res| res.ok().map(|(m, a)| (m, a)))
-            .collect();
-
-        for (member, amount) in locked_entries {
-            if !amount.is_zero() {
+            } else {
                 let refund_msgs = safe_refund_or_queue(
                     deps.branch(),
                     &env,
                     circle_id,
-                    &member,
-                    amount,
+                    &info.sender,
+                    refund,
                     &circle.denomination,
                 )?;
                 messages.extend(refund_msgs);
-                debit_member_locked(
-                    deps.storage,
-                    circle_id,
-                    &member,
-                    amount,
-                    &mut circle.total_amount_locked,
-                )?;
             }
         }
 
-        // Refund creator lock
-        if !circle.creator_lock_amount.is_zero() {
-            let creator_amount = circle.creator_lock_amount;
-            let refund_msgs = safe_refund_or_queue(
-                deps.branch(),
-                &env,
+        refund_amount = refund;
+
+        // Clean up: debit full locked amount from member and aggregate
+        debit_member_locked(
+            deps.storage,
+            circle_id,
+            &info.sender,
+            locked,
+            &mut circle.total_amount_locked,
+        )?;
+        MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, info.sender.clone()));
+
+        // Recalculate payout order without this member
+        circle.members_list.retain(|m| m != &info.sender);
+        circle.updated_at = env.block.time;
+
+        // Recompute max_missed_payments_allowed (dynamic from % penalty and late fee, scaled by active members)
+        let total_rounds = circle.max_members * circle.total_cycles;
+        circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
+            compute_max_missed_scaled(
+                max_missed_base_for_circle(&circle),
+                circle.members_at_start,
+                circle.members_list.len() as u32,
+            ),
+            total_rounds,
+        );
+
+        // Remove from payout order for future rounds
+        if let Some(ref mut order) = circle.payout_order_list {
+            order.retain(|m| m != &info.sender);
+        }
+
+        // Recalculate payout_amount
+        circle.payout_amount = circle
+            .contribution_amount
+            .checked_mul(Uint128::from(circle.members_list.len() as u128))
+            .unwrap_or(circle.payout_amount);
+
+        // Creator exit: forfeit creator_lock_amount to remaining active members,
+        // then hand the circle to the successor so it keeps an admin.
+        if info.sender == circle.creator_address {
+            forfeit_creator_lock_to_members(deps.storage, &mut circle)?;
+            succeed_departed_creator(&mut deps, &env, &mut circle)?;
+        }
+        messages.extend(sync_lock_stake(&mut deps, &env, &mut circle)?);
+        // What this exit undelegated backs the returned lock.
+        if let Some(delegated) = circle.lock_stake.as_ref().map(|stake| stake.delegated) {
+            let mut lock_backed = delegated_before.saturating_sub(delegated);
+            hold_lock_backed(
+                deps.storage,
                 circle_id,
-                &circle.creator_address,
-                creator_amount,
-                &circle.denomination,
+                &info.sender,
+                own_lock,
+                &mut lock_backed,
             )?;
-            messages.extend(refund_msgs);
+            if let Some(guarantor) = guarantor {
+                hold_lock_backed(
+                    deps.storage,
+                    circle_id,
+                    &guarantor,
+                    quote.lock_refund - own_lock,
+                    &mut lock_backed,
+                )?;
+            }
         }
     }
 
@@ -2919,24 +3054,27 @@ fn execute_cancel_circle(
         &mut deps,
         &env,
         circle_id,
-        "circle_cancelled",
+        "member_exited",
         &format!(
-            "Circle {} cancelled by {} (was_running: {})",
-            circle_id, info.sender, is_running
+            "Member {} exited circle {} (started: {}, refund: {})",
+            info.sender, circle_id, started, refund_amount
         ),
     )?;
 
-    Ok(Response::new()
+    let resp = Response::new()
         .add_messages(messages)
-        .add_attribute("action", "cancel_circle")
-        .add_attribute("circle_id", circle_id.to_string()))
+        .add_attribute("action", "exit_circle")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("refund_amount", refund_amount.to_string());
+    Ok(resp)
 }
 
 // ---------------------------------------------------------------------------
-// Pause / Unpause
+// Start Circle
 // ---------------------------------------------------------------------------
 
-fn execute_pause_circle(
+fn execute_start_circle(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
@@ -2946,231 +3084,441 @@ fn execute_pause_circle(
 
     if info.sender != circle.creator_address {
         return Err(ContractError::Unauthorized {
-            msg: "Only creator can pause circle".to_string(),
+            msg: "Only creator can start circle".to_string(),
         });
     }
 
-    if !matches!(circle.circle_status, CircleStatus::Running) {
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Open | CircleStatus::Full
+    ) {
         return Err(ContractError::InvalidCircleStatus {
-            expected: "Running".to_string(),
+            expected: "Open or Full".to_string(),
             actual: format!("{:?}", circle.circle_status),
         });
     }
 
-    circle.circle_status = CircleStatus::Paused;
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
-
-    log_event(&mut deps, &env, circle_id, "circle_paused", &format!("Circle {} paused", circle_id))?;
+    if (circle.members_list.len() as u32) < circle.min_members_required {
+        return Err(ContractError::MinMembersNotMet {
+            required: circle.min_members_required,
+            current: circle.members_list.len() as u32,
+        });
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "pause_circle")
-        .add_attribute("circle_id", circle_id.to_string()))
-}
+    generate_payout_order(&mut circle, &env);
 
-fn execute_unpause_circle(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    // Always use the actual on-chain time as the start — not the pre-set start_date
+    // which may have been set to a future date at creation time.
+    let start_timestamp = env.block.time;
+    apply_running_state(&mut circle, start_timestamp);
+    record_circle_joined(deps.storage, &circle)?;
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can unpause circle".to_string(),
-        });
-    }
+    // Anyone still waiting did not get a seat; release their pre-locked funds.
+    let waitlist_refunds = refund_waitlist(&mut deps, &env, &circle)?;
+    let stake_msgs = start_lock_stake(&mut deps, &env, &mut circle)?;
 
-    if !matches!(circle.circle_status, CircleStatus::Paused) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Paused".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
-    }
+    let total_rounds = circle.max_members * circle.total_cycles;
+    let end_timestamp = circle.end_date.unwrap_or(start_timestamp);
+    let archived_timestamp = Timestamp::from_seconds(
+        end_timestamp.seconds() + circle.grace_period_secs(),
+    );
 
-    circle.circle_status = CircleStatus::Running;
-    circle.updated_at = env.block.time;
     CIRCLES.save(deps.storage, circle_id, &circle)?;
 
-    log_event(&mut deps, &env, circle_id, "circle_unpaused", &format!("Circle {} unpaused", circle_id))?;
-
-    Ok(Response::new()
-        .add_attribute("action", "unpause_circle")
-        .add_attribute("circle_id", circle_id.to_string()))
-}
+    // Fresh start: no pauses yet.
+    let calendar_data = build_distribution_calendar(&circle, start_timestamp, &[]);
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "circle_started",
+        &format!("Circle {} started at {}", circle_id, start_timestamp.seconds()),
+    )?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "distribution_calendar",
+        &format!(
+            "{{start_date:{},end_date:{},archived_date:{},calendar:[{}]}}",
+            start_timestamp.seconds(),
+            end_timestamp.seconds(),
+            archived_timestamp.seconds(),
+            calendar_data
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(waitlist_refunds)
+        .add_messages(stake_msgs)
+        .add_attribute("action", "start_circle")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("start_date", start_timestamp.seconds().to_string())
+        .add_attribute("end_date", end_timestamp.seconds().to_string())
+        .add_attribute("archived_date", archived_timestamp.seconds().to_string())
+        .add_attribute("total_rounds", total_rounds.to_string()))
+}
 
 // ---------------------------------------------------------------------------
-// Emergency Stop
-// ---------------------------------------------------------------------------
+// Deposit Contribution
+// ---------------------------------------------------------------------------
 
-fn execute_emergency_stop(
+fn execute_deposit_contribution(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     circle_id: u64,
+    rounds: Option<u32>,
 ) -> Result<Response, ContractError> {
     let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    ensure_attested(deps.as_ref(), &circle, &info.sender)?;
+
+    // Checks and the full breakdown of what is owed; `SimulateDeposit` runs the same.
+    let quote = quote_deposit(deps.storage, &circle, &info.sender, env.block.time, rounds)?;
+    let prepay_rounds = quote.prepay_rounds;
+    let prepay_amount = quote.prepay_amount;
+
+    if quote.contribution_amount.is_zero() {
+        // Current round already paid: this call only prepays.
+        let payment = must_pay(&info, &circle.denomination).map_err(|_| {
+            ContractError::InsufficientFunds {
+                required: prepay_amount.to_string(),
+                sent: "0".to_string(),
+            }
+        })?;
+        if payment < prepay_amount {
+            return Err(ContractError::InsufficientFunds {
+                required: prepay_amount.to_string(),
+                sent: payment.to_string(),
+            });
+        }
+        if payment > prepay_amount {
+            return Err(ContractError::InvalidParameters {
+                msg: format!("Attach exactly the prepayment ({})", prepay_amount),
+            });
+        }
+        credit_prepaid(deps.storage, &mut circle, &info.sender, prepay_amount)?;
+        circle.updated_at = env.block.time;
+        CIRCLES.save(deps.storage, circle_id, &circle)?;
 
-    if !circle.emergency_stop_enabled {
-        return Err(ContractError::InvalidParameters {
-            msg: "Emergency stop not enabled".to_string(),
-        });
+        log_event(
+            &mut deps,
+            &env,
+            circle_id,
+            "rounds_prepaid",
+            &format!(
+                "Member {} prepaid {} rounds ({})",
+                info.sender, prepay_rounds, prepay_amount
+            ),
+        )?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "deposit_contribution")
+            .add_attribute("circle_id", circle_id.to_string())
+            .add_attribute("member", info.sender)
+            .add_attribute("cycle", circle.current_cycle_index.to_string())
+            .add_attribute("amount", "0")
+            .add_attribute("prepaid_rounds", prepay_rounds.to_string()));
     }
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can trigger emergency stop".to_string(),
+    let rounds_missed = quote.missed_rounds;
+
+    // If missed >= max_missed: add late fees for missed rounds, then eject, cannot deposit
+    if rounds_missed >= circle.max_missed_payments_allowed {
+        let late_fee_total = quote.missed_round_fees;
+        let mut accumulated = MEMBER_ACCUMULATED_LATE_FEES
+            .may_load(deps.storage, (circle_id, info.sender.clone()))?
+            .unwrap_or(Uint128::zero());
+        accumulated = accumulated
+            .checked_add(late_fee_total)
+            .unwrap_or(accumulated);
+        MEMBER_ACCUMULATED_LATE_FEES.save(
+            deps.storage,
+            (circle_id, info.sender.clone()),
+            &accumulated,
+        )?;
+
+        let orig_lock = original_lock_for_member(&circle, &info.sender);
+        if should_eject_member(
+            deps.storage,
+            circle_id,
+            &info.sender,
+            orig_lock,
+            circle.exit_penalty_percent,
+            circle.max_missed_payments_allowed,
+        ) {
+            eject_member_from_circle(&mut deps, &env, &mut circle, &info.sender)?;
+            CIRCLES.save(deps.storage, circle_id, &circle)?;
+        }
+        return Err(ContractError::MaxMissedPaymentsExceeded {
+            max: circle.max_missed_payments_allowed,
         });
     }
 
-    circle.emergency_stop_triggered = true;
-    circle.circle_status = CircleStatus::Paused;
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+    // After a successful catch-up (see `quote_deposit`) the member is "fresh":
+    // missed_count and accumulated_late_fees both reset to zero, lock back to
+    // original.
+    let late_fee_total = quote.missed_round_fees + quote.grace_late_fee;
+    let lock_refill_needed = quote.lock_refill;
+    let required_amount = quote.required_amount;
 
-    log_event(&mut deps, &env, circle_id, "emergency_stop", &format!("Emergency stop triggered for circle {}", circle_id))?;
+    let payment = must_pay(&info, &circle.denomination).map_err(|_| {
+        ContractError::InsufficientFunds {
+            required: required_amount.to_string(),
+            sent: "0".to_string(),
+        }
+    })?;
 
-    Ok(Response::new()
-        .add_attribute("action", "emergency_stop")
-        .add_attribute("circle_id", circle_id.to_string()))
-}
+    // PARTIAL DEPOSITS
+    // ----------------
+    // Members may top up toward `required_amount` over several transactions.
+    // Until the running total reaches it nothing is recorded in DEPOSITS -
+    // the funds sit in PARTIAL_DEPOSITS for this round (outside
+    // total_amount_locked) and the member still counts as missing. If the
+    // round closes first, the partial is refunded via PENDING_PAYOUTS.
+    let partial_key = (circle_id, info.sender.clone(), circle.current_cycle_index);
+    let partial = quote.partial_deposited;
+    let accumulated = partial
+        .checked_add(payment)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Partial deposit overflow".to_string(),
+        })?;
 
-// ---------------------------------------------------------------------------
-// Update Circle
-// ---------------------------------------------------------------------------
+    if accumulated < required_amount {
+        PARTIAL_DEPOSITS.save(deps.storage, partial_key, &accumulated)?;
+        circle.total_partial_deposits = circle
+            .total_partial_deposits
+            .checked_add(payment)
+            .unwrap_or(circle.total_partial_deposits);
+        circle.updated_at = env.block.time;
+        CIRCLES.save(deps.storage, circle_id, &circle)?;
 
-fn execute_update_circle(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-    circle_name: Option<String>,
-    circle_description: Option<String>,
-    circle_image: Option<String>,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+        let remaining = required_amount - accumulated;
+        log_event(
+            &mut deps,
+            &env,
+            circle_id,
+            "partial_deposit",
+            &format!(
+                "Member {} deposited {} toward cycle {} ({} of {}, {} remaining)",
+                info.sender, payment, circle.current_cycle_index, accumulated, required_amount, remaining
+            ),
+        )?;
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can update circle".to_string(),
-        });
+        return Ok(Response::new()
+            .add_attribute("action", "deposit_partial")
+            .add_attribute("circle_id", circle_id.to_string())
+            .add_attribute("member", info.sender)
+            .add_attribute("cycle", circle.current_cycle_index.to_string())
+            .add_attribute("amount", payment.to_string())
+            .add_attribute("accumulated", accumulated.to_string())
+            .add_attribute("remaining", remaining.to_string()));
     }
 
-    if matches!(
-        circle.circle_status,
-        CircleStatus::Running | CircleStatus::Finalizing | CircleStatus::Completed
-    ) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Not Running, Finalizing or Completed".to_string(),
-            actual: format!("{:?}", circle.circle_status),
+    if accumulated > required_amount {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "Deposit exceeds the amount still owed for this round ({})",
+                required_amount - partial
+            ),
         });
     }
 
-    if let Some(name) = circle_name {
-        circle.circle_name = name;
+    // Completed: the earlier partials become part of this deposit.
+    if !partial.is_zero() {
+        PARTIAL_DEPOSITS.remove(deps.storage, partial_key);
+        circle.total_partial_deposits = circle.total_partial_deposits.saturating_sub(partial);
     }
-    if let Some(desc) = circle_description {
-        circle.circle_description = desc;
+
+    let is_late = !quote.on_time;
+
+    // Add late fees (paid in tokens) to pool
+    if !late_fee_total.is_zero() {
+        circle.total_penalties_collected = circle
+            .total_penalties_collected
+            .checked_add(late_fee_total)
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Penalties overflow".to_string(),
+            })?;
+
+        PENALTIES.save(
+            deps.storage,
+            (circle_id, info.sender.clone(), circle.current_cycle_index),
+            &PenaltyRecord {
+                member: info.sender.clone(),
+                cycle: circle.current_cycle_index,
+                amount: late_fee_total,
+                reason: format!(
+                    "Late deposit: {} rounds missed ({:?} late fees paid in tokens)",
+                    rounds_missed, circle.late_fee_policy
+                ),
+                timestamp: env.block.time,
+            },
+        )?;
     }
-    if let Some(img) = circle_image {
-        circle.circle_image = Some(img);
+
+    // Refill the lock that was drained covering this member's misses, and
+    // reset the per-member miss counters so they get a clean slate.
+    if !lock_refill_needed.is_zero() {
+        add_member_locked(
+            deps.storage,
+            circle_id,
+            &info.sender,
+            lock_refill_needed,
+            &mut circle.total_amount_locked,
+        )?;
+    }
+    if rounds_missed > 0 {
+        MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, info.sender.clone()));
+        MEMBER_MISSED_PAYMENTS.remove(deps.storage, (circle_id, info.sender.clone()));
+    }
+
+    // Record deposit (only `contribution_amount` is the deposit proper;
+    // the refill goes into MEMBER_LOCKED above, the late fees into PENALTIES).
+    DEPOSITS.save(
+        deps.storage,
+        (circle_id, info.sender.clone(), circle.current_cycle_index),
+        &DepositRecord {
+            member: info.sender.clone(),
+            cycle: circle.current_cycle_index,
+            amount: circle.contribution_amount,
+            timestamp: env.block.time,
+            on_time: !is_late,
+        },
+    )?;
+
+    MEMBER_LAST_DEPOSITED_CYCLE.save(
+        deps.storage,
+        (circle_id, info.sender.clone()),
+        &circle.current_cycle_index,
+    )?;
+    update_member_history(deps.storage, &info.sender, |h| {
+        if is_late {
+            h.deposits_late += 1;
+        } else {
+            h.deposits_on_time += 1;
+        }
+        h.total_deposited += circle.contribution_amount;
+    })?;
+
+    circle.total_amount_locked = circle
+        .total_amount_locked
+        .checked_add(circle.contribution_amount)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Total amount overflow".to_string(),
+        })?;
+
+    if is_late {
+        circle.members_late_this_cycle.push(info.sender.clone());
+    } else {
+        circle.members_paid_this_cycle.push(info.sender.clone());
+    }
+
+    if !prepay_amount.is_zero() {
+        credit_prepaid(deps.storage, &mut circle, &info.sender, prepay_amount)?;
     }
+    release_payout_collateral(deps.storage, &mut circle, &info.sender)?;
+
+    let deposited_cycle = circle.current_cycle_index;
+
+    // Rounds advance by calendar (AdvanceRound/ProcessPayout), not by deposit
 
     circle.updated_at = env.block.time;
     CIRCLES.save(deps.storage, circle_id, &circle)?;
 
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "contribution_deposited",
+        &format!(
+            "Member {} deposited {} for cycle {} (on_time: {})",
+            info.sender, circle.contribution_amount, deposited_cycle, !is_late
+        ),
+    )?;
+
     Ok(Response::new()
-        .add_attribute("action", "update_circle")
-        .add_attribute("circle_id", circle_id.to_string()))
+        .add_attribute("action", "deposit_contribution")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("cycle", deposited_cycle.to_string())
+        .add_attribute("amount", circle.contribution_amount.to_string())
+        .add_attribute("on_time", (!is_late).to_string())
+        .add_attribute("prepaid_rounds", prepay_rounds.to_string()))
 }
 
 // ---------------------------------------------------------------------------
-// Withdraw Platform Fees (stub)
+// Auto-debit - keeper pulls contributions through an authz grant
 // ---------------------------------------------------------------------------
 
-/// Send accumulated platform fees of a circle to the configured platform
-/// address. Permissionless — anyone may trigger because the destination is
-/// fixed in `PLATFORM_CONFIG`. Gated to `Finalizing` / `Completed` so a draw
-/// cannot happen while accounting is mid-cycle. Used to recover fees from
-/// legacy circles that finalized under the previous code path where fees were
-/// never drained.
-fn execute_withdraw_platform_fees(
+fn execute_set_auto_debit(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    circle_id: Option<u64>,
+    circle_id: u64,
+    enabled: bool,
 ) -> Result<Response, ContractError> {
-    let id = circle_id.ok_or_else(|| ContractError::InvalidParameters {
-        msg: "circle_id is required".to_string(),
-    })?;
-    let mut circle = CIRCLES.load(deps.storage, id)?;
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    if !matches!(
+    if matches!(
         circle.circle_status,
-        CircleStatus::Finalizing | CircleStatus::Completed | CircleStatus::Cancelled
+        CircleStatus::Finalizing
+            | CircleStatus::Completed
+            | CircleStatus::Cancelled
+            | CircleStatus::WoundDown
     ) {
         return Err(ContractError::InvalidCircleStatus {
-            expected: "Finalizing, Completed or Cancelled".to_string(),
+            expected: "Draft, Open, Full, Running or Paused".to_string(),
             actual: format!("{:?}", circle.circle_status),
         });
     }
 
-    let fees = circle.total_platform_fees_collected;
-    if fees.is_zero() {
-        return Err(ContractError::NoPendingPayouts {});
+    if !circle.members_list.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            msg: "Not a member of this circle".to_string(),
+        });
     }
 
-    let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
-    circle.total_platform_fees_collected = Uint128::zero();
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, id, &circle)?;
+    if enabled {
+        AUTO_DEBIT.save(
+            deps.storage,
+            (circle_id, info.sender.clone()),
+            &AutoDebitEnrollment {
+                member: info.sender.clone(),
+                enabled_at: env.block.time,
+            },
+        )?;
+    } else {
+        AUTO_DEBIT.remove(deps.storage, (circle_id, info.sender.clone()));
+    }
 
     log_event(
         &mut deps,
         &env,
-        id,
-        "platform_fees_withdrawn",
-        &format!(
-            "{} usaf platform fees sent to {} (triggered by {})",
-            fees, platform_addr, info.sender
-        ),
+        circle_id,
+        if enabled { "auto_debit_enabled" } else { "auto_debit_disabled" },
+        &format!("Member {} set auto-debit to {}", info.sender, enabled),
     )?;
 
     Ok(Response::new()
-        .add_message(BankMsg::Send {
-            to_address: platform_addr.to_string(),
-            amount: vec![Coin {
-                denom: circle.denomination.clone(),
-                amount: fees,
-            }],
-        })
-        .add_attribute("action", "withdraw_platform_fees")
-        .add_attribute("circle_id", id.to_string())
-        .add_attribute("amount", fees.to_string()))
+        .add_attribute("action", "set_auto_debit")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("enabled", enabled.to_string()))
 }
 
-/// Sweep residual native funds on a Finalizing/Completed/Cancelled circle
-/// by crediting them to the CREATOR's `PENDING_PAYOUTS`, so the creator can
-/// reclaim them with a normal `Withdraw` call.
-///
-/// Permissionless. Only runs when every member has withdrawn
-/// (`total_pending_payouts == 0`).
-/// Residual = on-chain bank balance minus undrained platform fees
-/// (use `WithdrawPlatformFees` for those).
-///
-/// Use cases:
-/// - Legacy circles that finalized before the `members_at_start` fix in
-///   `total_rounds`: the contract zeroed `creator_lock_amount` but never
-///   credited the refund (e.g. an `Ws Savings Circle1` with 14 SAF stuck).
-///   This sweep delivers the funds to the creator.
-/// - Old residuals (truncated creator-lock refund, headroom remainder from
-///   over-deposits) — same recovery path.
+/// Pull the current round's contribution from every opted-in member who has not
+/// deposited yet. Each pull is an authz `MsgExec` wrapping a `MsgSend` from the
+/// member to this contract, sent as a `SubMsg::reply_always` and remembered in
+/// AUTO_DEBIT_PULLS; `reply` records the deposit once the pull succeeds. A missing
+/// or exhausted grant only fails that member's pull: it is logged and skipped,
+/// and the other members' deposits still go through.
 ///
-/// If the creator is no longer addressable (somehow removed; shouldn't
-/// happen), the funds fall back to the platform address to avoid being
-/// permanently locked.
-fn execute_sweep_dust(
+/// Members who are behind (would owe lock refill and late fees) are skipped:
+/// auto-debit only pays on time, catch-up stays a manual DepositContribution.
+fn execute_auto_debit_contributions(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
@@ -3178,1102 +3526,9412 @@ fn execute_sweep_dust(
 ) -> Result<Response, ContractError> {
     let mut circle = CIRCLES.load(deps.storage, circle_id)?;
 
-    if !matches!(
-        circle.circle_status,
-        CircleStatus::Finalizing | CircleStatus::Completed | CircleStatus::Cancelled
-    ) {
+    if !matches!(circle.circle_status, CircleStatus::Running) {
         return Err(ContractError::InvalidCircleStatus {
-            expected: "Finalizing, Completed or Cancelled".to_string(),
+            expected: "Running".to_string(),
             actual: format!("{:?}", circle.circle_status),
         });
     }
 
-    if !circle.total_pending_payouts.is_zero() {
-        return Err(ContractError::InvalidParameters {
-            msg: format!(
-                "Pending payouts must be zero before sweep (still {})",
-                circle.total_pending_payouts
+    // Only useful before the round's grace period ends: after that the member
+    // is already missing and ProcessPayout / AdvanceRound apply late fees.
+    if let Some(round_start) = circle.next_payout_date {
+        let grace_end = round_start.plus_seconds(circle.grace_period_secs());
+        if env.block.time > grace_end {
+            return Err(ContractError::InvalidParameters {
+                msg: format!(
+                    "Auto-debit window for cycle {} closed at {}",
+                    circle.current_cycle_index,
+                    grace_end.seconds()
+                ),
+            });
+        }
+    }
+
+    let cycle = circle.current_cycle_index;
+    let members = circle.members_list.clone();
+    let mut pulls: Vec<SubMsg> = vec![];
+    let mut debited: Vec<String> = vec![];
+    for member in &members {
+        if AUTO_DEBIT
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .is_none()
+        {
+            continue;
+        }
+        let blocked = BLOCKED_MEMBERS
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .map(|bc| bc <= cycle)
+            .unwrap_or(false);
+        let deposited = DEPOSITS
+            .may_load(deps.storage, (circle_id, member.clone(), cycle))?
+            .is_some();
+        let behind = cycle.saturating_sub(last_deposited_cycle(deps.storage, circle_id, member)?) > 1;
+        let prepaid = PREPAID_BALANCES
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero());
+        if blocked || deposited || behind || prepaid >= circle.contribution_amount {
+            continue;
+        }
+        // Same gate as a manual deposit: members whose attestation lapsed are left out.
+        match ensure_attested(deps.as_ref(), &circle, member) {
+            Err(ContractError::NotAttested { .. }) => continue,
+            result => result?,
+        }
+
+        // Whatever the member already sent as a partial deposit counts.
+        let partial = PARTIAL_DEPOSITS
+            .may_load(deps.storage, (circle_id, member.clone(), cycle))?
+            .unwrap_or(Uint128::zero());
+        // PerHour: past the deadline the pull also carries the grace-time fee.
+        let grace_fee = grace_time_late_fee(&circle, env.block.time);
+        let amount = circle.contribution_amount.saturating_sub(partial) + grace_fee;
+        if amount.is_zero() {
+            take_partial_deposit(deps.storage, &mut circle, member)?;
+            record_funded_deposit(deps.storage, &mut circle, member, env.block.time, grace_fee)?;
+        } else {
+            // Each pull stands alone: a revoked or underfunded grant only fails
+            // that member's pull (see `reply`), who then simply stays missing.
+            let id = AUTO_DEBIT_PULL_COUNTER.may_load(deps.storage)?.unwrap_or(0) + 1;
+            AUTO_DEBIT_PULL_COUNTER.save(deps.storage, &id)?;
+            AUTO_DEBIT_PULLS.save(
+                deps.storage,
+                id,
+                &AutoDebitPull {
+                    circle_id,
+                    member: member.clone(),
+                    cycle,
+                    amount,
+                    grace_fee,
+                },
+            )?;
+            pulls.push(SubMsg::reply_always(
+                crate::authz::exec_send_from_granter(
+                    &env.contract.address,
+                    member,
+                    Coin {
+                        denom: circle.denomination.clone(),
+                        amount,
+                    },
+                ),
+                id,
+            ));
+        }
+        debited.push(member.to_string());
+    }
+
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    if !debited.is_empty() {
+        log_event(
+            &mut deps,
+            &env,
+            circle_id,
+            "auto_debit",
+            &format!(
+                "Cycle {} contributions auto-debit requested for [{}] (triggered by {})",
+                cycle,
+                debited.join(","),
+                info.sender
             ),
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_submessages(pulls)
+        .add_attribute("action", "auto_debit_contributions")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("cycle", cycle.to_string())
+        .add_attribute("debited", debited.len().to_string()))
+}
+
+/// Submessage replies. Only auto-debit pulls use them: a successful pull records
+/// the member's on-time deposit, a failed one leaves the member missing for the
+/// round without reverting the other members' pulls.
+pub fn reply(mut deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let pull = AUTO_DEBIT_PULLS.load(deps.storage, msg.id)?;
+    AUTO_DEBIT_PULLS.remove(deps.storage, msg.id);
+    let mut circle = CIRCLES.load(deps.storage, pull.circle_id)?;
+
+    match msg.result {
+        SubMsgResult::Ok(_) => {
+            take_partial_deposit(deps.storage, &mut circle, &pull.member)?;
+            record_funded_deposit(
+                deps.storage,
+                &mut circle,
+                &pull.member,
+                env.block.time,
+                pull.grace_fee,
+            )?;
+            circle.updated_at = env.block.time;
+            CIRCLES.save(deps.storage, pull.circle_id, &circle)?;
+            Ok(Response::new()
+                .add_attribute("action", "auto_debit_pulled")
+                .add_attribute("circle_id", pull.circle_id.to_string())
+                .add_attribute("member", pull.member)
+                .add_attribute("amount", pull.amount.to_string()))
+        }
+        SubMsgResult::Err(err) => {
+            log_event(
+                &mut deps,
+                &env,
+                pull.circle_id,
+                "auto_debit_failed",
+                &format!(
+                    "Auto-debit of {} from {} for cycle {} failed: {}",
+                    pull.amount, pull.member, pull.cycle, err
+                ),
+            )?;
+            Ok(Response::new()
+                .add_attribute("action", "auto_debit_failed")
+                .add_attribute("circle_id", pull.circle_id.to_string())
+                .add_attribute("member", pull.member)
+                .add_attribute("error", err))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Process Payout — stores to PENDING_PAYOUTS instead of direct send
+// ---------------------------------------------------------------------------
+
+fn execute_process_payout(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(circle.circle_status, CircleStatus::Running) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    // Idempotency: reject if this cycle was already processed (prevents double trigger)
+    let already_processed = PAYOUTS
+        .prefix((circle_id, circle.current_cycle_index))
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if already_processed {
+        return Err(ContractError::PayoutAlreadyProcessed {
+            cycle: circle.current_cycle_index,
+        });
+    }
+
+    // Authorization: manual_trigger_enabled means only creator can call
+    if circle.manual_trigger_enabled && info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can trigger payout (manual_trigger_enabled)".to_string(),
         });
     }
 
-    let bal = deps
-        .querier
-        .query_balance(&env.contract.address, &circle.denomination)?;
-    // Reserve undrained platform fees - those go through WithdrawPlatformFees,
-    // not the dust path, so the platform address is not double-credited.
-    let dust = bal
-        .amount
-        .checked_sub(circle.total_platform_fees_collected)
-        .unwrap_or(Uint128::zero());
+    // Time gate (end-of-round): prevent back-to-back transitions that can skip rounds.
+    // `next_payout_date` is the round start; only allow payout after the round has ended.
+    if let Some(next_payout) = circle.next_payout_date {
+        let round_end = next_payout.plus_seconds(circle.cycle_duration_secs());
+        if env.block.time < round_end {
+            return Err(ContractError::CycleNotReady {
+                next_date: round_end.seconds(),
+            });
+        }
+    }
+
+    // The final round hands every lock back, so a staked circle's locks must be
+    // in the bank first: this call only undelegates them, and the payout goes
+    // through once the unbonding has matured.
+    let final_round = circle.current_cycle_index
+        >= circle.members_at_start.unwrap_or(circle.max_members) * circle.total_cycles;
+    if let (true, Some(stake)) = (final_round, circle.lock_stake.clone()) {
+        if !stake.delegated.is_zero() {
+            let stake_msgs = sync_lock_stake(&mut deps, &env, &mut circle)?;
+            let matures_at = circle
+                .lock_stake
+                .as_ref()
+                .and_then(|s| s.unbonding_matures_at)
+                .map(|at| at.seconds())
+                .unwrap_or_default();
+            circle.updated_at = env.block.time;
+            CIRCLES.save(deps.storage, circle_id, &circle)?;
+            return Ok(Response::new()
+                .add_messages(stake_msgs)
+                .add_attribute("action", "process_payout")
+                .add_attribute("circle_id", circle_id.to_string())
+                .add_attribute("cycle", circle.current_cycle_index.to_string())
+                .add_attribute("locks_unbonding_until", matures_at.to_string()));
+        }
+        if let Some(matures_at) = stake.unbonding_matures_at {
+            if env.block.time < matures_at {
+                return Err(ContractError::LockStakeUnbonding {
+                    circle_id,
+                    matures_at: matures_at.seconds(),
+                });
+            }
+        }
+    }
+
+    // Active members (not blocked)
+    let mut active_members: Vec<Addr> = circle
+        .members_list
+        .iter()
+        .filter(|m| {
+            BLOCKED_MEMBERS
+                .may_load(deps.storage, (circle_id, (*m).clone()))
+                .unwrap_or(None)
+                .map(|bc| bc > circle.current_cycle_index)
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect();
+
+    consume_prepaid_for_round(&mut deps, &env, &mut circle, &active_members)?;
+
+    let missing_members: Vec<Addr> = active_members
+        .iter()
+        .filter(|m| {
+            DEPOSITS
+                .may_load(deps.storage, (circle_id, (*m).clone(), circle.current_cycle_index))
+                .unwrap_or(None)
+                .is_none()
+        })
+        .cloned()
+        .collect();
+
+    // If any missing and grace period still active, block payout
+    if let Some(next_payout) = circle.next_payout_date {
+        let grace_end = next_payout.plus_seconds(circle.grace_period_secs());
+        if env.block.time <= grace_end && !missing_members.is_empty() {
+            return Err(ContractError::InvalidParameters {
+                msg: "Grace period not ended for all missing members".to_string(),
+            });
+        }
+    }
+
+    // The round is closing: unfinished partial deposits go back to their owners.
+    refund_partial_deposits(deps.storage, &mut circle, &missing_members)?;
+
+    let mut locked_used_total = Uint128::zero();
+    let mut reserve_used_total = Uint128::zero();
+    let mut collateral_used_total = Uint128::zero();
+
+    // Handle missing members (past grace): accumulate late fees, check ejection, use locked funds
+    for member in &missing_members {
+        let mut accumulated = MEMBER_ACCUMULATED_LATE_FEES
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero());
+
+        // Only count once per cycle
+        let mut missed = MEMBER_MISSED_PAYMENTS
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(MemberMissedPayments {
+                member: member.clone(),
+                missed_count: 0,
+                last_missed_cycle: None,
+                last_fee_round: None,
+            });
+
+        if missed.last_missed_cycle != Some(circle.current_cycle_index) {
+            missed.missed_count += 1;
+            missed.last_missed_cycle = Some(circle.current_cycle_index);
+            update_member_history(deps.storage, member, |h| h.missed_rounds += 1)?;
+            accumulated = accumulated
+                .checked_add(late_fee_for_nth_miss(&circle, missed.missed_count))
+                .unwrap_or(accumulated);
+
+            MEMBER_ACCUMULATED_LATE_FEES.save(
+                deps.storage,
+                (circle_id, member.clone()),
+                &accumulated,
+            )?;
+            MEMBER_MISSED_PAYMENTS.save(
+                deps.storage,
+                (circle_id, member.clone()),
+                &missed,
+            )?;
+        }
+
+        // Determine if this member should be ejected NOW.
+        //
+        // Two independent ejection triggers, by design:
+        //   (a) `should_eject_member` - the standard "missed too many" or
+        //       "accumulated fees exhausted the security" check.
+        //   (b) NEW: their remaining lock can no longer cover this round's
+        //       contribution. Without this guard, a member whose
+        //       MEMBER_LOCKED was depleted by earlier misses (but who hasn't
+        //       yet hit `max_missed_payments_allowed`) would block the round
+        //       forever - `use_locked_amount_for_member` returns 0, no
+        //       synthetic deposit is written, and the post-loop
+        //       `deposits_count < active_members.len()` check fails.
+        //
+        // Per the security contract: a member's lock IS their commitment;
+        // when it can't cover a round, they've effectively defaulted, and
+        // the circle MUST keep running for the remaining members.
+        let orig_lock = original_lock_for_member(&circle, member);
+        let standard_eject = should_eject_member(
+            deps.storage,
+            circle_id,
+            member,
+            orig_lock,
+            circle.exit_penalty_percent,
+            circle.max_missed_payments_allowed,
+        );
+        let lock_insufficient = if member == &circle.creator_address {
+            // Creator's lock is in `creator_lock_amount`; can't be drained
+            // per-miss the way MEMBER_LOCKED is. Skip this trigger for them
+            // (creator handling is via cancel/finalize, not per-round eject).
+            false
+        } else {
+            current_member_lock(deps.storage, circle_id, member)
+                + payout_collateral_of(deps.storage, circle_id, member)
+                < circle.contribution_amount
+        };
+
+        // Post-payout collateral covers a paid member's round before anything else.
+        let owed = circle.contribution_amount;
+        let from_collateral = use_payout_collateral(deps.storage, &mut circle, member, owed)?;
+
+        if standard_eject || lock_insufficient {
+            collateral_used_total += from_collateral;
+            eject_member_from_circle(&mut deps, &env, &mut circle, member)?;
+            // Ejected: their MEMBER_LOCKED stays in the circle (becomes part
+            // of `total_penalties_collected` per eject logic). No synthetic
+            // deposit for this round - active_members.len() will drop on
+            // the post-loop recount and the round proceeds with the rest.
+            // If they already took the pot, the reserve pays their share.
+            if has_received_payout(deps.storage, circle_id, member, circle.current_cycle_index) {
+                reserve_used_total += draw_from_reserve(&mut circle, owed - from_collateral);
+            }
+            continue;
+        }
+
+        // Use locked funds to cover missed deposit. Creator's lock is never
+        // touched (lives in creator_lock_amount, not MEMBER_LOCKED).
+        let used = if member == &circle.creator_address {
+            Uint128::zero()
+        } else {
+            use_locked_amount_for_member(deps.storage, circle_id, member, owed - from_collateral)?
+        };
+        // Counted in this round's deposits below, so it joins the pool.
+        circle.total_amount_locked += from_collateral;
+        if !(used + from_collateral).is_zero() {
+            locked_used_total = locked_used_total
+                .checked_add(used)
+                .map_err(|_| ContractError::InvalidParameters {
+                    msg: "Locked funds overflow".to_string(),
+                })?;
+            // Record synthetic deposit so this round remains auditable (no perceived "skipped" round).
+            DEPOSITS.save(
+                deps.storage,
+                (circle_id, member.clone(), circle.current_cycle_index),
+                &DepositRecord {
+                    member: member.clone(),
+                    cycle: circle.current_cycle_index,
+                    amount: used + from_collateral,
+                    timestamp: env.block.time,
+                    on_time: false,
+                },
+            )?;
+        }
+    }
+
+    // Also use locked funds from previously blocked members
+    let blocked_members_list: Vec<Addr> = BLOCKED_MEMBERS
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|res| res.ok().map(|(m, _)| m))
+        .collect();
+
+    for blocked_member in blocked_members_list {
+        if missing_members.iter().any(|m| m == &blocked_member) {
+            continue;
+        }
+        if let Ok(Some(bc)) =
+            BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, blocked_member.clone()))
+        {
+            if bc <= circle.current_cycle_index {
+                let owed = circle.contribution_amount;
+                let from_collateral =
+                    use_payout_collateral(deps.storage, &mut circle, &blocked_member, owed)?;
+                collateral_used_total += from_collateral;
+                let used = use_locked_amount_for_member(
+                    deps.storage,
+                    circle_id,
+                    &blocked_member,
+                    owed - from_collateral,
+                )?;
+                let gap = owed.saturating_sub(used + from_collateral);
+                if !gap.is_zero()
+                    && has_received_payout(deps.storage, circle_id, &blocked_member, circle.current_cycle_index)
+                {
+                    reserve_used_total += draw_from_reserve(&mut circle, gap);
+                }
+                if !(used + from_collateral).is_zero() {
+                    locked_used_total = locked_used_total
+                        .checked_add(used)
+                        .map_err(|_| ContractError::InvalidParameters {
+                            msg: "Locked funds overflow".to_string(),
+                        })?;
+                    // Record synthetic deposit for blocked member coverage in this round.
+                    DEPOSITS.save(
+                        deps.storage,
+                        (circle_id, blocked_member.clone(), circle.current_cycle_index),
+                        &DepositRecord {
+                            member: blocked_member.clone(),
+                            cycle: circle.current_cycle_index,
+                            amount: used + from_collateral,
+                            timestamp: env.block.time,
+                            on_time: false,
+                        },
+                    )?;
+                }
+            }
+        }
+    }
+
+    sweep_penalties_into_reserve(&mut circle);
+
+    // Recompute active members after ejections
+    active_members = circle
+        .members_list
+        .iter()
+        .filter(|m| {
+            BLOCKED_MEMBERS
+                .may_load(deps.storage, (circle_id, (*m).clone()))
+                .unwrap_or(None)
+                .map(|bc| bc > circle.current_cycle_index)
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect();
+
+    let deposits_count = active_members
+        .iter()
+        .filter(|m| {
+            DEPOSITS
+                .may_load(deps.storage, (circle_id, (*m).clone(), circle.current_cycle_index))
+                .unwrap_or(None)
+                .is_some()
+        })
+        .count();
+
+    // Distribution threshold check (based on active members). An accumulating
+    // circle banks every round into its savings pool instead of paying it out.
+    let accumulating = circle.savings.is_some();
+    let active_count = active_members.len() as u32;
+    if active_count == 0 {
+        return Err(ContractError::InvalidParameters {
+            msg: "No active members for distribution".to_string(),
+        });
+    }
+    let round_in_cycle = ((circle.current_cycle_index - 1) % active_count) + 1;
+    let min_round_for_distribution =
+        distribution_min_round_for_active(&circle.distribution_threshold, active_count);
+
+    if !accumulating && round_in_cycle < min_round_for_distribution {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "Distribution only from round {} (current round in cycle: {})",
+                min_round_for_distribution, round_in_cycle
+            ),
+        });
+    }
+
+    // All active members must have a real-or-synthetic deposit for this round.
+    //
+    // `deposits_count` is recomputed AFTER the missing-member loop, so it
+    // includes the synthetic DEPOSITS rows just written for members whose
+    // locked join-deposit was consumed to cover the round.
+    //
+    // The previous formulation `deposits_count + |missing_members ∩ active|`
+    // was tautological — since `missing_members ⊆ active_members`, the sum
+    // always equalled `active_members.len()` and the contract therefore
+    // accepted process_payout even when a missing member had ZERO locked
+    // funds left and no synthetic deposit was created. That over-credited
+    // the pool by the missing contribution amount, which is the root cause
+    // of the "PENDING_PAYOUTS > contract bank balance" symptom on Withdraw.
+    if deposits_count < active_members.len() {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "Not all active members have contributed: need {}, have {} (some missing members had no locked funds left to cover this round)",
+                active_members.len(),
+                deposits_count
+            ),
+        });
+    }
+
+    // Total threshold at last round of cycle: split equally among ALL active members
+    let is_total_at_last_round = !accumulating
+        && is_total_style_threshold(&circle.distribution_threshold)
+        && round_in_cycle == min_round_for_distribution;
+
+    // Calculate payout amount (total pool)
+    // For Total threshold at last round: sum from ALL rounds in the cycle. Each round: active_count * contribution
+    // (everyone contributed either by deposit or locked). Otherwise: only current round (one recipient per round).
+    // Accumulating: this round's deposits as recorded, locked cover included.
+    let base_payout = if accumulating {
+        round_deposits_total(deps.storage, &circle, &active_members)?
+    } else if is_total_at_last_round {
+        let rounds_in_cycle = active_count; // Total threshold: min_round = active_count
+        let round_amount = circle
+            .contribution_amount
+            .checked_mul(Uint128::from(active_count as u128))
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Payout amount overflow".to_string(),
+            })?;
+        round_amount
+            .checked_mul(Uint128::from(rounds_in_cycle as u128))
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Payout amount overflow".to_string(),
+            })?
+    } else {
+        circle
+            .contribution_amount
+            .checked_mul(Uint128::from(deposits_count as u128))
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Payout amount overflow".to_string(),
+            })?
+    };
+
+    let mut payout_amount = if accumulating {
+        base_payout
+    } else {
+        base_payout
+            .checked_add(locked_used_total)
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Payout amount overflow".to_string(),
+            })?
+    };
+    // Reserve: premium slice of this round's contributions in, covered shortfalls out.
+    if let Some(ref mut reserve) = circle.reserve {
+        let premium = payout_amount.multiply_ratio(reserve.premium_bps, 10000u64);
+        reserve.balance += premium;
+        reserve.premiums_collected += premium;
+        payout_amount = payout_amount - premium + reserve_used_total;
+    }
+    // Collateral covering members no longer active is not among the deposits counted above.
+    payout_amount += collateral_used_total;
+
+    let platform_fee = payout_amount.multiply_ratio(circle.platform_fee_percent, 10000u64);
+    payout_amount = payout_amount
+        .checked_sub(platform_fee)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Payout amount underflow".to_string(),
+        })?;
+
+    circle.total_platform_fees_collected = circle
+        .total_platform_fees_collected
+        .checked_add(platform_fee)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Platform fees overflow".to_string(),
+        })?;
+
+    // Settle the lock stake before budgeting: locks consumed above start
+    // unbonding now, and rewards credited here are withdrawn with this TX.
+    let pending_before_sync = circle.total_pending_payouts;
+    let stake_msgs = sync_lock_stake(&mut deps, &env, &mut circle)?;
+    let rewards_withdrawn = circle.total_pending_payouts - pending_before_sync;
+    let unbonding = match circle.lock_stake {
+        Some(ref stake) if stake.unbonding_matures_at.is_some() => stake.unbonding,
+        _ => Uint128::zero(),
+    };
+
+    let liquid_balance = deps
+        .querier
+        .query_balance(&env.contract.address, &circle.denomination)
+        .map_err(|e| ContractError::InvalidParameters {
+            msg: format!("Balance query failed: {}", e),
+        })?;
+    let available = liquid_balance.amount;
+
+    // Any PENDING_PAYOUTS already credited (from earlier rounds, not yet
+    // withdrawn) must remain backed by bank balance — we cannot credit so
+    // much new payout that the contract can no longer satisfy outstanding
+    // withdrawals. Subtract them from the spendable budget for this TX.
+    // Prepaid balances and partial deposits are not ours to distribute either.
+    let uncommitted = |funds: Uint128| {
+        funds
+            .checked_sub(circle.total_pending_payouts)
+            .and_then(|v| v.checked_sub(circle.total_prepaid))
+            .and_then(|v| v.checked_sub(circle.total_partial_deposits))
+            .and_then(|v| v.checked_sub(circle.total_payout_collateral))
+            .and_then(|v| v.checked_sub(circle.savings.as_ref().map(|p| p.balance).unwrap_or_default()))
+            .unwrap_or(Uint128::zero())
+    };
+    let liquid_for_new_credits = uncommitted(available + rewards_withdrawn);
+    // Unbonding locks count too: whatever they back is held from Withdraw
+    // until they are back in the bank (see LOCK_BACKED_PENDING).
+    let spendable_for_new_credits = uncommitted(available + rewards_withdrawn + unbonding);
+
+    // Compute total we will add to PENDING_PAYOUTS and verify contract has sufficient balance
+    let mut total_to_credit = payout_amount;
+    let mut creator_refund_amount = Uint128::zero();
+    // Total rounds = ACTIVE roster at start × total_cycles. Using `max_members`
+    // here was wrong for circles that auto-started with fewer members than
+    // the cap: e.g. a circle configured `max_members=3, total_cycles=2` that
+    // actually started with 2 members has 4 real rounds, not 6. With the old
+    // formula, `current_cycle_index=4 >= 6` was false on the FINAL round,
+    // the pre-computation block was skipped, `creator_refund_amount` stayed
+    // at zero, and the finalize block below (which uses the correct
+    // `members_at_start` formula) zeroed `creator_lock_amount` without ever
+    // crediting the creator's refund - silently stranding it on the bank.
+    // Must mirror the second `total_rounds` computation further down.
+    let members_basis_pre = circle.members_at_start.unwrap_or(circle.max_members);
+    let total_rounds = members_basis_pre * circle.total_cycles;
+    if circle.current_cycle_index >= total_rounds {
+        let locked_sum: Uint128 = MEMBER_LOCKED_AMOUNTS
+            .prefix(circle_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter_map(|r| r.ok())
+            .map(|(_, amt)| amt)
+            .fold(Uint128::zero(), |a, b| a.saturating_add(b));
+        // Add creator lock only when creator has no MEMBER_LOCKED_AMOUNTS entry.
+        // For old circles where creator lock may have been partially consumed, cap refund by available funds.
+        let creator_has_locked_entry = MEMBER_LOCKED_AMOUNTS
+            .may_load(deps.storage, (circle_id, circle.creator_address.clone()))?
+            .is_some();
+        total_to_credit = total_to_credit
+            .checked_add(locked_sum)
+            .unwrap_or(total_to_credit);
+        total_to_credit = total_to_credit
+            .checked_add(circle.total_penalties_collected)
+            .unwrap_or(total_to_credit);
+        if let Some(ref reserve) = circle.reserve {
+            total_to_credit = total_to_credit
+                .checked_add(reserve.balance)
+                .unwrap_or(total_to_credit);
+        }
+        if !creator_has_locked_entry && !circle.creator_lock_amount.is_zero() {
+            // Refund at most the still-spendable funds AFTER honouring
+            // outstanding pending payouts and the mandatory final credits.
+            // Using `available` alone (the prior behaviour) over-credited the
+            // creator when earlier cycles had already committed funds to
+            // PENDING_PAYOUTS, producing the bank-vs-pending mismatch.
+            creator_refund_amount = spendable_for_new_credits
+                .checked_sub(total_to_credit)
+                .unwrap_or(Uint128::zero());
+            if creator_refund_amount > circle.creator_lock_amount {
+                creator_refund_amount = circle.creator_lock_amount;
+            }
+            total_to_credit = total_to_credit
+                .checked_add(creator_refund_amount)
+                .unwrap_or(total_to_credit);
+        }
+    }
+    if spendable_for_new_credits < total_to_credit {
+        return Err(ContractError::InsufficientContractBalance {
+            required: total_to_credit.to_string(),
+            available: available.to_string(),
+        });
+    }
+    let mut lock_backed = total_to_credit.saturating_sub(liquid_for_new_credits);
+
+    // Capture single-recipient address for logging (none for Total threshold final-round split).
+    let mut single_recipient: Option<Addr> = None;
+
+    // Outbound bank messages produced by this transaction (e.g. platform fee
+    // drain at finalization). Withdrawals are still pulled via Withdraw — this
+    // only carries amounts the contract autonomously sends out (platform fees,
+    // dust). Building it here keeps the response self-contained.
+    let mut outbound_messages: Vec<CosmosMsg> = Vec::new();
+    let mut platform_fees_sent: Uint128 = Uint128::zero();
+
+    if accumulating {
+        bank_round_savings(deps.storage, &mut circle, &active_members, payout_amount)?;
+    } else if is_total_at_last_round {
+        // Split payout_amount equally among all active members
+        let member_count = active_members.len() as u128;
+        if member_count == 0 {
+            return Err(ContractError::InvalidParameters {
+                msg: "No active members for distribution".to_string(),
+            });
+        }
+        let amount_per_member = payout_amount
+            .checked_div(Uint128::from(member_count))
+            .unwrap_or(Uint128::zero());
+        let remainder = payout_amount
+            .checked_sub(amount_per_member * Uint128::from(member_count))
+            .unwrap_or(Uint128::zero());
+
+        for (idx, member) in active_members.iter().enumerate() {
+            let mut amt = amount_per_member;
+            if idx == 0 {
+                amt = amt.checked_add(remainder).unwrap_or(amt);
+            }
+            if amt.is_zero() {
+                continue;
+            }
+
+            PAYOUTS.save(
+                deps.storage,
+                (circle_id, circle.current_cycle_index, member.clone()),
+                &PayoutRecord {
+                    cycle: circle.current_cycle_index,
+                    recipient: member.clone(),
+                    amount: amt,
+                    timestamp: env.block.time,
+                    transaction_hash: None,
+                },
+            )?;
+
+            let withheld = withhold_payout_collateral(deps.storage, &mut circle, member, amt)?;
+            credit_pending_payout(
+                deps.storage,
+                circle_id,
+                member,
+                amt - withheld,
+                &mut circle.total_pending_payouts,
+            )?;
+            hold_lock_backed(deps.storage, circle_id, member, amt - withheld, &mut lock_backed)?;
+            update_member_history(deps.storage, member, |h| {
+                h.payouts_received += 1;
+                h.total_received += amt;
+            })?;
+        }
+    } else {
+        // One recipient per round (MinMembers or None)
+        let recipient = if let Some(ref order_list) = circle.payout_order_list {
+            let active_order: Vec<Addr> = order_list
+                .iter()
+                .filter(|m| active_members.iter().any(|a| a == *m))
+                .cloned()
+                .collect();
+            if active_order.is_empty() {
+                return Err(ContractError::InvalidParameters {
+                    msg: "No active members in payout order".to_string(),
+                });
+            }
+            let index = (round_in_cycle as usize - 1) % active_order.len();
+            active_order[index].clone()
+        } else {
+            return Err(ContractError::InvalidParameters {
+                msg: "Payout order not set".to_string(),
+            });
+        };
+        single_recipient = Some(recipient.clone());
+
+        PAYOUTS.save(
+            deps.storage,
+            (circle_id, circle.current_cycle_index, recipient.clone()),
+            &PayoutRecord {
+                cycle: circle.current_cycle_index,
+                recipient: recipient.clone(),
+                amount: payout_amount,
+                timestamp: env.block.time,
+                transaction_hash: None,
+            },
+        )?;
+
+        // The PAYOUTS record keeps the full pot; only the net is withdrawable.
+        let withheld =
+            withhold_payout_collateral(deps.storage, &mut circle, &recipient, payout_amount)?;
+        credit_pending_payout(
+            deps.storage,
+            circle_id,
+            &recipient,
+            payout_amount - withheld,
+            &mut circle.total_pending_payouts,
+        )?;
+        hold_lock_backed(
+            deps.storage,
+            circle_id,
+            &recipient,
+            payout_amount - withheld,
+            &mut lock_backed,
+        )?;
+        update_member_history(deps.storage, &recipient, |h| {
+            h.payouts_received += 1;
+            h.total_received += payout_amount;
+        })?;
+    }
+
+    // Subtract the contributed deposits from total_amount_locked (excluding locked join deposits used above — those were already deducted by use_locked_amount_for_member)
+    let deposit_outflow = base_payout
+        .checked_add(locked_used_total)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Outflow overflow".to_string(),
+        })?;
+
+    circle.total_amount_locked = circle
+        .total_amount_locked
+        .checked_sub(deposit_outflow)
+        .unwrap_or(Uint128::zero());
+
+    circle.cycles_completed += 1;
+    circle.members_paid_this_cycle.clear();
+    circle.members_late_this_cycle.clear();
+
+    // Check if last round across all cycles. Round count is based on the
+    // active member count locked at start (members_at_start), NOT the
+    // configured cap (max_members). Using max_members caused circles that
+    // started with fewer members than the cap to keep advancing past their
+    // calendar's last round — status never transitioned to Finalizing, the
+    // cron kept queuing actions, and the UI displayed phantom rounds with
+    // no recipient.
+    let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
+    let total_rounds = members_basis * circle.total_cycles;
+    if circle.current_cycle_index >= total_rounds {
+        // Set Finalizing — Completed only when all have withdrawn (contract balance = 0)
+        circle.circle_status = CircleStatus::Finalizing;
+        refund_all_prepaid(deps.storage, &mut circle)?;
+        refund_payout_collateral(deps.storage, &mut circle)?;
+        for member in &active_members {
+            update_member_history(deps.storage, member, |h| h.circles_completed += 1)?;
+        }
+
+        // Final distribution (automatic): (1) Creator gets creator_lock back (if not in MEMBER_LOCKED); (2) Each member gets their join-deposit lock back; (3) Penalties split equally
+        let mut total_distributed = Uint128::zero();
+
+        // 1. Creator gets creator lock back (capped for old circles by actual available funds).
+        if !creator_refund_amount.is_zero() && active_members.iter().any(|m| m == &circle.creator_address) {
+            credit_pending_payout(
+                deps.storage,
+                circle_id,
+                &circle.creator_address,
+                creator_refund_amount,
+                &mut circle.total_pending_payouts,
+            )?;
+            total_distributed = total_distributed
+                .checked_add(creator_refund_amount)
+                .unwrap_or(total_distributed);
+        }
+        circle.creator_lock_amount = Uint128::zero();
+
+        // 1b. Accumulating: share out the savings pool. Unpaid loans are settled
+        //     from the borrower's share, then their lock, before step 2 returns it.
+        share_out_savings(deps.storage, &mut circle)?;
+
+        // 2. Each member gets their own join-deposit lock back (from MEMBER_LOCKED_AMOUNTS)
+        let locked_entries: Vec<(Addr, Uint128)> = MEMBER_LOCKED_AMOUNTS
+            .prefix(circle_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter_map(|res| res.ok())
+            .collect();
+        for (member, locked) in &locked_entries {
+            if locked.is_zero() {
+                continue;
+            }
+            // A guarantor is refunded their remaining stake out of the lock first.
+            let own = refund_guaranteed_lock(deps.storage, &mut circle, member, *locked)?;
+            credit_pending_payout(
+                deps.storage,
+                circle_id,
+                member,
+                own,
+                &mut circle.total_pending_payouts,
+            )?;
+            total_distributed = total_distributed.checked_add(*locked).unwrap_or(total_distributed);
+        }
+        for (m, _) in &locked_entries {
+            MEMBER_LOCKED_AMOUNTS.remove(deps.storage, (circle_id, m.clone()));
+        }
+        close_guarantees(deps.storage, &mut circle)?;
+
+        // 3. Penalties split equally among all active members
+        let pool = circle.total_penalties_collected;
+
+        if !pool.is_zero() && !active_members.is_empty() {
+            let member_count = Uint128::from(active_members.len() as u128);
+            let amount_per_member = pool
+                .checked_div(member_count)
+                .unwrap_or(Uint128::zero());
+            let remainder = pool
+                .checked_sub(amount_per_member * member_count)
+                .unwrap_or(Uint128::zero());
+
+            for (idx, member) in active_members.iter().enumerate() {
+                let mut amt = amount_per_member;
+                if idx == 0 {
+                    amt = amt.checked_add(remainder).unwrap_or(amt);
+                }
+                if !amt.is_zero() {
+                    credit_pending_payout(
+                        deps.storage,
+                        circle_id,
+                        member,
+                        amt,
+                        &mut circle.total_pending_payouts,
+                    )?;
+                }
+            }
+        }
+
+        // 4. Reserve remainder to the members who never missed a round
+        //    (all active members if everyone missed at least once).
+        if let Some(ref mut reserve) = circle.reserve {
+            let left = reserve.balance;
+            let honest: Vec<Addr> = active_members
+                .iter()
+                .filter(|m| {
+                    MEMBER_MISSED_PAYMENTS
+                        .may_load(deps.storage, (circle_id, (*m).clone()))
+                        .unwrap_or(None)
+                        .map(|missed| missed.missed_count == 0)
+                        .unwrap_or(true)
+                })
+                .cloned()
+                .collect();
+            let recipients = if honest.is_empty() { &active_members } else { &honest };
+            if !left.is_zero() && !recipients.is_empty() {
+                let count = recipients.len() as u128;
+                let per_member = left.multiply_ratio(1u128, count);
+                let remainder = left - per_member * Uint128::from(count);
+                for (idx, member) in recipients.iter().enumerate() {
+                    let share = if idx == 0 { per_member + remainder } else { per_member };
+                    credit_pending_payout(
+                        deps.storage,
+                        circle_id,
+                        member,
+                        share,
+                        &mut circle.total_pending_payouts,
+                    )?;
+                }
+                reserve.balance = Uint128::zero();
+            }
+        }
+
+        circle.total_amount_locked = Uint128::zero();
+        circle.total_penalties_collected = Uint128::zero();
+
+        // Drain accumulated platform fees to the platform address. Before this
+        // fix, `total_platform_fees_collected` was a pure accumulator with no
+        // outflow path — the corresponding native coins remained on the
+        // contract bank balance after finalization, breaking the documented
+        // invariant in state.rs ("Becomes Completed when contract balance = 0").
+        // We send the fees automatically as part of finalization so new circles
+        // are clean by construction.
+        let fees = circle.total_platform_fees_collected;
+        if !fees.is_zero() {
+            let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
+            outbound_messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: platform_addr.to_string(),
+                amount: vec![Coin {
+                    denom: circle.denomination.clone(),
+                    amount: fees,
+                }],
+            }));
+            platform_fees_sent = fees;
+            circle.total_platform_fees_collected = Uint128::zero();
+        }
+
+        log_event(
+            &mut deps,
+            &env,
+            circle_id,
+            "circle_completed",
+            &format!(
+                "Circle {} completed. All payouts stored in PENDING_PAYOUTS. Platform fees drained: {} usaf.",
+                circle_id, platform_fees_sent
+            ),
+        )?;
+    } else {
+        // Round-progression invariant: current_cycle_index must advance by
+        // exactly 1 per transaction. Catches accidental skips from refactors.
+        let prev_round = circle.current_cycle_index;
+        circle.current_cycle_index = prev_round.checked_add(1).ok_or_else(|| {
+            ContractError::InvalidParameters {
+                msg: "current_cycle_index overflow".to_string(),
+            }
+        })?;
+        if circle.current_cycle_index != prev_round + 1 {
+            return Err(ContractError::InvalidParameters {
+                msg: format!(
+                    "Round progression invariant violated: {} -> {} (must be +1)",
+                    prev_round, circle.current_cycle_index
+                ),
+            });
+        }
+        if let Some(current_date) = circle.next_payout_date {
+            circle.next_payout_date = Some(Timestamp::from_seconds(
+                current_date.seconds() + circle.cycle_duration_secs(),
+            ));
+        }
+    }
+
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    let recipient_attr = if accumulating {
+        "savings_pool".to_string()
+    } else if is_total_at_last_round {
+        format!("{} members", active_members.len())
+    } else {
+        single_recipient
+            .as_ref()
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    let log_msg = if accumulating {
+        format!(
+            "Round {} contributions ({} usaf) added to the savings pool",
+            circle.cycles_completed, payout_amount
+        )
+    } else if is_total_at_last_round {
+        format!(
+            "Payout processed for round {} to all {} members ({} usaf total pending withdrawal)",
+            circle.cycles_completed,
+            active_members.len(),
+            payout_amount
+        )
+    } else {
+        format!(
+            "Payout processed for round {} to {} ({} usaf pending withdrawal)",
+            circle.cycles_completed, recipient_attr, payout_amount
+        )
+    };
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "payout_processed",
+        &log_msg,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(stake_msgs)
+        .add_messages(outbound_messages)
+        .add_attribute("action", "process_payout")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("cycle", circle.cycles_completed.to_string())
+        .add_attribute("recipient", recipient_attr)
+        .add_attribute("amount", payout_amount.to_string())
+        .add_attribute("platform_fees_sent", platform_fees_sent.to_string())
+        .add_attribute("pending_withdrawal", "true"))
+}
+
+// ---------------------------------------------------------------------------
+// Advance Round — move to next round without payout (for Total/MinMembers threshold)
+// ---------------------------------------------------------------------------
+
+fn execute_advance_round(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(circle.circle_status, CircleStatus::Running) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    // Accumulating circles bank every round through ProcessPayout; skipping one
+    // here would leave its deposits out of the savings pool.
+    if circle.savings.is_some() {
+        return Err(ContractError::InvalidParameters {
+            msg: "Accumulating circles advance through ProcessPayout".to_string(),
+        });
+    }
+
+    // Authorization: manual_trigger_enabled means only creator can call (same as ProcessPayout)
+    if circle.manual_trigger_enabled && info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can advance round (manual_trigger_enabled)".to_string(),
+        });
+    }
+
+    // Time gate (end-of-round): prevent back-to-back transitions that can skip rounds.
+    // `next_payout_date` is the round start; only allow advancing after the round has ended.
+    if let Some(next_payout) = circle.next_payout_date {
+        let round_end = next_payout.plus_seconds(circle.cycle_duration_secs());
+        if env.block.time < round_end {
+            return Err(ContractError::CycleNotReady {
+                next_date: round_end.seconds(),
+            });
+        }
+    }
+
+    // Active members (not blocked) — used for round_in_cycle and min_round
+    let active_members: Vec<Addr> = circle
+        .members_list
+        .iter()
+        .filter(|m| {
+            BLOCKED_MEMBERS
+                .may_load(deps.storage, (circle_id, (*m).clone()))
+                .unwrap_or(None)
+                .map(|bc| bc > circle.current_cycle_index)
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect();
+
+    if active_members.is_empty() {
+        return Err(ContractError::InvalidParameters {
+            msg: "No active members".to_string(),
+        });
+    }
+
+    let active_count = active_members.len() as u32;
+    let round_in_cycle = ((circle.current_cycle_index - 1) % active_count) + 1;
+    let min_round_for_distribution =
+        distribution_min_round_for_active(&circle.distribution_threshold, active_count);
+
+    if round_in_cycle >= min_round_for_distribution {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "AdvanceRound only when round_in_cycle < {} (current: {}). Use ProcessPayout for distribution round.",
+                min_round_for_distribution, round_in_cycle
+            ),
+        });
+    }
+
+    consume_prepaid_for_round(&mut deps, &env, &mut circle, &active_members)?;
+
+    // Process missing members: add late fees, check ejection, use locked funds (calendar-based advance)
+    let missing_members: Vec<Addr> = active_members
+        .iter()
+        .filter(|m| {
+            DEPOSITS
+                .may_load(deps.storage, (circle_id, (*m).clone(), circle.current_cycle_index))
+                .unwrap_or(None)
+                .is_none()
+        })
+        .cloned()
+        .collect();
+
+    // Match ProcessPayout semantics: if members are still missing, do not apply
+    // missed-payment late fees before due date + grace period.
+    if let Some(next_payout) = circle.next_payout_date {
+        let grace_end = next_payout.plus_seconds(circle.grace_period_secs());
+        if env.block.time <= grace_end && !missing_members.is_empty() {
+            return Err(ContractError::InvalidParameters {
+                msg: "Grace period not ended for all missing members".to_string(),
+            });
+        }
+    }
+
+    // The round is closing: unfinished partial deposits go back to their owners.
+    refund_partial_deposits(deps.storage, &mut circle, &missing_members)?;
+
+    let mut locked_used_in_advance = Uint128::zero();
+    for member in &missing_members {
+        let mut accumulated = MEMBER_ACCUMULATED_LATE_FEES
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero());
+
+        let mut missed = MEMBER_MISSED_PAYMENTS
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(MemberMissedPayments {
+                member: member.clone(),
+                missed_count: 0,
+                last_missed_cycle: None,
+                last_fee_round: None,
+            });
+
+        if missed.last_missed_cycle != Some(circle.current_cycle_index) {
+            missed.missed_count += 1;
+            missed.last_missed_cycle = Some(circle.current_cycle_index);
+            update_member_history(deps.storage, member, |h| h.missed_rounds += 1)?;
+            accumulated = accumulated
+                .checked_add(late_fee_for_nth_miss(&circle, missed.missed_count))
+                .unwrap_or(accumulated);
+
+            MEMBER_ACCUMULATED_LATE_FEES.save(
+                deps.storage,
+                (circle_id, member.clone()),
+                &accumulated,
+            )?;
+            MEMBER_MISSED_PAYMENTS.save(
+                deps.storage,
+                (circle_id, member.clone()),
+                &missed,
+            )?;
+        }
+
+        // Force-eject when lock is insufficient to cover this round - same
+        // rationale as in execute_process_payout's missing-members loop:
+        // rounds must always progress, the member's MEMBER_LOCKED is their
+        // commitment, and a depleted lock means they've defaulted on the
+        // protocol contract. See the longer comment in process_payout.
+        let orig_lock = original_lock_for_member(&circle, member);
+        let standard_eject = should_eject_member(
+            deps.storage,
+            circle_id,
+            member,
+            orig_lock,
+            circle.exit_penalty_percent,
+            circle.max_missed_payments_allowed,
+        );
+        let lock_insufficient = if member == &circle.creator_address {
+            false
+        } else {
+            current_member_lock(deps.storage, circle_id, member)
+                + payout_collateral_of(deps.storage, circle_id, member)
+                < circle.contribution_amount
+        };
+        if standard_eject || lock_insufficient {
+            // Any post-payout collateral goes to penalties with the ejection.
+            eject_member_from_circle(&mut deps, &env, &mut circle, member)?;
+            continue;
+        }
+
+        // Post-payout collateral covers a paid member's round before the lock.
+        // Never use creator's lock - creator lock must stay intact
+        let owed = circle.contribution_amount;
+        let from_collateral = use_payout_collateral(deps.storage, &mut circle, member, owed)?;
+        let used = if member == &circle.creator_address {
+            Uint128::zero()
+        } else {
+            use_locked_amount_for_member(deps.storage, circle_id, member, owed - from_collateral)?
+        };
+        // Stays in the circle for the distribution round, as a deposit would.
+        circle.total_amount_locked += from_collateral;
+        locked_used_in_advance = locked_used_in_advance
+            .checked_add(used)
+            .unwrap_or(locked_used_in_advance);
+        if !(used + from_collateral).is_zero() {
+            // Record synthetic deposit to preserve round-by-round audit trail.
+            DEPOSITS.save(
+                deps.storage,
+                (circle_id, member.clone(), circle.current_cycle_index),
+                &DepositRecord {
+                    member: member.clone(),
+                    cycle: circle.current_cycle_index,
+                    amount: used + from_collateral,
+                    timestamp: env.block.time,
+                    on_time: false,
+                },
+            )?;
+        }
+    }
+
+    // Also use locked from previously blocked members for this round
+    let blocked_members_list: Vec<Addr> = BLOCKED_MEMBERS
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|res| res.ok().map(|(m, _)| m))
+        .collect();
+
+    for blocked_member in blocked_members_list {
+        if missing_members.iter().any(|m| m == &blocked_member) {
+            continue;
+        }
+        if let Ok(Some(bc)) =
+            BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, blocked_member.clone()))
+        {
+            if bc <= circle.current_cycle_index {
+                let used = use_locked_amount_for_member(
+                    deps.storage,
+                    circle_id,
+                    &blocked_member,
+                    circle.contribution_amount,
+                )?;
+                locked_used_in_advance = locked_used_in_advance
+                    .checked_add(used)
+                    .unwrap_or(locked_used_in_advance);
+                if !used.is_zero() {
+                    // Record synthetic deposit for blocked member coverage in this round.
+                    DEPOSITS.save(
+                        deps.storage,
+                        (circle_id, blocked_member.clone(), circle.current_cycle_index),
+                        &DepositRecord {
+                            member: blocked_member.clone(),
+                            cycle: circle.current_cycle_index,
+                            amount: used,
+                            timestamp: env.block.time,
+                            on_time: false,
+                        },
+                    )?;
+                }
+            }
+        }
+    }
+
+    // Keep total_amount_locked in sync: locked used for missed deposits is no longer "locked"
+    if !locked_used_in_advance.is_zero() {
+        circle.total_amount_locked = circle
+            .total_amount_locked
+            .checked_sub(locked_used_in_advance)
+            .unwrap_or(Uint128::zero());
+    }
+
+    let total_rounds = circle.max_members * circle.total_cycles;
+    if circle.current_cycle_index >= total_rounds {
+        return Err(ContractError::InvalidParameters {
+            msg: "Circle has no more rounds to advance".to_string(),
+        });
+    }
+
+    // Round-progression invariant: current_cycle_index must advance by
+    // exactly 1 per transaction. Catches accidental skips from refactors.
+    let prev_round = circle.current_cycle_index;
+    circle.current_cycle_index = prev_round.checked_add(1).ok_or_else(|| {
+        ContractError::InvalidParameters {
+            msg: "current_cycle_index overflow".to_string(),
+        }
+    })?;
+    if circle.current_cycle_index != prev_round + 1 {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "Round progression invariant violated: {} -> {} (must be +1)",
+                prev_round, circle.current_cycle_index
+            ),
+        });
+    }
+    if let Some(current_date) = circle.next_payout_date {
+        circle.next_payout_date = Some(Timestamp::from_seconds(
+            current_date.seconds() + circle.cycle_duration_secs(),
+        ));
+    }
+
+    let stake_msgs = sync_lock_stake(&mut deps, &env, &mut circle)?;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "round_advanced",
+        &format!(
+            "Advanced to round {} (no payout — distribution at round {})",
+            circle.current_cycle_index, min_round_for_distribution
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(stake_msgs)
+        .add_attribute("action", "advance_round")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("new_cycle_index", circle.current_cycle_index.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Withdraw — member claims all pending payouts at once
+// ---------------------------------------------------------------------------
+
+fn execute_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    // Validate circle exists
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    let stake_msgs = sync_lock_stake(&mut deps, &env, &mut circle)?;
+
+    // Whatever was credited against undelegated locks is not back in the bank
+    // yet: it stays pending until the unbonding matures.
+    let held = lock_backed_hold(deps.storage, &circle, &info.sender, env.block.time)?;
+    let requested = PENDING_PAYOUTS
+        .may_load(deps.storage, (circle_id, info.sender.clone()))?
+        .unwrap_or_default();
+    if !requested.is_zero() && held >= requested {
+        return Err(ContractError::LockStakeUnbonding {
+            circle_id,
+            matures_at: circle
+                .lock_stake
+                .as_ref()
+                .and_then(|stake| stake.unbonding_matures_at)
+                .map(|at| at.seconds())
+                .unwrap_or_default(),
+        });
+    }
+    let pending = debit_pending_payout(
+        deps.storage,
+        circle_id,
+        &info.sender,
+        &mut circle.total_pending_payouts,
+    )?;
+
+    if pending.is_zero() {
+        return Err(ContractError::NoPendingPayouts {});
+    }
+    if held.is_zero() {
+        LOCK_BACKED_PENDING.remove(deps.storage, (circle_id, info.sender.clone()));
+    } else {
+        credit_pending_payout(
+            deps.storage,
+            circle_id,
+            &info.sender,
+            held,
+            &mut circle.total_pending_payouts,
+        )?;
+        LOCK_BACKED_PENDING.save(deps.storage, (circle_id, info.sender.clone()), &held)?;
+    }
+    let pending = pending - held;
+
+    // When Finalizing and this is the last withdrawal, only flip to Completed
+    // if the documented invariant in state.rs actually holds: contract bank
+    // balance, AFTER the BankMsg::Send queued below settles, must be zero AND
+    // no undrained platform fees remain. The previous check (only
+    // `total_pending_payouts.is_zero()`) let the status flip to Completed
+    // while real native funds were still held by the contract (the "Completed
+    // with 26 SAF" bug). Anything residual is recoverable via
+    // `WithdrawPlatformFees` and `SweepDust`.
+    if circle.circle_status == CircleStatus::Finalizing
+        && circle.total_pending_payouts.is_zero()
+    {
+        let bal = deps
+            .querier
+            .query_balance(&env.contract.address, &circle.denomination)?;
+        let post_send = bal.amount.saturating_sub(pending);
+        if post_send.is_zero() && circle.total_platform_fees_collected.is_zero() {
+            circle.circle_status = CircleStatus::Completed;
+        }
+    }
+
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "withdrawal",
+        &format!("Member {} withdrew {} usaf", info.sender, pending),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(stake_msgs)
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: circle.denomination.clone(),
+                amount: pending,
+            }],
+        })
+        .add_attribute("action", "withdraw")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("amount", pending.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Check And Eject — permissionless ejection check
+// ---------------------------------------------------------------------------
+
+fn execute_check_and_eject(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(circle.circle_status, CircleStatus::Running | CircleStatus::Paused) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    let members_snapshot: Vec<Addr> = circle.members_list.clone();
+    let mut ejected_count = 0u32;
+
+    for member in &members_snapshot {
+        let orig_lock = original_lock_for_member(&circle, member);
+        if should_eject_member(
+            deps.storage,
+            circle_id,
+            member,
+            orig_lock,
+            circle.exit_penalty_percent,
+            circle.max_missed_payments_allowed,
+        ) {
+            eject_member_from_circle(&mut deps, &env, &mut circle, member)?;
+            ejected_count += 1;
+        }
+    }
+
+    if ejected_count > 0 {
+        // `payout_order_list`, `payout_amount`, the `calendar_rebuilt` event,
+        // and the min-members breach warning are all already handled per-eject
+        // inside `eject_member_from_circle`. Just persist the aggregated state
+        // and bump `updated_at` so the off-chain sync notices the change.
+        circle.updated_at = env.block.time;
+        CIRCLES.save(deps.storage, circle_id, &circle)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "check_and_eject")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("ejected_count", ejected_count.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Late-fee forgiveness and reinstatement
+// ---------------------------------------------------------------------------
+
+/// Votes needed to forgive `member`: strict majority of the active non-creator
+/// members other than `member` (at least 1).
+fn forgiveness_votes_required(circle: &Circle, member: &Addr) -> u32 {
+    let eligible = circle
+        .members_list
+        .iter()
+        .filter(|m| **m != circle.creator_address && *m != member)
+        .count() as u32;
+    eligible / 2 + 1
+}
+
+fn execute_forgive_late_fees(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Running | CircleStatus::Paused
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    if !circle.members_list.contains(&member) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Late fees can only be forgiven for an active member".to_string(),
+        });
+    }
+    let accumulated = MEMBER_ACCUMULATED_LATE_FEES
+        .may_load(deps.storage, (circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    if amount.is_zero() || accumulated.is_zero() {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Nothing to forgive ({} accumulated)", accumulated),
+        });
+    }
+
+    // The creator and the platform (acting as arbiter) decide alone; other
+    // members vote. A member cannot vote on their own fees.
+    let platform = PLATFORM_CONFIG
+        .may_load(deps.storage)?
+        .map(|c| c.platform_address);
+    let direct = info.sender == circle.creator_address || Some(&info.sender) == platform.as_ref();
+    if !direct && (info.sender == member || !circle.members_list.contains(&info.sender)) {
+        return Err(ContractError::Unauthorized {
+            msg: "Only the creator, the platform, or another active member (by vote) can forgive late fees"
+                .to_string(),
+        });
+    }
+
+    let (votes, required) = if direct {
+        (0, 0)
+    } else {
+        FORGIVENESS_VOTES.save(
+            deps.storage,
+            (circle_id, member.clone(), info.sender.clone()),
+            &amount,
+        )?;
+        let votes = FORGIVENESS_VOTES
+            .prefix((circle_id, member.clone()))
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter_map(|res| res.ok())
+            .filter(|(voter, voted)| {
+                *voted == amount
+                    && *voter != circle.creator_address
+                    && circle.members_list.contains(voter)
+            })
+            .count() as u32;
+        (votes, forgiveness_votes_required(&circle, &member))
+    };
+
+    if !direct && votes < required {
+        log_event(
+            &mut deps,
+            &env,
+            circle_id,
+            "forgiveness_vote",
+            &format!(
+                "{} voted to forgive {} of {}'s late fees ({}/{} votes)",
+                info.sender, amount, member, votes, required
+            ),
+        )?;
+        return Ok(Response::new()
+            .add_attribute("action", "forgive_late_fees")
+            .add_attribute("circle_id", circle_id.to_string())
+            .add_attribute("member", member)
+            .add_attribute("votes", votes.to_string())
+            .add_attribute("votes_required", required.to_string())
+            .add_attribute("forgiven", "0"));
+    }
+
+    let forgiven = amount.min(accumulated);
+    let remaining = accumulated - forgiven;
+    if remaining.is_zero() {
+        MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, member.clone()));
+    } else {
+        MEMBER_ACCUMULATED_LATE_FEES.save(deps.storage, (circle_id, member.clone()), &remaining)?;
+    }
+    let voters: Vec<Addr> = FORGIVENESS_VOTES
+        .prefix((circle_id, member.clone()))
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter_map(|k| k.ok())
+        .collect();
+    for voter in voters {
+        FORGIVENESS_VOTES.remove(deps.storage, (circle_id, member.clone(), voter));
+    }
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "late_fees_forgiven",
+        &format!(
+            "{} of {}'s late fees forgiven by {} ({} remaining)",
+            forgiven,
+            member,
+            if direct { info.sender.to_string() } else { format!("vote {}/{}", votes, required) },
+            remaining
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "forgive_late_fees")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", member)
+        .add_attribute("votes", votes.to_string())
+        .add_attribute("votes_required", required.to_string())
+        .add_attribute("forgiven", forgiven.to_string()))
+}
+
+/// Bring an ejected member back. They top their lock back up to the original
+/// join lock and pay `contribution_amount` for every round spent ejected; the
+/// arrears go to the penalty pool (shared at finalization) since those rounds
+/// were already paid out without them. Their miss counters start fresh.
+fn execute_reinstate_member(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Running | CircleStatus::Paused
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    let Some(blocked_from) =
+        BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, info.sender.clone()))?
+    else {
+        return Err(ContractError::Unauthorized {
+            msg: "Only an ejected member can be reinstated".to_string(),
+        });
+    };
+
+    let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
+    if circle.members_list.len() as u32 >= members_basis {
+        return Err(ContractError::CircleFull { max: members_basis });
+    }
+
+    let original_lock = original_lock_for_member(&circle, &info.sender);
+    let lock_top_up =
+        original_lock.saturating_sub(current_member_lock(deps.storage, circle_id, &info.sender));
+    let rounds_ejected = circle.current_cycle_index.saturating_sub(blocked_from);
+    let arrears = circle
+        .contribution_amount
+        .checked_mul(Uint128::from(rounds_ejected as u128))
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Arrears overflow".to_string(),
+        })?;
+    let required = lock_top_up + arrears;
+
+    let payment = must_pay(&info, &circle.denomination).map_err(|_| {
+        ContractError::InsufficientFunds {
+            required: required.to_string(),
+            sent: "0".to_string(),
+        }
+    })?;
+    if payment < required {
+        return Err(ContractError::InsufficientFunds {
+            required: required.to_string(),
+            sent: payment.to_string(),
+        });
+    }
+    if payment > required {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Attach exactly the lock top-up plus arrears ({})", required),
+        });
+    }
+
+    add_member_locked(
+        deps.storage,
+        circle_id,
+        &info.sender,
+        lock_top_up,
+        &mut circle.total_amount_locked,
+    )?;
+    if !arrears.is_zero() {
+        circle.total_penalties_collected = circle
+            .total_penalties_collected
+            .checked_add(arrears)
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Penalties overflow".to_string(),
+            })?;
+        PENALTIES.save(
+            deps.storage,
+            (circle_id, info.sender.clone(), circle.current_cycle_index),
+            &PenaltyRecord {
+                member: info.sender.clone(),
+                cycle: circle.current_cycle_index,
+                amount: arrears,
+                reason: format!("Reinstatement arrears: {} rounds ejected", rounds_ejected),
+                timestamp: env.block.time,
+            },
+        )?;
+    }
+
+    BLOCKED_MEMBERS.remove(deps.storage, (circle_id, info.sender.clone()));
+    MEMBER_MISSED_PAYMENTS.remove(deps.storage, (circle_id, info.sender.clone()));
+    MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, info.sender.clone()));
+    // Arrears settle every round up to the current one, so they are not "behind".
+    MEMBER_LAST_DEPOSITED_CYCLE.save(
+        deps.storage,
+        (circle_id, info.sender.clone()),
+        &circle.current_cycle_index.saturating_sub(1),
+    )?;
+    circle.members_list.push(info.sender.clone());
+
+    // Back into the payout order only if they have not been paid yet; a member
+    // who already received their pot just keeps contributing.
+    let already_paid = (1..=circle.current_cycle_index).any(|round| {
+        PAYOUTS.has(deps.storage, (circle_id, round, info.sender.clone()))
+    });
+    let restored_to_order = !already_paid;
+    if restored_to_order {
+        if let Some(ref mut order) = circle.payout_order_list {
+            if !order.contains(&info.sender) {
+                order.push(info.sender.clone());
+            }
+        }
+    }
+
+    let total_rounds = members_basis * circle.total_cycles;
+    circle.max_missed_payments_allowed = cap_max_missed_by_rounds(
+        compute_max_missed_scaled(
+            max_missed_base_for_circle(&circle),
+            circle.members_at_start,
+            circle.members_list.len() as u32,
+        ),
+        total_rounds,
+    );
+    circle.payout_amount = circle
+        .contribution_amount
+        .checked_mul(Uint128::from(circle.members_list.len() as u128))
+        .unwrap_or(circle.payout_amount);
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "member_reinstated",
+        &format!(
+            "Member {} reinstated at cycle {} (lock top-up {}, arrears {}, in payout order: {})",
+            info.sender, circle.current_cycle_index, lock_top_up, arrears, restored_to_order
+        ),
+    )?;
+    let start_ts = circle.first_cycle_date.unwrap_or(env.block.time);
+    let pauses = PAUSE_HISTORY
+        .may_load(deps.storage, circle_id)?
+        .unwrap_or_default();
+    let rebuilt = build_distribution_calendar(&circle, start_ts, &pauses);
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "calendar_rebuilt",
+        &format!(
+            "{{reason:\"reinstatement\",active_members:{},calendar:[{}]}}",
+            circle.members_list.len(),
+            rebuilt
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reinstate_member")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("lock_top_up", lock_top_up.to_string())
+        .add_attribute("arrears", arrears.to_string())
+        .add_attribute("restored_to_payout_order", restored_to_order.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Cancel Circle — extended to allow running circles (creator forfeits lock)
+// ---------------------------------------------------------------------------
+
+fn execute_cancel_circle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can cancel circle".to_string(),
+        });
+    }
+
+    let is_running = matches!(
+        circle.circle_status,
+        CircleStatus::Running | CircleStatus::Paused
+    );
+
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Draft | CircleStatus::Open | CircleStatus::Full | CircleStatus::Running | CircleStatus::Paused
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft, Open, Full, Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    // After at least one distribution, cancel is not allowed — circle must complete or be wound down
+    if is_running {
+        let max_cycle = circle
+            .current_cycle_index
+            .max(circle.cycles_completed)
+            .max(1);
+        let mut has_distributed = false;
+        for cycle in 1..=max_cycle {
+            if PAYOUTS
+                .prefix((circle_id, cycle))
+                .range(deps.storage, None, None, Order::Ascending)
+                .next()
+                .is_some()
+            {
+                has_distributed = true;
+                break;
+            }
+        }
+        // An accumulating circle's pool holds every closed round's contributions.
+        if has_distributed || (circle.savings.is_some() && circle.cycles_completed > 0) {
+            return Err(ContractError::CancelNotAllowedAfterDistribution {});
+        }
+    }
+
+    circle.circle_status = CircleStatus::Cancelled;
+    circle.updated_at = env.block.time;
+
+    let mut messages: Vec<CosmosMsg> = Vec::new();
+
+    if is_running {
+        // Cancelling a running circle is the creator's choice — so members
+        // should be made whole. The old code path (a) deducted an exit penalty
+        // from each member's lock refund (unfair: the creator cancelled, not
+        // the member), (b) never refunded the current-cycle deposits made by
+        // anyone, (c) never zeroed `creator_lock_amount`, and (d) used direct
+        // BankMsg sends for the lock refund — so the creator (no
+        // MEMBER_LOCKED_AMOUNTS entry) got NOTHING and the UI had no Withdraw
+        // button. This rewrite credits everything via PENDING_PAYOUTS so a
+        // single Withdraw call by each member recovers what they're owed,
+        // and balances out to bank balance == sum(pending) on success.
+
+        let active_members: Vec<Addr> = circle
+            .members_list
+            .iter()
+            .filter(|m| m.as_ref() != circle.creator_address.as_str())
+            .filter(|m| {
+                BLOCKED_MEMBERS
+                    .may_load(deps.storage, (circle_id, (*m).clone()))
+                    .unwrap_or(None)
+                    .is_none()
+            })
+            .cloned()
+            .collect();
+
+        // 1. Creator forfeits creator_lock_amount → distributed to active
+        //    non-creator members via PENDING_PAYOUTS. Cancellation penalty.
+        let creator_lock = circle.creator_lock_amount;
+        if !creator_lock.is_zero() && !active_members.is_empty() {
+            let count = Uint128::from(active_members.len() as u128);
+            let per_member = creator_lock.multiply_ratio(1u128, count.u128());
+            let remainder = creator_lock
+                .checked_sub(per_member * count)
+                .unwrap_or(Uint128::zero());
+
+            for (idx, member) in active_members.iter().enumerate() {
+                let mut share = per_member;
+                if idx == 0 {
+                    share = share.checked_add(remainder).unwrap_or(share);
+                }
+                if !share.is_zero() {
+                    credit_pending_payout(
+                        deps.storage,
+                        circle_id,
+                        member,
+                        share,
+                        &mut circle.total_pending_payouts,
+                    )?;
+                }
+            }
+        }
+        // Always zero out, whether or not there were members to receive it —
+        // otherwise the field stays stale and future invariant checks lie.
+        circle.creator_lock_amount = Uint128::zero();
+        refund_all_prepaid(deps.storage, &mut circle)?;
+        refund_payout_collateral(deps.storage, &mut circle)?;
+        let roster = circle.members_list.clone();
+        refund_partial_deposits(deps.storage, &mut circle, &roster)?;
+
+        // 2. Refund each non-creator member's FULL locked join-deposit via
+        //    PENDING_PAYOUTS (no exit penalty when the creator is the one
+        //    cancelling). Creator has no MEMBER_LOCKED entry — their initial
+        //    "lock" is `creator_lock_amount`, already handled above.
+        let member_list_snapshot: Vec<Addr> = circle.members_list.clone();
+        for member in &member_list_snapshot {
+            let locked = MEMBER_LOCKED_AMOUNTS
+                .may_load(deps.storage, (circle_id, member.clone()))?
+                .unwrap_or(Uint128::zero());
+            if !locked.is_zero() {
+                let own = refund_guaranteed_lock(deps.storage, &mut circle, member, locked)?;
+                credit_pending_payout(
+                    deps.storage,
+                    circle_id,
+                    member,
+                    own,
+                    &mut circle.total_pending_payouts,
+                )?;
+                debit_member_locked(
+                    deps.storage,
+                    circle_id,
+                    member,
+                    locked,
+                    &mut circle.total_amount_locked,
+                )?;
+            }
+            MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, member.clone()));
+        }
+        close_guarantees(deps.storage, &mut circle)?;
+
+        // 3. Refund every deposit already made in the current (unfinished)
+        //    cycle. Without this the funds sit on the contract forever — the
+        //    cycle's payout will never happen, but `total_amount_locked` and
+        //    the bank balance still hold them. Crediting via PENDING_PAYOUTS
+        //    lets each depositor Withdraw the exact amount they put in.
+        //
+        //    DEPOSITS is keyed `(circle_id, member, cycle)`. The codebase
+        //    loads it by exact key per-member rather than iterating with
+        //    `prefix()`, so do the same here.
+        let unfinished_cycle = circle.current_cycle_index;
+        if unfinished_cycle > 0 {
+            for depositor in &member_list_snapshot {
+                let deposit_record = DEPOSITS.may_load(
+                    deps.storage,
+                    (circle_id, depositor.clone(), unfinished_cycle),
+                )?;
+                let amount = deposit_record.map(|r| r.amount).unwrap_or(Uint128::zero());
+                if amount.is_zero() {
+                    continue;
+                }
+                credit_pending_payout(
+                    deps.storage,
+                    circle_id,
+                    depositor,
+                    amount,
+                    &mut circle.total_pending_payouts,
+                )?;
+            }
+        }
+        messages.extend(sync_lock_stake(&mut deps, &env, &mut circle)?);
+    } else {
+        // Before start: refund all join deposits
+        let locked_entries: Vec<(Addr, Uint128)> = MEMBER_LOCKED_AMOUNTS
+            .prefix(circle_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter_map(|res| res.ok().map(|(m, a)| (m, a)))
+            .collect();
+
+        for (member, amount) in locked_entries {
+            if !amount.is_zero() {
+                let own = refund_guaranteed_lock(deps.storage, &mut circle, &member, amount)?;
+                let refund_msgs = safe_refund_or_queue(
+                    deps.branch(),
+                    &env,
+                    circle_id,
+                    &member,
+                    own,
+                    &circle.denomination,
+                )?;
+                messages.extend(refund_msgs);
+                debit_member_locked(
+                    deps.storage,
+                    circle_id,
+                    &member,
+                    amount,
+                    &mut circle.total_amount_locked,
+                )?;
+            }
+        }
+        close_guarantees(deps.storage, &mut circle)?;
+
+        // Refund creator lock
+        if !circle.creator_lock_amount.is_zero() {
+            let creator_amount = circle.creator_lock_amount;
+            let refund_msgs = safe_refund_or_queue(
+                deps.branch(),
+                &env,
+                circle_id,
+                &circle.creator_address,
+                creator_amount,
+                &circle.denomination,
+            )?;
+            messages.extend(refund_msgs);
+        }
+
+        messages.extend(refund_waitlist(&mut deps, &env, &circle)?);
+    }
+
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "circle_cancelled",
+        &format!(
+            "Circle {} cancelled by {} (was_running: {})",
+            circle_id, info.sender, is_running
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "cancel_circle")
+        .add_attribute("circle_id", circle_id.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Wind Down - settle net positions when a running circle cannot finish
+// ---------------------------------------------------------------------------
+
+fn execute_wind_down_circle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Running | CircleStatus::Paused
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    if circle.savings.is_some() {
+        return Err(ContractError::InvalidParameters {
+            msg: "Accumulating circles settle through the end-of-season share-out".to_string(),
+        });
+    }
+
+    // Only once the roster has collapsed below the minimum (see the
+    // `min_members_breach` event), and then the creator or any member may.
+    let roster_collapsed = (circle.members_list.len() as u32) < circle.min_members_required;
+    let authorized = roster_collapsed
+        && (info.sender == circle.creator_address || circle.members_list.contains(&info.sender));
+    if !authorized {
+        return Err(ContractError::Unauthorized {
+            msg: "Wind-down is only open to the creator or a member once the roster is below min_members_required"
+                .to_string(),
+        });
+    }
+
+    // Prepaid future rounds and partial deposits never entered the pool; hand
+    // them back first so `spendable` below already excludes them.
+    refund_all_prepaid(deps.storage, &mut circle)?;
+    let roster = circle.members_list.clone();
+    refund_partial_deposits(deps.storage, &mut circle, &roster)?;
+
+    // Settle the lock stake before budgeting. A wound-down circle is off
+    // schedule, so every delegated lock starts unbonding here; rewards credited
+    // by the sync are withdrawn with this TX.
+    circle.circle_status = CircleStatus::WoundDown;
+    let pending_before_sync = circle.total_pending_payouts;
+    let stake_msgs = sync_lock_stake(&mut deps, &env, &mut circle)?;
+    let rewards_withdrawn = circle.total_pending_payouts - pending_before_sync;
+    let unbonding = match circle.lock_stake {
+        Some(ref stake) if stake.unbonding_matures_at.is_some() => stake.unbonding,
+        _ => Uint128::zero(),
+    };
+
+    let liquid_balance = deps
+        .querier
+        .query_balance(&env.contract.address, &circle.denomination)
+        .map_err(|e| ContractError::InvalidParameters {
+            msg: format!("Balance query failed: {}", e),
+        })?;
+    let spendable = (liquid_balance.amount + rewards_withdrawn + unbonding)
+        .saturating_sub(circle.total_pending_payouts);
+
+    let positions = compute_wind_down(deps.storage, &circle, spendable)?;
+    // The circle's unbonding locks back that much of the settlements: it is
+    // held from Withdraw until the unbonding matures.
+    let mut lock_backed = unbonding;
+    let mut total_settled = Uint128::zero();
+    for p in &positions {
+        credit_pending_payout(
+            deps.storage,
+            circle_id,
+            &p.member,
+            p.settlement,
+            &mut circle.total_pending_payouts,
+        )?;
+        hold_lock_backed(deps.storage, circle_id, &p.member, p.settlement, &mut lock_backed)?;
+        MEMBER_LOCKED_AMOUNTS.remove(deps.storage, (circle_id, p.member.clone()));
+        MEMBER_ACCUMULATED_LATE_FEES.remove(deps.storage, (circle_id, p.member.clone()));
+        take_payout_collateral(deps.storage, &mut circle, &p.member);
+        if let Some(guarantee) = GUARANTEES
+            .may_load(deps.storage, (circle_id, p.member.clone()))?
+            .filter(|g| g.joined)
+        {
+            hold_lock_backed(
+                deps.storage,
+                circle_id,
+                &guarantee.guarantor,
+                p.guarantor_refund,
+                &mut lock_backed,
+            )?;
+        }
+        refund_guaranteed_lock(deps.storage, &mut circle, &p.member, p.guarantor_refund)?;
+        total_settled += p.settlement;
+    }
+    close_guarantees(deps.storage, &mut circle)?;
+    // Blocked members' collateral was not part of the settlement.
+    refund_payout_collateral(deps.storage, &mut circle)?;
+
+    circle.creator_lock_amount = Uint128::zero();
+    circle.total_amount_locked = Uint128::zero();
+    if let Some(ref mut reserve) = circle.reserve {
+        // Settled through `holdings` in compute_wind_down.
+        reserve.balance = Uint128::zero();
+    }
+    circle.members_paid_this_cycle.clear();
+    circle.members_late_this_cycle.clear();
+    circle.next_payout_date = None;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    let summary: Vec<String> = positions
+        .iter()
+        .map(|p| {
+            format!(
+                "{}: paid {} received {} fees {} lock {} seized {} shortfall {} settled {}",
+                p.member, p.paid_in, p.received, p.late_fees, p.lock, p.seized, p.shortfall, p.settlement
+            )
+        })
+        .collect();
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "circle_wound_down",
+        &format!(
+            "Circle {} wound down by {} at round {} ({})",
+            circle_id,
+            info.sender,
+            circle.current_cycle_index,
+            summary.join("; ")
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(stake_msgs)
+        .add_attribute("action", "wind_down_circle")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("members_settled", positions.len().to_string())
+        .add_attribute("total_settled", total_settled.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Pause / Unpause
+// ---------------------------------------------------------------------------
+
+fn execute_pause_circle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can pause circle".to_string(),
+        });
+    }
+
+    if !matches!(circle.circle_status, CircleStatus::Running) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    record_pause_start(deps.storage, &circle, env.block.time, false)?;
+    circle.circle_status = CircleStatus::Paused;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(&mut deps, &env, circle_id, "circle_paused", &format!("Circle {} paused", circle_id))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_circle")
+        .add_attribute("circle_id", circle_id.to_string()))
+}
+
+fn execute_unpause_circle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can unpause circle".to_string(),
+        });
+    }
+
+    if !matches!(circle.circle_status, CircleStatus::Paused) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    // Shift the schedule by the time spent paused so deadlines, grace periods and
+    // late fees don't count time in which members could not deposit.
+    let mut pauses = PAUSE_HISTORY.may_load(deps.storage, circle_id)?.unwrap_or_default();
+    let mut paused_seconds = 0u64;
+    if let Some(open) = pauses.iter_mut().rev().find(|p| p.resumed_at.is_none()) {
+        paused_seconds = env.block.time.seconds().saturating_sub(open.paused_at.seconds());
+        open.resumed_at = Some(env.block.time);
+        open.duration_seconds = paused_seconds;
+        PAUSE_HISTORY.save(deps.storage, circle_id, &pauses)?;
+    }
+    if paused_seconds > 0 {
+        circle.next_payout_date = circle.next_payout_date.map(|t| t.plus_seconds(paused_seconds));
+        circle.end_date = circle.end_date.map(|t| t.plus_seconds(paused_seconds));
+        circle.total_paused_seconds += paused_seconds;
+    }
+
+    circle.circle_status = CircleStatus::Running;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "circle_unpaused",
+        &format!("Circle {} unpaused (schedule shifted {}s)", circle_id, paused_seconds),
+    )?;
+
+    if paused_seconds > 0 {
+        let start_ts = circle.first_cycle_date.unwrap_or(env.block.time);
+        let rebuilt = build_distribution_calendar(&circle, start_ts, &pauses);
+        log_event(
+            &mut deps,
+            &env,
+            circle_id,
+            "calendar_rebuilt",
+            &format!(
+                "{{reason:\"unpause\",paused_seconds:{},calendar:[{}]}}",
+                paused_seconds, rebuilt
+            ),
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause_circle")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("paused_seconds", paused_seconds.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Emergency Stop
+// ---------------------------------------------------------------------------
+
+fn execute_emergency_stop(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !circle.emergency_stop_enabled {
+        return Err(ContractError::InvalidParameters {
+            msg: "Emergency stop not enabled".to_string(),
+        });
+    }
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can trigger emergency stop".to_string(),
+        });
+    }
+
+    circle.emergency_stop_triggered = true;
+    if circle.circle_status != CircleStatus::Paused {
+        record_pause_start(deps.storage, &circle, env.block.time, true)?;
+    }
+    circle.circle_status = CircleStatus::Paused;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(&mut deps, &env, circle_id, "emergency_stop", &format!("Emergency stop triggered for circle {}", circle_id))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "emergency_stop")
+        .add_attribute("circle_id", circle_id.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Update Circle
+// ---------------------------------------------------------------------------
+
+fn execute_update_circle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    circle_name: Option<String>,
+    circle_description: Option<String>,
+    circle_image: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can update circle".to_string(),
+        });
+    }
+
+    if matches!(
+        circle.circle_status,
+        CircleStatus::Running | CircleStatus::Finalizing | CircleStatus::Completed
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Not Running, Finalizing or Completed".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    if let Some(name) = circle_name {
+        circle.circle_name = name;
+    }
+    if let Some(desc) = circle_description {
+        circle.circle_description = desc;
+    }
+    if let Some(img) = circle_image {
+        circle.circle_image = Some(img);
+    }
+
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_circle")
+        .add_attribute("circle_id", circle_id.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Withdraw Platform Fees (stub)
+// ---------------------------------------------------------------------------
+
+/// Send accumulated platform fees of a circle to the configured platform
+/// address. Permissionless — anyone may trigger because the destination is
+/// fixed in `PLATFORM_CONFIG`. Gated to `Finalizing` / `Completed` so a draw
+/// cannot happen while accounting is mid-cycle. Used to recover fees from
+/// legacy circles that finalized under the previous code path where fees were
+/// never drained.
+fn execute_withdraw_platform_fees(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let id = circle_id.ok_or_else(|| ContractError::InvalidParameters {
+        msg: "circle_id is required".to_string(),
+    })?;
+    let mut circle = CIRCLES.load(deps.storage, id)?;
+
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Finalizing
+            | CircleStatus::Completed
+            | CircleStatus::Cancelled
+            | CircleStatus::WoundDown
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Finalizing, Completed, Cancelled or WoundDown".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    let fees = circle.total_platform_fees_collected;
+    if fees.is_zero() {
+        return Err(ContractError::NoPendingPayouts {});
+    }
+
+    let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
+    circle.total_platform_fees_collected = Uint128::zero();
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        id,
+        "platform_fees_withdrawn",
+        &format!(
+            "{} usaf platform fees sent to {} (triggered by {})",
+            fees, platform_addr, info.sender
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: platform_addr.to_string(),
+            amount: vec![Coin {
+                denom: circle.denomination.clone(),
+                amount: fees,
+            }],
+        })
+        .add_attribute("action", "withdraw_platform_fees")
+        .add_attribute("circle_id", id.to_string())
+        .add_attribute("amount", fees.to_string()))
+}
+
+/// Sweep residual native funds on a Finalizing/Completed/Cancelled circle
+/// by crediting them to the CREATOR's `PENDING_PAYOUTS`, so the creator can
+/// reclaim them with a normal `Withdraw` call.
+///
+/// Permissionless. Only runs when every member has withdrawn
+/// (`total_pending_payouts == 0`).
+/// Residual = on-chain bank balance minus undrained platform fees
+/// (use `WithdrawPlatformFees` for those).
+///
+/// Use cases:
+/// - Legacy circles that finalized before the `members_at_start` fix in
+///   `total_rounds`: the contract zeroed `creator_lock_amount` but never
+///   credited the refund (e.g. an `Ws Savings Circle1` with 14 SAF stuck).
+///   This sweep delivers the funds to the creator.
+/// - Old residuals (truncated creator-lock refund, headroom remainder from
+///   over-deposits) — same recovery path.
+///
+/// If the creator is no longer addressable (somehow removed; shouldn't
+/// happen), the funds fall back to the platform address to avoid being
+/// permanently locked.
+fn execute_sweep_dust(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Finalizing
+            | CircleStatus::Completed
+            | CircleStatus::Cancelled
+            | CircleStatus::WoundDown
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Finalizing, Completed, Cancelled or WoundDown".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    if !circle.total_pending_payouts.is_zero() {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "Pending payouts must be zero before sweep (still {})",
+                circle.total_pending_payouts
+            ),
+        });
+    }
+
+    let bal = deps
+        .querier
+        .query_balance(&env.contract.address, &circle.denomination)?;
+    // Reserve undrained platform fees - those go through WithdrawPlatformFees,
+    // not the dust path, so the platform address is not double-credited.
+    let dust = bal
+        .amount
+        .checked_sub(circle.total_platform_fees_collected)
+        .unwrap_or(Uint128::zero());
+
+    if dust.is_zero() {
+        return Err(ContractError::NoPendingPayouts {});
+    }
+
+    // Credit the residual to the CREATOR's pending payouts so they can
+    // reclaim it with a normal `Withdraw` call. This is the correct owner
+    // for stuck creator-lock refunds (the most common cause of dust).
+    //
+    // After this credit, `total_pending_payouts` is non-zero again - so the
+    // circle goes BACK to Finalizing (if it had flipped to Completed) until
+    // the creator's `Withdraw` zeroes it. Documented invariant preserved:
+    // Completed only when bank balance == 0.
+    credit_pending_payout(
+        deps.storage,
+        circle_id,
+        &circle.creator_address,
+        dust,
+        &mut circle.total_pending_payouts,
+    )?;
+    if circle.circle_status == CircleStatus::Completed {
+        // We just made the chain truth non-zero again; reflect that.
+        circle.circle_status = CircleStatus::Finalizing;
+    }
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "dust_swept",
+        &format!(
+            "{} usaf residual credited to creator {} (triggered by {}); creator can now Withdraw",
+            dust, circle.creator_address, info.sender
+        ),
+    )?;
+
+    // No outbound BankMsg - the creator will pull the funds via Withdraw,
+    // which keeps `total_pending_payouts` accounting consistent.
+    Ok(Response::new()
+        .add_attribute("action", "sweep_dust")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("recipient", circle.creator_address.to_string())
+        .add_attribute("amount", dust.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Deposit Creator Reward - platform sends X% of total volume to creator
+// ---------------------------------------------------------------------------
+
+/// Platform-funded reward for the creator, paid out of the platform / server
+/// wallet when a circle transitions to `Finalizing`. The caller attaches the
+/// reward amount as funds in the circle's denomination; the contract validates
+/// it, credits the creator's `PENDING_PAYOUTS`, and locks the per-circle
+/// idempotency flag so a duplicate broadcast cannot double-credit.
+///
+/// Design notes
+/// ------------
+///   - Permissionless: sending funds INTO the contract for someone else is
+///     harmless and lets any external party (DAO, partner, the server itself)
+///     boost a circle. The idempotency guard (CREATOR_REWARDS_CREDITED)
+///     prevents abuse.
+///   - Status gate is `Finalizing` (NOT Completed). At Finalizing the final
+///     round's PENDING_PAYOUTS are already credited; the creator's first
+///     `Withdraw` call after this credit will pick up both the round payout(s)
+///     AND the reward in one go. Pre-Finalizing the round flow hasn't run so
+///     the volume basis isn't fully known yet; post-Completed the bank should
+///     already be drained and adding to PENDING_PAYOUTS would resurrect the
+///     "Completed with non-zero balance" state we just fixed.
+///   - The reward is added to `total_pending_payouts` so the bank-vs-pending
+///     invariant on Withdraw stays consistent: the funds we just received
+///     are immediately backed by an equal pending credit.
+fn execute_deposit_creator_reward(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    // Status gate: only at Finalizing. See doc-comment above.
+    if !matches!(circle.circle_status, CircleStatus::Finalizing) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Finalizing".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    // Idempotency: refuse a second reward credit for the same circle.
+    if CREATOR_REWARDS_CREDITED
+        .may_load(deps.storage, circle_id)?
+        .is_some()
+    {
+        return Err(ContractError::InvalidParameters {
+            msg: "Creator reward already credited for this circle".to_string(),
+        });
+    }
+
+    // Pull attached funds in the circle's denomination.
+    let amount = must_pay(&info, &circle.denomination).map_err(|_| {
+        ContractError::InsufficientFunds {
+            required: "non-zero reward in circle denomination".to_string(),
+            sent: "0".to_string(),
+        }
+    })?;
+    if amount.is_zero() {
+        return Err(ContractError::InsufficientFunds {
+            required: "non-zero reward".to_string(),
+            sent: "0".to_string(),
+        });
+    }
+
+    // Credit the creator's pending payouts. They will pick this up via the
+    // existing `Withdraw` flow alongside any final-round payouts.
+    credit_pending_payout(
+        deps.storage,
+        circle_id,
+        &circle.creator_address,
+        amount,
+        &mut circle.total_pending_payouts,
+    )?;
+
+    CREATOR_REWARDS_CREDITED.save(deps.storage, circle_id, &amount)?;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "creator_reward_credited",
+        &format!(
+            "{} usaf reward credited to creator {} by {}",
+            amount, circle.creator_address, info.sender
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit_creator_reward")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("creator", circle.creator_address.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("funder", info.sender.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Creator Succession - handover by creator nomination or member vote
+// ---------------------------------------------------------------------------
+
+fn ensure_succession_allowed(circle: &Circle) -> Result<(), ContractError> {
+    if matches!(
+        circle.circle_status,
+        CircleStatus::Finalizing
+            | CircleStatus::Completed
+            | CircleStatus::Cancelled
+            | CircleStatus::WoundDown
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft, Open, Full, Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+    Ok(())
+}
+
+fn execute_transfer_creatorship(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    new_creator: Addr,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can transfer creatorship".to_string(),
+        });
+    }
+    ensure_succession_allowed(&circle)?;
+    if new_creator == circle.creator_address || !circle.members_list.contains(&new_creator) {
+        return Err(ContractError::InvalidParameters {
+            msg: "New creator must be another active member of the circle".to_string(),
+        });
+    }
+
+    CREATOR_NOMINATIONS.save(
+        deps.storage,
+        circle_id,
+        &CreatorNomination {
+            nominee: new_creator.clone(),
+            initiated_by: "creator".to_string(),
+            nominated_at: env.block.time,
+        },
+    )?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "creator_nominated",
+        &format!("Creator {} nominated {} as successor", info.sender, new_creator),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_creatorship")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("nominee", new_creator))
+}
+
+fn execute_vote_creator_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    nominee: Addr,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    ensure_succession_allowed(&circle)?;
+    if info.sender == circle.creator_address || !circle.members_list.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            msg: "Only active non-creator members can vote on creatorship".to_string(),
+        });
+    }
+    if nominee == circle.creator_address || !circle.members_list.contains(&nominee) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Nominee must be another active member of the circle".to_string(),
+        });
+    }
+
+    CREATOR_VOTES.save(deps.storage, (circle_id, info.sender.clone()), &nominee)?;
+
+    // Only votes from current active non-creator members count.
+    let votes = CREATOR_VOTES
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|res| res.ok())
+        .filter(|(voter, choice)| {
+            *choice == nominee
+                && *voter != circle.creator_address
+                && circle.members_list.contains(voter)
+        })
+        .count() as u32;
+    let required = creator_votes_required(&circle);
+    let nominated = votes >= required;
+
+    if nominated {
+        CREATOR_NOMINATIONS.save(
+            deps.storage,
+            circle_id,
+            &CreatorNomination {
+                nominee: nominee.clone(),
+                initiated_by: "vote".to_string(),
+                nominated_at: env.block.time,
+            },
+        )?;
+    }
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "creator_vote",
+        &format!(
+            "{} voted for {} as creator ({}/{} votes{})",
+            info.sender,
+            nominee,
+            votes,
+            required,
+            if nominated { ", nominated" } else { "" }
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote_creator_transfer")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("nominee", nominee)
+        .add_attribute("votes", votes.to_string())
+        .add_attribute("votes_required", required.to_string())
+        .add_attribute("nominated", nominated.to_string()))
+}
+
+fn execute_accept_creatorship(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    ensure_succession_allowed(&circle)?;
+    let nomination = CREATOR_NOMINATIONS
+        .may_load(deps.storage, circle_id)?
+        .filter(|n| n.nominee == info.sender)
+        .ok_or_else(|| ContractError::Unauthorized {
+            msg: "No pending creatorship nomination for sender".to_string(),
+        })?;
+    if !circle.members_list.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            msg: "Not a member of this circle".to_string(),
+        });
+    }
+
+    // The new creator is collateralized under the circle's policy: their join lock
+    // counts toward it and they attach the difference.
+    let required_lock = compute_creator_lock(
+        &circle.creator_collateral_policy,
+        circle.contribution_amount,
+        circle.max_members,
+    )?;
+    let join_lock = current_member_lock(deps.storage, circle_id, &info.sender);
+    let top_up = required_lock.saturating_sub(join_lock);
+    let payment = if top_up.is_zero() {
+        Uint128::zero()
+    } else {
+        must_pay(&info, &circle.denomination).map_err(|_| ContractError::InsufficientFunds {
+            required: top_up.to_string(),
+            sent: "0".to_string(),
+        })?
+    };
+    if payment < top_up {
+        return Err(ContractError::InsufficientFunds {
+            required: top_up.to_string(),
+            sent: payment.to_string(),
+        });
+    }
+    if payment > top_up || (top_up.is_zero() && !info.funds.is_empty()) {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Attach exactly the creator lock top-up ({})", top_up),
+        });
+    }
+
+    // Settle the outgoing creator: they stay a member with contribution_amount
+    // as their join lock; the rest of the old creator lock is refunded.
+    let previous = circle.creator_address.clone();
+    let old_lock = circle.creator_lock_amount;
+    circle.total_amount_locked = circle.total_amount_locked.saturating_sub(old_lock);
+    circle.creator_lock_amount = Uint128::zero();
+    let kept = if circle.members_list.contains(&previous) {
+        old_lock.min(circle.contribution_amount)
+    } else {
+        Uint128::zero()
+    };
+    add_member_locked(
+        deps.storage,
+        circle_id,
+        &previous,
+        kept,
+        &mut circle.total_amount_locked,
+    )?;
+    let refund = old_lock - kept;
+    let mut messages = vec![];
+    if circle.lock_stake.is_some() {
+        // The old lock may be delegated: refund through Withdraw, holding what
+        // the sync below has to undelegate until it matures.
+        credit_pending_payout(
+            deps.storage,
+            circle_id,
+            &previous,
+            refund,
+            &mut circle.total_pending_payouts,
+        )?;
+    } else {
+        messages = safe_refund_or_queue(
+            deps.branch(),
+            &env,
+            circle_id,
+            &previous,
+            refund,
+            &circle.denomination,
+        )?;
+    }
+
+    install_creator(deps.storage, &mut circle, &info.sender, top_up)?;
+    if let Some(delegated_before) = circle.lock_stake.as_ref().map(|stake| stake.delegated) {
+        messages.extend(sync_lock_stake(&mut deps, &env, &mut circle)?);
+        let delegated = circle.lock_stake.as_ref().map(|stake| stake.delegated).unwrap_or_default();
+        let mut lock_backed = delegated_before.saturating_sub(delegated);
+        hold_lock_backed(deps.storage, circle_id, &previous, refund, &mut lock_backed)?;
+    }
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "creatorship_transferred",
+        &format!(
+            "Creatorship passed from {} to {} (by {}, creator_lock: {}, refunded: {})",
+            previous, info.sender, nomination.initiated_by, circle.creator_lock_amount, refund
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "accept_creatorship")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("previous_creator", previous)
+        .add_attribute("new_creator", info.sender)
+        .add_attribute("creator_lock_amount", circle.creator_lock_amount.to_string())
+        .add_attribute("refund_amount", refund.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Platform Collateral Config - approved creator-lock policies and minimum
+// ---------------------------------------------------------------------------
+
+fn execute_set_collateral_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowed_policies: Vec<CollateralPolicyKind>,
+    min_lock_bps: u64,
+) -> Result<Response, ContractError> {
+    let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
+    if info.sender != platform_addr {
+        return Err(ContractError::Unauthorized {
+            msg: "Only the platform address can set the collateral config".to_string(),
+        });
+    }
+    if allowed_policies.is_empty() {
+        return Err(ContractError::InvalidParameters {
+            msg: "allowed_policies must contain at least one policy kind".to_string(),
+        });
+    }
+
+    let mut allowed: Vec<CollateralPolicyKind> = vec![];
+    for kind in allowed_policies {
+        if !allowed.contains(&kind) {
+            allowed.push(kind);
+        }
+    }
+    COLLATERAL_CONFIG.save(
+        deps.storage,
+        &CollateralConfig {
+            allowed_policies: allowed,
+            min_lock_bps,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_collateral_config")
+        .add_attribute("min_lock_bps", min_lock_bps.to_string()))
+}
+
+fn execute_set_attestation_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    registry: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let mut config = PLATFORM_CONFIG.load(deps.storage)?;
+    if info.sender != config.platform_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only the platform address can set the attestation registry".to_string(),
+        });
+    }
+    config.attestation_registry = registry
+        .map(|addr| deps.api.addr_validate(addr.as_str()))
+        .transpose()?;
+    PLATFORM_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_attestation_registry")
+        .add_attribute(
+            "registry",
+            config
+                .attestation_registry
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
+// ---------------------------------------------------------------------------
+// Lock Staking - delegate idle locks, share rewards, unbond before the end
+// ---------------------------------------------------------------------------
+
+fn execute_set_staking_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    validators: Vec<String>,
+    unbonding_period_secs: u64,
+) -> Result<Response, ContractError> {
+    let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
+    if info.sender != platform_addr {
+        return Err(ContractError::Unauthorized {
+            msg: "Only the platform address can set the staking config".to_string(),
+        });
+    }
+    if unbonding_period_secs == 0 {
+        return Err(ContractError::InvalidParameters {
+            msg: "unbonding_period_secs must be greater than 0".to_string(),
+        });
+    }
+
+    let mut approved: Vec<String> = vec![];
+    for validator in validators {
+        if validator.is_empty() {
+            return Err(ContractError::InvalidParameters {
+                msg: "validator address cannot be empty".to_string(),
+            });
+        }
+        if !approved.contains(&validator) {
+            approved.push(validator);
+        }
+    }
+    let count = approved.len();
+    STAKING_CONFIG.save(
+        deps.storage,
+        &StakingConfig {
+            validators: approved,
+            unbonding_period_secs,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_staking_config")
+        .add_attribute("validators", count.to_string())
+        .add_attribute("unbonding_period_secs", unbonding_period_secs.to_string()))
+}
+
+fn execute_sync_lock_stake(
+    mut deps: DepsMut,
+    env: Env,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    if circle.lock_stake.is_none() {
+        return Err(ContractError::InvalidParameters {
+            msg: "Circle has no staked locks".to_string(),
+        });
+    }
+    let messages = sync_lock_stake(&mut deps, &env, &mut circle)?;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    let (delegated, unbonding) = circle
+        .lock_stake
+        .map(|stake| (stake.delegated, stake.unbonding))
+        .unwrap_or_default();
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "sync_lock_stake")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("delegated", delegated.to_string())
+        .add_attribute("unbonding", unbonding.to_string()))
+}
+
+/// Locks still held for a circle: creator_lock_amount plus every MEMBER_LOCKED_AMOUNTS entry.
+fn lock_holders(storage: &dyn Storage, circle: &Circle) -> Vec<(Addr, Uint128)> {
+    let mut holders: Vec<(Addr, Uint128)> = vec![];
+    if !circle.creator_lock_amount.is_zero() {
+        holders.push((circle.creator_address.clone(), circle.creator_lock_amount));
+    }
+    holders.extend(
+        MEMBER_LOCKED_AMOUNTS
+            .prefix(circle.circle_id)
+            .range(storage, None, None, Order::Ascending)
+            .filter_map(|res| res.ok())
+            .filter(|(_, locked)| !locked.is_zero()),
+    );
+    holders
+}
+
+/// Whether locks delegated now could still be unbonded before `end_date`.
+fn stake_on_schedule(circle: &Circle, now: Timestamp, unbonding_period_secs: u64) -> bool {
+    matches!(circle.circle_status, CircleStatus::Running | CircleStatus::Paused)
+        && circle
+            .end_date
+            .map(|end| now.plus_seconds(unbonding_period_secs) < end)
+            .unwrap_or(false)
+}
+
+/// Fold rewards pending on `validator` into its reward index and withdraw them.
+/// Runs before any change to the validator's bonded amount (the chain pays
+/// pending rewards out on every delegation change).
+fn accrue_validator_rewards(
+    deps: &mut DepsMut,
+    env: &Env,
+    validator: &str,
+) -> Result<(ValidatorStake, Vec<CosmosMsg>), ContractError> {
+    let mut stake = VALIDATOR_STAKES
+        .may_load(deps.storage, validator)?
+        .unwrap_or_default();
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(delegation) = deps.querier.query_delegation(&env.contract.address, validator)? {
+        let rewards: Uint128 = delegation
+            .accumulated_rewards
+            .iter()
+            .filter(|c| c.denom == delegation.amount.denom)
+            .map(|c| c.amount)
+            .sum();
+        if !rewards.is_zero() && !stake.bonded.is_zero() {
+            stake.reward_index += Decimal::from_ratio(rewards, stake.bonded);
+            messages.push(CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+                validator: validator.to_string(),
+            }));
+        }
+    }
+    VALIDATOR_STAKES.save(deps.storage, validator, &stake)?;
+    Ok((stake, messages))
+}
+
+/// Delegate the circle's locks at start when it opted into `stake_locks`. Skipped
+/// (the circle simply runs unstaked) when no validator is approved any more or the
+/// schedule is shorter than the unbonding period.
+fn start_lock_stake(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &mut Circle,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    if !circle.stake_locks || circle.lock_stake.is_some() {
+        return Ok(vec![]);
+    }
+    let config = STAKING_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    if config.validators.is_empty()
+        || !stake_on_schedule(circle, env.block.time, config.unbonding_period_secs)
+    {
+        return Ok(vec![]);
+    }
+    let holders = lock_holders(deps.storage, circle);
+    let amount: Uint128 = holders.iter().map(|(_, locked)| *locked).sum();
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+    let validator =
+        config.validators[(circle.circle_id % config.validators.len() as u64) as usize].clone();
+
+    let (mut validator_stake, mut messages) = accrue_validator_rewards(deps, env, &validator)?;
+    validator_stake.bonded += amount;
+    VALIDATOR_STAKES.save(deps.storage, &validator, &validator_stake)?;
+    messages.push(CosmosMsg::Staking(StakingMsg::Delegate {
+        validator: validator.clone(),
+        amount: Coin {
+            denom: circle.denomination.clone(),
+            amount,
+        },
+    }));
+    circle.lock_stake = Some(LockStake {
+        validator: validator.clone(),
+        delegated: amount,
+        unbonding: Uint128::zero(),
+        unbonding_matures_at: None,
+        reward_index: validator_stake.reward_index,
+        rewards_distributed: Uint128::zero(),
+        holders,
+    });
+
+    log_event(
+        deps,
+        env,
+        circle.circle_id,
+        "locks_delegated",
+        &format!("Delegated {} {} of locks to {}", amount, circle.denomination, validator),
+    )?;
+    Ok(messages)
+}
+
+/// Settle a staked circle: credit rewards accrued since the last settlement to
+/// lock holders (pro rata to their locks at delegation, among current members)
+/// via PENDING_PAYOUTS, then undelegate what is no longer backed by locks — or
+/// everything once the circle is off schedule. Called wherever locks are consumed
+/// and by `SyncLockStake`; Withdraw calls it first so it never misses a change.
+fn sync_lock_stake(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &mut Circle,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut stake = match circle.lock_stake.take() {
+        Some(stake) => stake,
+        None => return Ok(vec![]),
+    };
+    let now = env.block.time;
+    if stake.unbonding_matures_at.map(|at| now >= at).unwrap_or(false) {
+        stake.unbonding = Uint128::zero();
+        stake.unbonding_matures_at = None;
+    }
+
+    let (mut validator_stake, mut messages) = accrue_validator_rewards(deps, env, &stake.validator)?;
+    let earned = stake.delegated * (validator_stake.reward_index - stake.reward_index);
+    stake.reward_index = validator_stake.reward_index;
+    let eligible: Vec<(Addr, Uint128)> = stake
+        .holders
+        .iter()
+        .filter(|(holder, _)| circle.members_list.contains(holder))
+        .cloned()
+        .collect();
+    let weight: Uint128 = eligible.iter().map(|(_, locked)| *locked).sum();
+    let mut distributed = Uint128::zero();
+    if !earned.is_zero() && !weight.is_zero() {
+        for (holder, locked) in &eligible {
+            let share = earned.multiply_ratio(*locked, weight);
+            credit_pending_payout(
+                deps.storage,
+                circle.circle_id,
+                holder,
+                share,
+                &mut circle.total_pending_payouts,
+            )?;
+            distributed += share;
+        }
+        stake.rewards_distributed += distributed;
+    }
+
+    let config = STAKING_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    let target = if stake_on_schedule(circle, now, config.unbonding_period_secs) {
+        let locks: Uint128 = lock_holders(deps.storage, circle)
+            .iter()
+            .map(|(_, locked)| *locked)
+            .sum();
+        stake.delegated.min(locks)
+    } else {
+        Uint128::zero()
+    };
+    let undelegate = stake.delegated - target;
+    if !undelegate.is_zero() {
+        validator_stake.bonded = validator_stake.bonded.saturating_sub(undelegate);
+        VALIDATOR_STAKES.save(deps.storage, &stake.validator, &validator_stake)?;
+        stake.delegated = target;
+        stake.unbonding += undelegate;
+        stake.unbonding_matures_at = Some(now.plus_seconds(config.unbonding_period_secs));
+        messages.push(CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: stake.validator.clone(),
+            amount: Coin {
+                denom: circle.denomination.clone(),
+                amount: undelegate,
+            },
+        }));
+    }
+
+    if !distributed.is_zero() || !undelegate.is_zero() {
+        log_event(
+            deps,
+            env,
+            circle.circle_id,
+            "lock_stake_synced",
+            &format!(
+                "Rewards {} credited to lock holders; undelegated {} from {} (still delegated: {})",
+                distributed, undelegate, stake.validator, stake.delegated
+            ),
+        )?;
+    }
+    circle.lock_stake = Some(stake);
+    Ok(messages)
+}
+
+// ---------------------------------------------------------------------------
+// Guarantors - third parties posting part or all of a member's join lock
+// ---------------------------------------------------------------------------
+
+fn execute_set_guarantor_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_members_backed: u32,
+) -> Result<Response, ContractError> {
+    let platform_addr = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
+    if info.sender != platform_addr {
+        return Err(ContractError::Unauthorized {
+            msg: "Only the platform address can set the guarantor config".to_string(),
+        });
+    }
+    GUARANTOR_CONFIG.save(deps.storage, &GuarantorConfig { max_members_backed })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_guarantor_config")
+        .add_attribute("max_members_backed", max_members_backed.to_string()))
+}
+
+fn ensure_accepting_guarantees(circle: &Circle) -> Result<(), ContractError> {
+    if !matches!(circle.circle_status, CircleStatus::Draft | CircleStatus::Open) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft or Open".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+    Ok(())
+}
+
+fn execute_nominate_guarantor(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    guarantor: Addr,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    ensure_accepting_guarantees(&circle)?;
+    if circle.members_list.contains(&info.sender) {
+        return Err(ContractError::AlreadyMember {
+            address: info.sender.to_string(),
+        });
+    }
+    let guarantor = deps.api.addr_validate(guarantor.as_str())?;
+    if guarantor == info.sender {
+        return Err(ContractError::InvalidParameters {
+            msg: "A member cannot be their own guarantor".to_string(),
+        });
+    }
+    if GUARANTEES.has(deps.storage, (circle_id, info.sender.clone())) {
+        return Err(ContractError::InvalidParameters {
+            msg: "A guarantee is already posted for this member".to_string(),
+        });
+    }
+
+    GUARANTOR_NOMINATIONS.save(deps.storage, (circle_id, info.sender.clone()), &guarantor)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "guarantor_nominated",
+        &format!("{} nominated {} as guarantor", info.sender, guarantor),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "nominate_guarantor")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", info.sender)
+        .add_attribute("guarantor", guarantor))
+}
+
+fn execute_guarantee_member(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member: Addr,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    ensure_accepting_guarantees(&circle)?;
+    let member = deps.api.addr_validate(member.as_str())?;
+
+    let nominated = GUARANTOR_NOMINATIONS.may_load(deps.storage, (circle_id, member.clone()))?;
+    if nominated.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            msg: "Not the guarantor nominated by this member".to_string(),
+        });
+    }
+    if circle.members_list.contains(&member) {
+        return Err(ContractError::AlreadyMember {
+            address: member.to_string(),
+        });
+    }
+
+    let posted = must_pay(&info, &circle.denomination).map_err(|_| {
+        ContractError::InsufficientFunds {
+            required: circle.contribution_amount.to_string(),
+            sent: "0".to_string(),
+        }
+    })?;
+    if posted > circle.contribution_amount {
+        return Err(ContractError::InvalidParameters {
+            msg: format!(
+                "A guarantee cannot exceed the join lock ({})",
+                circle.contribution_amount
+            ),
+        });
+    }
+
+    let config = GUARANTOR_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    let backed = GUARANTOR_BACKINGS
+        .sub_prefix(info.sender.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u32;
+    if backed >= config.max_members_backed {
+        return Err(ContractError::GuarantorLimitReached {
+            guarantor: info.sender.to_string(),
+            max: config.max_members_backed,
+        });
+    }
+
+    GUARANTEES.save(
+        deps.storage,
+        (circle_id, member.clone()),
+        &Guarantee {
+            guarantor: info.sender.clone(),
+            posted,
+            stake: posted,
+            posted_at: env.block.time,
+            joined: false,
+        },
+    )?;
+    GUARANTOR_BACKINGS.save(
+        deps.storage,
+        (info.sender.clone(), circle_id, member.clone()),
+        &posted,
+    )?;
+    GUARANTOR_NOMINATIONS.remove(deps.storage, (circle_id, member.clone()));
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "member_guaranteed",
+        &format!(
+            "{} posted {} of {}'s join lock as guarantor",
+            info.sender, posted, member
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "guarantee_member")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", member)
+        .add_attribute("guarantor", info.sender)
+        .add_attribute("posted", posted.to_string()))
+}
+
+fn execute_revoke_guarantee(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member: Addr,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let guarantee = GUARANTEES
+        .may_load(deps.storage, (circle_id, member.clone()))?
+        .filter(|g| g.guarantor == info.sender)
+        .ok_or_else(|| ContractError::Unauthorized {
+            msg: "No guarantee of yours for this member".to_string(),
+        })?;
+    if guarantee.joined {
+        return Err(ContractError::InvalidParameters {
+            msg: "The member has joined; the stake comes back with their lock".to_string(),
+        });
+    }
+    remove_guarantee(deps.storage, circle_id, &member, &info.sender);
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "guarantee_revoked",
+        &format!("{} revoked the guarantee for {}", info.sender, member),
+    )?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: circle.denomination,
+                amount: guarantee.posted,
+            }],
+        })
+        .add_attribute("action", "revoke_guarantee")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", member)
+        .add_attribute("refunded", guarantee.posted.to_string()))
+}
+
+fn remove_guarantee(storage: &mut dyn Storage, circle_id: u64, member: &Addr, guarantor: &Addr) {
+    GUARANTEES.remove(storage, (circle_id, member.clone()));
+    GUARANTOR_BACKINGS.remove(storage, (guarantor.clone(), circle_id, member.clone()));
+}
+
+/// Part of `refund` (a returned join lock) owed to `member`'s guarantor: what is
+/// left of their stake comes out first.
+fn guarantor_share(storage: &dyn Storage, circle_id: u64, member: &Addr, refund: Uint128) -> Uint128 {
+    GUARANTEES
+        .may_load(storage, (circle_id, member.clone()))
+        .unwrap_or(None)
+        .filter(|g| g.joined)
+        .map(|g| g.stake.min(refund))
+        .unwrap_or_default()
+}
+
+/// Return `refund` of `member`'s join lock: the guarantor's share is credited to
+/// their PENDING_PAYOUTS and the guarantee closed. Returns the member's part.
+fn refund_guaranteed_lock(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+    refund: Uint128,
+) -> Result<Uint128, ContractError> {
+    let guarantee = match GUARANTEES.may_load(storage, (circle.circle_id, member.clone()))? {
+        Some(g) if g.joined => g,
+        _ => return Ok(refund),
+    };
+    let share = guarantee.stake.min(refund);
+    credit_pending_payout(
+        storage,
+        circle.circle_id,
+        &guarantee.guarantor,
+        share,
+        &mut circle.total_pending_payouts,
+    )?;
+    remove_guarantee(storage, circle.circle_id, member, &guarantee.guarantor);
+    Ok(refund - share)
+}
+
+/// Charge `penalty` against `member`'s guarantor stake first (ejection).
+/// Returns what the guarantor lost.
+fn draw_guarantor_stake(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    penalty: Uint128,
+) -> StdResult<Uint128> {
+    let mut guarantee = match GUARANTEES.may_load(storage, (circle_id, member.clone()))? {
+        Some(g) if g.joined => g,
+        _ => return Ok(Uint128::zero()),
+    };
+    let drawn = guarantee.stake.min(penalty);
+    guarantee.stake -= drawn;
+    GUARANTEES.save(storage, (circle_id, member.clone()), &guarantee)?;
+    Ok(drawn)
+}
+
+/// Close the guarantees still open once the circle settles: a stake whose member
+/// never joined goes back in full; a joined member's stake not returned with a
+/// lock by now was used up.
+fn close_guarantees(storage: &mut dyn Storage, circle: &mut Circle) -> Result<(), ContractError> {
+    let open: Vec<(Addr, Guarantee)> = GUARANTEES
+        .prefix(circle.circle_id)
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|r| r.ok())
+        .collect();
+    for (member, guarantee) in open {
+        if !guarantee.joined {
+            credit_pending_payout(
+                storage,
+                circle.circle_id,
+                &guarantee.guarantor,
+                guarantee.posted,
+                &mut circle.total_pending_payouts,
+            )?;
+        }
+        remove_guarantee(storage, circle.circle_id, &member, &guarantee.guarantor);
+    }
+    let circle_id = circle.circle_id;
+    let stale: Vec<Addr> = GUARANTOR_NOMINATIONS
+        .prefix(circle_id)
+        .keys(storage, None, None, Order::Ascending)
+        .filter_map(|k| k.ok())
+        .collect();
+    for member in stale {
+        GUARANTOR_NOMINATIONS.remove(storage, (circle_id, member));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Renewal - start the next season of a finished circle
+// ---------------------------------------------------------------------------
+
+fn execute_renew_circle(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    overrides: CircleOverrides,
+) -> Result<Response, ContractError> {
+    let mut previous = CIRCLES.load(deps.storage, circle_id)?;
+    if info.sender != previous.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can renew the circle".to_string(),
+        });
+    }
+    if !matches!(
+        previous.circle_status,
+        CircleStatus::Finalizing | CircleStatus::Completed
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Finalizing or Completed".to_string(),
+            actual: format!("{:?}", previous.circle_status),
+        });
+    }
+    if let Some(successor) = previous.successor_id {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Circle already renewed as circle {}", successor),
+        });
+    }
+
+    let contribution_amount = overrides
+        .contribution_amount
+        .unwrap_or(previous.contribution_amount);
+    let max_members = overrides.max_members.unwrap_or(previous.max_members);
+    let (creator_lock, _) = quote_creator_lock(
+        deps.storage,
+        &previous.creator_collateral_policy,
+        contribution_amount,
+        max_members,
+    )?;
+    let attached = may_pay(&info, &previous.denomination).map_err(|_| {
+        ContractError::InvalidParameters {
+            msg: format!("Only {} is accepted", previous.denomination),
+        }
+    })?;
+    let rolled = roll_over_pending(
+        deps.storage,
+        &mut previous,
+        &info.sender,
+        creator_lock.saturating_sub(attached),
+        env.block.time,
+    )?;
+
+    // The rolled-over payout is already held by the contract: present it to
+    // CreateCircle as attached alongside the sender's own funds.
+    let create_info = MessageInfo {
+        sender: info.sender.clone(),
+        funds: vec![Coin {
+            denom: previous.denomination.clone(),
+            amount: attached + rolled,
+        }],
+    };
+    let created = execute_create_circle(
+        deps.branch(),
+        env.clone(),
+        create_info,
+        overrides
+            .circle_name
+            .unwrap_or_else(|| previous.circle_name.clone()),
+        overrides
+            .circle_description
+            .unwrap_or_else(|| previous.circle_description.clone()),
+        previous.circle_image.clone(),
+        max_members,
+        overrides
+            .min_members_required
+            .unwrap_or(previous.min_members_required),
+        previous.invite_only,
+        contribution_amount,
+        Some(previous.denomination.clone()),
+        previous.exit_penalty_percent,
+        previous.late_fee_percent,
+        overrides.total_cycles.unwrap_or(previous.total_cycles),
+        previous.cycle_duration_days,
+        Some(previous.cycle_duration_seconds).filter(|secs| *secs > 0),
+        overrides.start_date,
+        previous.grace_period_hours,
+        Some(previous.grace_period_seconds).filter(|secs| *secs > 0),
+        previous.auto_start_when_full,
+        previous.auto_start_type.clone(),
+        None,
+        previous.payout_order_type.clone(),
+        None,
+        previous.auto_payout_enabled,
+        previous.manual_trigger_enabled,
+        previous.emergency_stop_enabled,
+        previous.auto_refund_if_min_not_met,
+        previous.strict_mode,
+        previous.visibility.clone(),
+        previous.show_member_identities,
+        previous.distribution_threshold.clone(),
+        Some(previous.creator_collateral_policy.clone()),
+        Some(previous.late_fee_policy.clone()),
+        previous.join_requirements.clone(),
+        previous.requires_attestation,
+        previous.stake_locks,
+        previous.reserve.as_ref().map(|r| r.premium_bps),
+        previous.post_payout_collateral,
+        previous.kind.clone(),
+    )?;
+
+    let successor_id = CIRCLE_COUNTER.load(deps.storage)?;
+    let mut successor = CIRCLES.load(deps.storage, successor_id)?;
+    successor.predecessor_id = Some(circle_id);
+    // Everyone still in the finished circle is invited to the new season.
+    successor.pending_members = previous
+        .members_list
+        .iter()
+        .filter(|m| **m != info.sender)
+        .cloned()
+        .collect();
+    CIRCLES.save(deps.storage, successor_id, &successor)?;
+
+    previous.successor_id = Some(successor_id);
+    previous.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &previous)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "circle_renewed",
+        &format!(
+            "Circle {} renewed as circle {} ({} of the creator lock rolled over)",
+            circle_id, successor_id, rolled
+        ),
+    )?;
+
+    Ok(created
+        .add_attribute("predecessor_id", circle_id.to_string())
+        .add_attribute("rolled_over", rolled.to_string()))
+}
+
+fn execute_join_renewal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut previous = CIRCLES.load(deps.storage, circle_id)?;
+    let successor_id = previous
+        .successor_id
+        .ok_or_else(|| ContractError::InvalidParameters {
+            msg: "Circle has not been renewed".to_string(),
+        })?;
+    let successor = CIRCLES.load(deps.storage, successor_id)?;
+
+    let attached = may_pay(&info, &successor.denomination).map_err(|_| {
+        ContractError::InvalidParameters {
+            msg: format!("Only {} is accepted", successor.denomination),
+        }
+    })?;
+    let owed = successor.contribution_amount.saturating_sub(
+        GUARANTEES
+            .may_load(deps.storage, (successor_id, info.sender.clone()))?
+            .map(|g| g.posted)
+            .unwrap_or_default(),
+    );
+    let rolled = roll_over_pending(
+        deps.storage,
+        &mut previous,
+        &info.sender,
+        owed.saturating_sub(attached),
+        env.block.time,
+    )?;
+    previous.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &previous)?;
+
+    // As in RenewCircle, the rolled-over payout counts as attached funds.
+    let join_info = MessageInfo {
+        sender: info.sender.clone(),
+        funds: if (attached + rolled).is_zero() {
+            vec![]
+        } else {
+            vec![Coin {
+                denom: successor.denomination.clone(),
+                amount: attached + rolled,
+            }]
+        },
+    };
+    let joined = execute_join_circle(deps.branch(), env.clone(), join_info, successor_id, None)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "member_renewed",
+        &format!(
+            "Member {} joined circle {} ({} rolled over from pending payout)",
+            info.sender, successor_id, rolled
+        ),
+    )?;
+
+    Ok(joined
+        .add_attribute("predecessor_id", circle_id.to_string())
+        .add_attribute("rolled_over", rolled.to_string()))
+}
+
+/// Take up to `up_to` of `member`'s pending payout in `circle` for use as a lock
+/// in its successor. Like Withdraw, the part backed by locks still unbonding stays
+/// behind, and rolling over the last pending payout completes a Finalizing circle;
+/// the funds move to the successor, so there is no post-send balance to check.
+fn roll_over_pending(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+    up_to: Uint128,
+    now: Timestamp,
+) -> Result<Uint128, ContractError> {
+    let pending = PENDING_PAYOUTS
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let held = lock_backed_hold(storage, circle, member, now)?;
+    let rolled = pending.saturating_sub(held).min(up_to);
+    if rolled.is_zero() {
+        return Ok(rolled);
+    }
+    if rolled == pending {
+        PENDING_PAYOUTS.remove(storage, (circle.circle_id, member.clone()));
+    } else {
+        PENDING_PAYOUTS.save(storage, (circle.circle_id, member.clone()), &(pending - rolled))?;
+    }
+    circle.total_pending_payouts = circle.total_pending_payouts.saturating_sub(rolled);
+    if circle.circle_status == CircleStatus::Finalizing
+        && circle.total_pending_payouts.is_zero()
+        && circle.total_platform_fees_collected.is_zero()
+    {
+        circle.circle_status = CircleStatus::Completed;
+    }
+    Ok(rolled)
+}
+
+// ---------------------------------------------------------------------------
+// Circle templates
+// ---------------------------------------------------------------------------
+
+fn execute_save_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    template_id: Option<u64>,
+    template_name: String,
+    params: CircleTemplateParams,
+) -> Result<Response, ContractError> {
+    if template_name.trim().is_empty() {
+        return Err(ContractError::InvalidParameters {
+            msg: "template_name must not be empty".to_string(),
+        });
+    }
+    // Same checks as CreateCircle, minus the circle-specific dates and order list.
+    let (_, errors) = check_circle_terms(
+        deps.storage,
+        params.max_members,
+        params.min_members_required,
+        params.total_cycles,
+        params.contribution_amount,
+        params.denomination.as_deref(),
+        params.exit_penalty_percent,
+        params.late_fee_percent,
+        params.late_fee_policy.clone(),
+        params.cycle_duration_days,
+        params.cycle_duration_seconds,
+        params.grace_period_hours,
+        params.grace_period_seconds,
+        None,
+        &params.visibility,
+        params.distribution_threshold.clone(),
+        None,
+        params.creator_collateral_policy.clone(),
+        params.join_requirements.as_ref(),
+        params.requires_attestation,
+        params.stake_locks,
+        params.reserve_premium_bps,
+        &params.kind,
+    );
+    if let Some(err) = errors.into_iter().next() {
+        return Err(err);
+    }
+
+    let template = match template_id {
+        Some(id) => {
+            let existing = TEMPLATES.load(deps.storage, id)?;
+            if info.sender != existing.owner {
+                return Err(ContractError::Unauthorized {
+                    msg: "Only the template owner can update it".to_string(),
+                });
+            }
+            CircleTemplate {
+                template_name,
+                params,
+                updated_at: env.block.time,
+                ..existing
+            }
+        }
+        None => {
+            let id = TEMPLATE_COUNTER.may_load(deps.storage)?.unwrap_or(0) + 1;
+            TEMPLATE_COUNTER.save(deps.storage, &id)?;
+            CircleTemplate {
+                template_id: id,
+                template_name,
+                owner: info.sender.clone(),
+                params,
+                created_at: env.block.time,
+                updated_at: env.block.time,
+            }
+        }
+    };
+    TEMPLATES.save(deps.storage, template.template_id, &template)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "save_template")
+        .add_attribute("template_id", template.template_id.to_string())
+        .add_attribute("owner", template.owner.to_string()))
+}
+
+fn execute_delete_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    template_id: u64,
+) -> Result<Response, ContractError> {
+    let template = TEMPLATES.load(deps.storage, template_id)?;
+    let platform = PLATFORM_CONFIG.load(deps.storage)?.platform_address;
+    if info.sender != template.owner && info.sender != platform {
+        return Err(ContractError::Unauthorized {
+            msg: "Only the template owner or the platform can delete it".to_string(),
+        });
+    }
+    TEMPLATES.remove(deps.storage, template_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "delete_template")
+        .add_attribute("template_id", template_id.to_string()))
+}
+
+fn execute_create_circle_from_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    template_id: u64,
+    overrides: CircleOverrides,
+) -> Result<Response, ContractError> {
+    let CircleTemplateParams {
+        circle_name,
+        circle_description,
+        circle_image,
+        max_members,
+        min_members_required,
+        invite_only,
+        contribution_amount,
+        denomination,
+        exit_penalty_percent,
+        late_fee_percent,
+        total_cycles,
+        cycle_duration_days,
+        cycle_duration_seconds,
+        grace_period_hours,
+        grace_period_seconds,
+        auto_start_when_full,
+        auto_start_type,
+        payout_order_type,
+        auto_payout_enabled,
+        manual_trigger_enabled,
+        emergency_stop_enabled,
+        auto_refund_if_min_not_met,
+        strict_mode,
+        visibility,
+        show_member_identities,
+        distribution_threshold,
+        creator_collateral_policy,
+        late_fee_policy,
+        join_requirements,
+        requires_attestation,
+        stake_locks,
+        reserve_premium_bps,
+        post_payout_collateral,
+        kind,
+    } = TEMPLATES.load(deps.storage, template_id)?.params;
+
+    let res = execute_create_circle(
+        deps,
+        env,
+        info,
+        overrides.circle_name.unwrap_or(circle_name),
+        overrides.circle_description.unwrap_or(circle_description),
+        circle_image,
+        overrides.max_members.unwrap_or(max_members),
+        overrides
+            .min_members_required
+            .unwrap_or(min_members_required),
+        invite_only,
+        overrides.contribution_amount.unwrap_or(contribution_amount),
+        denomination,
+        exit_penalty_percent,
+        late_fee_percent,
+        overrides.total_cycles.unwrap_or(total_cycles),
+        cycle_duration_days,
+        cycle_duration_seconds,
+        overrides.start_date,
+        grace_period_hours,
+        grace_period_seconds,
+        auto_start_when_full,
+        auto_start_type,
+        None,
+        payout_order_type,
+        None,
+        auto_payout_enabled,
+        manual_trigger_enabled,
+        emergency_stop_enabled,
+        auto_refund_if_min_not_met,
+        strict_mode,
+        visibility,
+        show_member_identities,
+        distribution_threshold,
+        creator_collateral_policy,
+        late_fee_policy,
+        join_requirements,
+        requires_attestation,
+        stake_locks,
+        reserve_premium_bps,
+        post_payout_collateral,
+        kind,
+    )?;
+
+    Ok(res.add_attribute("template_id", template_id.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Savings and loans (accumulating circles)
+// ---------------------------------------------------------------------------
+
+/// Interest rate, loan cap and approval rule of an accumulating circle.
+fn loan_terms(circle: &Circle) -> Result<(u64, u64, LoanApproval), ContractError> {
+    match circle.kind {
+        CircleKind::Accumulating {
+            interest_bps_per_round,
+            max_loan_bps,
+            ref loan_approval,
+        } => Ok((interest_bps_per_round, max_loan_bps, loan_approval.clone())),
+        CircleKind::Rotating => Err(ContractError::InvalidParameters {
+            msg: format!("Circle {} is not an accumulating circle", circle.circle_id),
+        }),
+    }
+}
+
+fn ensure_running(circle: &Circle) -> Result<(), ContractError> {
+    if circle.circle_status != CircleStatus::Running {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+    Ok(())
+}
+
+/// Simple interest on the outstanding principal for every round closed since the
+/// last accrual.
+fn accrue_loan_interest(loan: &mut Loan, interest_bps_per_round: u64, round: u32) {
+    let rounds = round.saturating_sub(loan.interest_accrued_round) as u64;
+    loan.interest_due += loan
+        .principal
+        .multiply_ratio(interest_bps_per_round * rounds, 10000u64);
+    loan.interest_accrued_round = loan.interest_accrued_round.max(round);
+}
+
+/// Sum of this round's DEPOSITS (real or lock-covered) of the active members.
+fn round_deposits_total(
+    storage: &dyn Storage,
+    circle: &Circle,
+    active_members: &[Addr],
+) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for member in active_members {
+        if let Some(record) =
+            DEPOSITS.may_load(storage, (circle.circle_id, member.clone(), circle.current_cycle_index))?
+        {
+            total += record.amount;
+        }
+    }
+    Ok(total)
+}
+
+/// Add the round's net contributions to the pool and each depositor's savings.
+fn bank_round_savings(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    active_members: &[Addr],
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let Some(mut pool) = circle.savings.take() else {
+        return Ok(());
+    };
+    for member in active_members {
+        if let Some(record) =
+            DEPOSITS.may_load(storage, (circle.circle_id, member.clone(), circle.current_cycle_index))?
+        {
+            MEMBER_SAVINGS.update(storage, (circle.circle_id, member.clone()), |saved| {
+                Ok::<_, ContractError>(saved.unwrap_or_default() + record.amount)
+            })?;
+            pool.total_savings += record.amount;
+        }
+    }
+    pool.balance += amount;
+    circle.savings = Some(pool);
+    Ok(())
+}
+
+/// End-of-season share-out: the pool (cash plus what borrowers still owe) is split in
+/// proportion to MEMBER_SAVINGS and credited to PENDING_PAYOUTS. A borrower's debt comes
+/// out of their share, then their lock; the rest is written off and, if the cash then
+/// falls short, every share is cut pro rata.
+fn share_out_savings(storage: &mut dyn Storage, circle: &mut Circle) -> Result<(), ContractError> {
+    let Some(mut pool) = circle.savings.take() else {
+        return Ok(());
+    };
+    let circle_id = circle.circle_id;
+    let (interest_bps_per_round, _, _) = loan_terms(circle)?;
+
+    let loans = LOANS
+        .prefix(circle_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut debts: Vec<(Addr, Uint128)> = vec![];
+    for (borrower, mut loan) in loans {
+        LOANS.remove(storage, (circle_id, borrower.clone()));
+        if loan.disbursed_at.is_none() {
+            continue;
+        }
+        accrue_loan_interest(&mut loan, interest_bps_per_round, circle.current_cycle_index);
+        pool.interest_earned += loan.interest_due;
+        debts.push((borrower, loan.principal + loan.interest_due));
+    }
+    pool.outstanding_principal = Uint128::zero();
+
+    let savers = MEMBER_SAVINGS
+        .prefix(circle_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let value = debts.iter().fold(pool.balance, |acc, (_, debt)| acc + *debt);
+    let share_of = |member: &Addr| {
+        savers
+            .iter()
+            .find(|(m, _)| m == member)
+            .map(|(_, saved)| value.multiply_ratio(*saved, pool.total_savings))
+            .unwrap_or_default()
+    };
+
+    let mut cash = pool.balance;
+    let mut deducted: Vec<(Addr, Uint128)> = vec![];
+    for (borrower, debt) in &debts {
+        let from_share = (*debt).min(share_of(borrower));
+        let rest = *debt - from_share;
+        let from_lock = rest.min(current_member_lock(storage, circle_id, borrower));
+        debit_member_locked(storage, circle_id, borrower, from_lock, &mut circle.total_amount_locked)?;
+        cash += from_lock;
+        pool.written_off += rest - from_lock;
+        deducted.push((borrower.clone(), from_share));
+    }
+
+    let credits: Vec<(Addr, Uint128)> = savers
+        .iter()
+        .map(|(member, _)| {
+            let owed = deducted
+                .iter()
+                .find(|(b, _)| b == member)
+                .map(|(_, d)| *d)
+                .unwrap_or_default();
+            (member.clone(), share_of(member) - owed)
+        })
+        .collect();
+    let total_credit = credits.iter().fold(Uint128::zero(), |acc, (_, c)| acc + *c);
+    for (member, credit) in credits {
+        let credit = if total_credit > cash {
+            credit.multiply_ratio(cash, total_credit)
+        } else {
+            credit
+        };
+        credit_pending_payout(storage, circle_id, &member, credit, &mut circle.total_pending_payouts)?;
+    }
+
+    pool.balance = Uint128::zero();
+    circle.savings = Some(pool);
+    Ok(())
+}
+
+fn execute_request_loan(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let (_, max_loan_bps, _) = loan_terms(&circle)?;
+    ensure_running(&circle)?;
+    if !circle.members_list.contains(&info.sender)
+        || BLOCKED_MEMBERS.has(deps.storage, (circle_id, info.sender.clone()))
+    {
+        return Err(ContractError::Unauthorized {
+            msg: "Only active members can borrow".to_string(),
+        });
+    }
+    if LOANS.has(deps.storage, (circle_id, info.sender.clone())) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Member already has an open loan".to_string(),
+        });
+    }
+    let saved = MEMBER_SAVINGS
+        .may_load(deps.storage, (circle_id, info.sender.clone()))?
+        .unwrap_or_default();
+    let max_loan = saved.multiply_ratio(max_loan_bps, 10000u64);
+    if amount.is_zero() || amount > max_loan {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Loan must be between 1 and {} (savings {})", max_loan, saved),
+        });
+    }
+
+    LOANS.save(
+        deps.storage,
+        (circle_id, info.sender.clone()),
+        &Loan {
+            borrower: info.sender.clone(),
+            principal: amount,
+            interest_due: Uint128::zero(),
+            interest_accrued_round: circle.current_cycle_index,
+            requested_at: env.block.time,
+            disbursed_at: None,
+            approvals: vec![],
+        },
+    )?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "loan_requested",
+        &format!("Member {} requested a loan of {}", info.sender, amount),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_loan")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_approve_loan(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    borrower: Addr,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    let (_, _, loan_approval) = loan_terms(&circle)?;
+    ensure_running(&circle)?;
+    let mut loan = LOANS.load(deps.storage, (circle_id, borrower.clone()))?;
+    if loan.disbursed_at.is_some() {
+        return Err(ContractError::InvalidParameters {
+            msg: "Loan already disbursed".to_string(),
+        });
+    }
+    if info.sender == borrower {
+        return Err(ContractError::Unauthorized {
+            msg: "Borrowers cannot approve their own loan".to_string(),
+        });
+    }
+
+    match loan_approval {
+        LoanApproval::Creator => {
+            if info.sender != circle.creator_address {
+                return Err(ContractError::Unauthorized {
+                    msg: "Only creator can approve loans".to_string(),
+                });
+            }
+        }
+        LoanApproval::MemberVote => {
+            if !circle.members_list.contains(&info.sender) {
+                return Err(ContractError::Unauthorized {
+                    msg: "Only members can vote on loans".to_string(),
+                });
+            }
+            if loan.approvals.contains(&info.sender) {
+                return Err(ContractError::InvalidParameters {
+                    msg: "Already voted for this loan".to_string(),
+                });
+            }
+            loan.approvals.push(info.sender.clone());
+            let eligible = circle.members_list.iter().filter(|m| **m != borrower).count();
+            let required = eligible / 2 + 1;
+            if loan.approvals.len() < required {
+                LOANS.save(deps.storage, (circle_id, borrower.clone()), &loan)?;
+                return Ok(Response::new()
+                    .add_attribute("action", "approve_loan")
+                    .add_attribute("circle_id", circle_id.to_string())
+                    .add_attribute("borrower", borrower.to_string())
+                    .add_attribute("approvals", loan.approvals.len().to_string())
+                    .add_attribute("required", required.to_string()));
+            }
+        }
+    }
+
+    // Approved: disburse from the pool's cash.
+    let pool = circle.savings.get_or_insert_with(SavingsPool::default);
+    if pool.balance < loan.principal {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Savings pool holds only {}", pool.balance),
+        });
+    }
+    pool.balance -= loan.principal;
+    pool.outstanding_principal += loan.principal;
+    loan.disbursed_at = Some(env.block.time);
+    loan.interest_accrued_round = circle.current_cycle_index;
+    LOANS.save(deps.storage, (circle_id, borrower.clone()), &loan)?;
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "loan_disbursed",
+        &format!("Loan of {} disbursed to {}", loan.principal, borrower),
+    )?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: borrower.to_string(),
+            amount: vec![Coin {
+                denom: circle.denomination.clone(),
+                amount: loan.principal,
+            }],
+        })
+        .add_attribute("action", "approve_loan")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("borrower", borrower.to_string())
+        .add_attribute("amount", loan.principal.to_string()))
+}
+
+fn execute_repay_loan(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    let (interest_bps_per_round, _, _) = loan_terms(&circle)?;
+    if !matches!(circle.circle_status, CircleStatus::Running | CircleStatus::Paused) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Running or Paused".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+    let mut loan = LOANS
+        .may_load(deps.storage, (circle_id, info.sender.clone()))?
+        .filter(|l| l.disbursed_at.is_some())
+        .ok_or_else(|| ContractError::InvalidParameters {
+            msg: "No disbursed loan to repay".to_string(),
+        })?;
+    accrue_loan_interest(&mut loan, interest_bps_per_round, circle.current_cycle_index);
+    let owed = loan.principal + loan.interest_due;
+    let payment = must_pay(&info, &circle.denomination).map_err(|_| ContractError::InsufficientFunds {
+        required: owed.to_string(),
+        sent: "0".to_string(),
+    })?;
+    if payment > owed {
+        return Err(ContractError::InvalidParameters {
+            msg: format!("Loan balance is {}", owed),
+        });
+    }
+
+    // Interest is paid off first.
+    let to_interest = payment.min(loan.interest_due);
+    let to_principal = payment - to_interest;
+    loan.interest_due -= to_interest;
+    loan.principal -= to_principal;
+    let pool = circle.savings.get_or_insert_with(SavingsPool::default);
+    pool.balance += payment;
+    pool.interest_earned += to_interest;
+    pool.outstanding_principal = pool.outstanding_principal.saturating_sub(to_principal);
+    if (loan.principal + loan.interest_due).is_zero() {
+        LOANS.remove(deps.storage, (circle_id, info.sender.clone()));
+    } else {
+        LOANS.save(deps.storage, (circle_id, info.sender.clone()), &loan)?;
+    }
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "loan_repaid",
+        &format!(
+            "Member {} repaid {} ({} interest); {} still owed",
+            info.sender,
+            payment,
+            to_interest,
+            owed - payment
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "repay_loan")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("amount", payment.to_string())
+        .add_attribute("remaining", (owed - payment).to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Private Circle Management
+// ---------------------------------------------------------------------------
+
+fn execute_add_private_member(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member_address: Addr,
+    pseudonym: Option<String>,
+    acceptance_window_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can add private members".to_string(),
+        });
+    }
+
+    if !matches!(circle.visibility, Visibility::Private) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Circle must be private to use AddPrivateMember".to_string(),
+        });
+    }
+
+    // Full may only be reservations, some of which may have expired.
+    if !matches!(
+        circle.circle_status,
+        CircleStatus::Draft | CircleStatus::Open | CircleStatus::Full
+    ) {
+        return Err(ContractError::InvalidCircleStatus {
+            expected: "Draft, Open or Full".to_string(),
+            actual: format!("{:?}", circle.circle_status),
+        });
+    }
+
+    let validated = deps.api.addr_validate(member_address.as_str())?;
+
+    if circle.members_list.contains(&validated) {
+        return Err(ContractError::AlreadyMember {
+            address: validated.to_string(),
+        });
+    }
+
+    let reserved = live_reservations(deps.storage, &mut circle, env.block.time)?;
+    if reserved.contains(&validated) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Member already has a pending reservation".to_string(),
+        });
+    }
+
+    // Unexpired reservations hold a seat, so they count against capacity.
+    if (circle.members_list.len() + reserved.len()) as u32 >= circle.max_members {
+        return Err(ContractError::CircleFull {
+            max: circle.max_members,
+        });
+    }
+
+    let window = acceptance_window_seconds
+        .filter(|&s| s > 0)
+        .unwrap_or(DEFAULT_RESERVATION_WINDOW_SECS);
+    let expires_at = env.block.time.plus_seconds(window);
+
+    // Reservation (re)placed — an expired one for the same address is overwritten.
+    PRIVATE_RESERVATIONS.save(
+        deps.storage,
+        (circle_id, validated.clone()),
+        &PrivateReservation {
+            member: validated.clone(),
+            pseudonym: pseudonym.clone(),
+            reserved_at: env.block.time,
+            expires_at,
+        },
+    )?;
+
+    // pending_members is what the JoinCircle / AcceptInvite gate checks.
+    if !circle.pending_members.contains(&validated) {
+        circle.pending_members.push(validated.clone());
+    }
+
+    let mut private_members = PRIVATE_MEMBER_LIST
+        .may_load(deps.storage, circle_id)?
+        .unwrap_or_default();
+    if !private_members.contains(&validated) {
+        private_members.push(validated.clone());
+        PRIVATE_MEMBER_LIST.save(deps.storage, circle_id, &private_members)?;
+    }
+
+    if let Some(pseudo) = pseudonym {
+        MEMBER_PSEUDONYMS.save(deps.storage, (circle_id, validated.clone()), &pseudo)?;
+    }
+
+    refresh_seat_status(&mut circle, reserved.len() + 1);
+    circle.updated_at = env.block.time;
+    CIRCLES.save(deps.storage, circle_id, &circle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "private_member_reserved",
+        &format!(
+            "Seat reserved for {} by {} (accept by {})",
+            validated,
+            info.sender,
+            expires_at.seconds()
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_private_member")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", validated)
+        .add_attribute("expires_at", expires_at.seconds().to_string()))
+}
+
+fn execute_update_member_pseudonym(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member_address: Addr,
+    pseudonym: String,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can update pseudonyms".to_string(),
+        });
+    }
+
+    let validated = deps.api.addr_validate(member_address.as_str())?;
+    let is_member = circle.members_list.contains(&validated);
+    let is_pending = circle.pending_members.contains(&validated);
+
+    if !is_member && !is_pending {
+        return Err(ContractError::InvalidParameters {
+            msg: "Address not found in circle members or pending invitations".to_string(),
+        });
+    }
+
+    MEMBER_PSEUDONYMS.save(deps.storage, (circle_id, validated.clone()), &pseudonym)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "member_pseudonym_updated",
+        &format!("Pseudonym '{}' set for {}", pseudonym, validated),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_member_pseudonym")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", validated)
+        .add_attribute("pseudonym", pseudonym))
+}
+
+fn execute_block_member(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    member_address: Addr,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can block members".to_string(),
+        });
+    }
+
+    let validated = deps.api.addr_validate(member_address.as_str())?;
+
+    if !circle.members_list.contains(&validated) {
+        return Err(ContractError::InvalidParameters {
+            msg: "Member not found in circle".to_string(),
+        });
+    }
+
+    let blocked_from_cycle = circle.current_cycle_index + 1;
+    BLOCKED_MEMBERS.save(deps.storage, (circle_id, validated.clone()), &blocked_from_cycle)?;
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "member_blocked",
+        &format!("Member {} blocked from cycle {}", validated, blocked_from_cycle),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "block_member")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("member", validated)
+        .add_attribute("blocked_from_cycle", blocked_from_cycle.to_string()))
+}
+
+fn execute_distribute_blocked_funds(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    circle_id: u64,
+    cycle: u32,
+) -> Result<Response, ContractError> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+
+    if info.sender != circle.creator_address {
+        return Err(ContractError::Unauthorized {
+            msg: "Only creator can distribute blocked funds".to_string(),
+        });
+    }
+
+    let mut total_blocked_funds = Uint128::zero();
+    let mut blocked_in_cycle: Vec<(Addr, Uint128)> = Vec::new();
+
+    for member in &circle.members_list {
+        if let Some(blocked_cycle) =
+            BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, member.clone()))?
+        {
+            if blocked_cycle <= cycle {
+                if let Some(locked) =
+                    MEMBER_LOCKED_AMOUNTS.may_load(deps.storage, (circle_id, member.clone()))?
+                {
+                    total_blocked_funds += locked;
+                    blocked_in_cycle.push((member.clone(), locked));
+                }
+            }
+        }
+    }
+
+    if total_blocked_funds.is_zero() {
+        return Err(ContractError::InvalidParameters {
+            msg: "No blocked funds to distribute".to_string(),
+        });
+    }
+
+    let active_members: Vec<Addr> = circle
+        .members_list
+        .iter()
+        .filter(|m| {
+            BLOCKED_MEMBERS
+                .may_load(deps.storage, (circle_id, (*m).clone()))
+                .unwrap_or(None)
+                .map(|bc| bc > cycle)
+                .unwrap_or(true)
+        })
+        .filter(|m| {
+            DEPOSITS
+                .may_load(deps.storage, (circle_id, (*m).clone(), cycle))
+                .map(|d| d.is_some())
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect();
+
+    if active_members.is_empty() {
+        return Err(ContractError::InvalidParameters {
+            msg: "No active members to distribute to".to_string(),
+        });
+    }
+
+    let amount_per_member =
+        total_blocked_funds.multiply_ratio(1u128, active_members.len() as u128);
+    let remainder = total_blocked_funds
+        .checked_sub(amount_per_member * Uint128::from(active_members.len() as u128))
+        .unwrap_or(Uint128::zero());
+
+    let mut messages = Vec::new();
+    for (idx, member) in active_members.iter().enumerate() {
+        let mut amount = amount_per_member;
+        if idx == 0 {
+            amount = amount.checked_add(remainder).unwrap_or(amount);
+        }
+        messages.push(BankMsg::Send {
+            to_address: member.to_string(),
+            amount: vec![Coin {
+                denom: circle.denomination.clone(),
+                amount,
+            }],
+        });
+    }
+
+    log_event(
+        &mut deps,
+        &env,
+        circle_id,
+        "blocked_funds_distributed",
+        &format!(
+            "Blocked funds {} distributed to {} active members",
+            total_blocked_funds,
+            active_members.len()
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "distribute_blocked_funds")
+        .add_attribute("circle_id", circle_id.to_string())
+        .add_attribute("cycle", cycle.to_string())
+        .add_attribute("total_distributed", total_blocked_funds.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Refunds (liquid balance only)
+// ---------------------------------------------------------------------------
+
+fn safe_refund_or_queue(
+    deps: DepsMut,
+    env: &Env,
+    _circle_id: u64,
+    member: &Addr,
+    amount: Uint128,
+    denom: &str,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, denom)
+        .map_err(|e| ContractError::InvalidParameters {
+            msg: format!("Balance query failed: {}", e),
+        })?;
+
+    if balance.amount >= amount {
+        return Ok(vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: member.to_string(),
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount,
+            }],
+        })]);
+    }
+
+    Err(ContractError::InvalidParameters {
+        msg: "Insufficient liquid balance for refund".to_string(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Internal Helpers
+// ---------------------------------------------------------------------------
+
+fn generate_payout_order(circle: &mut Circle, env: &Env) {
+    if circle.payout_order_list.is_none() {
+        let members = circle.members_list.clone();
+        circle.payout_order_list = Some(match circle.payout_order_type {
+            PayoutOrderType::RandomOrder => {
+                let mut shuffled = members;
+                let seed = env.block.time.seconds() + circle.circle_id;
+                for i in 0..shuffled.len() {
+                    let j = (seed as usize + i * 7) % shuffled.len();
+                    shuffled.swap(i, j);
+                }
+                shuffled
+            }
+            PayoutOrderType::PredefinedOrder => members,
+        });
+    }
+}
+
+fn build_distribution_calendar(
+    circle: &Circle,
+    start_timestamp: Timestamp,
+    pauses: &[PauseRecord],
+) -> String {
+    // Mirror `distribution_min_round_for_active` (and the matching helper in
+    // query.rs) so the emitted calendar matches the execute path. Diverging
+    // from execute previously broke the cron classifier — and showed phantom
+    // distribution rounds when `max_members` exceeded the actual roster
+    // (round_size was max_members, so round_in_cycle could legitimately reach
+    // it on cycle 2+ for a not-yet-full circle).
+    //
+    // Both the modulo (round_size) and the distribution gate must use the
+    // payout-order length — locked at start to the active roster size.
+    let round_size = circle.payout_order_list
+        .as_ref()
+        .map(|l| l.len() as u32)
+        .unwrap_or(circle.max_members)
+        .max(1);
+    let min_round_for_distribution: u32 = match circle.distribution_threshold {
+        None | Some(DistributionThreshold::Total {}) => round_size,
+        Some(DistributionThreshold::MinMembers { count }) => count,
+    };
+
+    let mut calendar_data = String::new();
+    if let Some(payout_order) = &circle.payout_order_list {
+        let mut round_number = 1u32;
+        for cycle in 1..=circle.total_cycles {
+            for recipient in payout_order.iter() {
+                let round_in_cycle = ((round_number - 1) % round_size) + 1;
+                let distribution_occurs = round_in_cycle >= min_round_for_distribution;
+                let round_offset_seconds = (round_number - 1) as u64 * circle.cycle_duration_secs()
+                    + pause_shift_for_round(pauses, round_number);
+                let deposit_deadline = Timestamp::from_seconds(
+                    start_timestamp.seconds() + round_offset_seconds,
+                );
+                let distribution_date = Timestamp::from_seconds(
+                    start_timestamp.seconds() + round_offset_seconds + circle.cycle_duration_secs(),
+                );
+                if !calendar_data.is_empty() {
+                    calendar_data.push(',');
+                }
+                calendar_data.push_str(&format!(
+                    "{{round:{},cycle:{},deposit_deadline:{},distribution_date:{},distribution_occurs:{},recipient:\"{}\"}}",
+                    round_number,
+                    cycle,
+                    deposit_deadline.seconds(),
+                    distribution_date.seconds(),
+                    distribution_occurs,
+                    recipient
+                ));
+                round_number += 1;
+            }
+        }
+    }
+    calendar_data
+}
+
+fn use_locked_amount_for_member(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    amount_needed: Uint128,
+) -> Result<Uint128, ContractError> {
+    if let Some(locked) = MEMBER_LOCKED_AMOUNTS.may_load(storage, (circle_id, member.clone()))? {
+        let used = if locked > amount_needed {
+            amount_needed
+        } else {
+            locked
+        };
+        let remaining = locked
+            .checked_sub(used)
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Locked amount underflow".to_string(),
+            })?;
+        if remaining.is_zero() {
+            MEMBER_LOCKED_AMOUNTS.remove(storage, (circle_id, member.clone()));
+        } else {
+            MEMBER_LOCKED_AMOUNTS.save(storage, (circle_id, member.clone()), &remaining)?;
+        }
+        return Ok(used);
+    }
+    Ok(Uint128::zero())
+}
+
+/// Whether `member` was paid in any round of this circle up to `upto_cycle`.
+fn has_received_payout(storage: &dyn Storage, circle_id: u64, member: &Addr, upto_cycle: u32) -> bool {
+    (1..=upto_cycle).any(|cycle| PAYOUTS.has(storage, (circle_id, cycle, member.clone())))
+}
+
+/// Move penalties collected so far into the circle's reserve, if it has one.
+fn sweep_penalties_into_reserve(circle: &mut Circle) {
+    if let Some(ref mut reserve) = circle.reserve {
+        reserve.balance += circle.total_penalties_collected;
+        reserve.penalties_received += circle.total_penalties_collected;
+        circle.total_penalties_collected = Uint128::zero();
+    }
+}
+
+/// Take up to `gap` from the reserve to cover a shortfall; returns what was covered.
+fn draw_from_reserve(circle: &mut Circle, gap: Uint128) -> Uint128 {
+    sweep_penalties_into_reserve(circle);
+    match circle.reserve {
+        Some(ref mut reserve) => {
+            let covered = gap.min(reserve.balance);
+            reserve.balance -= covered;
+            reserve.shortfalls_covered += covered;
+            covered
+        }
+        None => Uint128::zero(),
+    }
+}
+
+/// Rounds still to be played after the current one (see `total_rounds` in process_payout).
+fn rounds_after_current(circle: &Circle) -> u32 {
+    let members_basis = circle.members_at_start.unwrap_or(circle.max_members);
+    (members_basis * circle.total_cycles).saturating_sub(circle.current_cycle_index)
+}
+
+fn payout_collateral_of(storage: &dyn Storage, circle_id: u64, member: &Addr) -> Uint128 {
+    PAYOUT_COLLATERAL
+        .may_load(storage, (circle_id, member.clone()))
+        .unwrap_or(None)
+        .unwrap_or(Uint128::zero())
+}
+
+fn save_payout_collateral(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        PAYOUT_COLLATERAL.remove(storage, (circle_id, member.clone()));
+        Ok(())
+    } else {
+        PAYOUT_COLLATERAL.save(storage, (circle_id, member.clone()), &amount)
+    }
+}
+
+/// Collateral a paid member must hold on top of their lock to cover every
+/// contribution still owed after the current round. The creator lock is not
+/// drawn per round, so it does not count.
+fn payout_collateral_required(storage: &dyn Storage, circle: &Circle, member: &Addr) -> Uint128 {
+    let still_owed = circle
+        .contribution_amount
+        .checked_mul(Uint128::from(rounds_after_current(circle)))
+        .unwrap_or(Uint128::MAX);
+    still_owed.saturating_sub(current_member_lock(storage, circle.circle_id, member))
+}
+
+/// Top up `member`'s collateral out of a payout of `amount` they are about to be
+/// credited; returns the part withheld (zero unless `post_payout_collateral`).
+fn withhold_payout_collateral(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    if !circle.post_payout_collateral {
+        return Ok(Uint128::zero());
+    }
+    let held = payout_collateral_of(storage, circle.circle_id, member);
+    let top_up = payout_collateral_required(storage, circle, member)
+        .saturating_sub(held)
+        .min(amount);
+    if !top_up.is_zero() {
+        save_payout_collateral(storage, circle.circle_id, member, held + top_up)?;
+        circle.total_payout_collateral += top_up;
+    }
+    Ok(top_up)
+}
+
+/// Credit back whatever collateral exceeds what `member` still owes (called
+/// once their deposit for the current round is recorded). Returns the release.
+fn release_payout_collateral(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+) -> Result<Uint128, ContractError> {
+    let held = payout_collateral_of(storage, circle.circle_id, member);
+    let excess = held.saturating_sub(payout_collateral_required(storage, circle, member));
+    if excess.is_zero() {
+        return Ok(excess);
+    }
+    save_payout_collateral(storage, circle.circle_id, member, held - excess)?;
+    circle.total_payout_collateral = circle.total_payout_collateral.saturating_sub(excess);
+    credit_pending_payout(
+        storage,
+        circle.circle_id,
+        member,
+        excess,
+        &mut circle.total_pending_payouts,
+    )?;
+    Ok(excess)
+}
+
+/// Take up to `needed` from `member`'s collateral to cover a round they missed.
+fn use_payout_collateral(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+    needed: Uint128,
+) -> StdResult<Uint128> {
+    let held = payout_collateral_of(storage, circle.circle_id, member);
+    let used = held.min(needed);
+    if !used.is_zero() {
+        save_payout_collateral(storage, circle.circle_id, member, held - used)?;
+        circle.total_payout_collateral = circle.total_payout_collateral.saturating_sub(used);
+    }
+    Ok(used)
+}
+
+/// Remove all of `member`'s collateral from the circle's books and return it.
+fn take_payout_collateral(storage: &mut dyn Storage, circle: &mut Circle, member: &Addr) -> Uint128 {
+    let held = payout_collateral_of(storage, circle.circle_id, member);
+    PAYOUT_COLLATERAL.remove(storage, (circle.circle_id, member.clone()));
+    circle.total_payout_collateral = circle.total_payout_collateral.saturating_sub(held);
+    held
+}
+
+/// Return every member's remaining collateral via PENDING_PAYOUTS (circle
+/// finalized or cancelled: nothing is left for it to cover).
+fn refund_payout_collateral(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+) -> Result<(), ContractError> {
+    let holders: Vec<Addr> = PAYOUT_COLLATERAL
+        .prefix(circle.circle_id)
+        .keys(storage, None, None, Order::Ascending)
+        .filter_map(|k| k.ok())
+        .collect();
+    for member in holders {
+        let amount = take_payout_collateral(storage, circle, &member);
+        credit_pending_payout(
+            storage,
+            circle.circle_id,
+            &member,
+            amount,
+            &mut circle.total_pending_payouts,
+        )?;
+    }
+    Ok(())
+}
+
+/// Credit pending payout for a member and update circle aggregate.
+fn credit_pending_payout(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    amount: Uint128,
+    total_pending: &mut Uint128,
+) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let existing = PENDING_PAYOUTS
+        .may_load(storage, (circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let new_pending = existing
+        .checked_add(amount)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Pending payout overflow".to_string(),
+        })?;
+    PENDING_PAYOUTS.save(storage, (circle_id, member.clone()), &new_pending)?;
+    *total_pending = total_pending
+        .checked_add(amount)
+        .unwrap_or(*total_pending);
+    Ok(())
+}
+
+/// Debit pending payout for a member and update circle aggregate. Returns the amount debited.
+fn debit_pending_payout(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    total_pending: &mut Uint128,
+) -> Result<Uint128, ContractError> {
+    let pending = PENDING_PAYOUTS
+        .may_load(storage, (circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    if pending.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    PENDING_PAYOUTS.remove(storage, (circle_id, member.clone()));
+    *total_pending = total_pending
+        .checked_sub(pending)
+        .unwrap_or(Uint128::zero());
+    Ok(pending)
+}
+
+/// Mark up to `remaining` of a fresh `credited` payout as backed by unbonding locks.
+fn hold_lock_backed(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    credited: Uint128,
+    remaining: &mut Uint128,
+) -> StdResult<()> {
+    let held = credited.min(*remaining);
+    if held.is_zero() {
+        return Ok(());
+    }
+    *remaining -= held;
+    LOCK_BACKED_PENDING.update(storage, (circle_id, member.clone()), |existing| {
+        Ok::<_, StdError>(existing.unwrap_or_default() + held)
+    })?;
+    Ok(())
+}
+
+/// Lock-backed part of `member`'s pending payout while the circle's stake is
+/// still unbonding; zero once it has matured.
+fn lock_backed_hold(
+    storage: &dyn Storage,
+    circle: &Circle,
+    member: &Addr,
+    now: Timestamp,
+) -> StdResult<Uint128> {
+    let unbonding = circle
+        .lock_stake
+        .as_ref()
+        .and_then(|stake| stake.unbonding_matures_at)
+        .map(|at| now < at)
+        .unwrap_or(false);
+    if !unbonding {
+        return Ok(Uint128::zero());
+    }
+    Ok(LOCK_BACKED_PENDING
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or_default())
+}
+
+/// Add locked amount for a member and update circle aggregate.
+fn add_member_locked(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    amount: Uint128,
+    total_locked: &mut Uint128,
+) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let existing = MEMBER_LOCKED_AMOUNTS
+        .may_load(storage, (circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let new_locked = existing
+        .checked_add(amount)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Locked amount overflow".to_string(),
+        })?;
+    MEMBER_LOCKED_AMOUNTS.save(storage, (circle_id, member.clone()), &new_locked)?;
+    *total_locked = total_locked
+        .checked_add(amount)
+        .unwrap_or(*total_locked);
+    Ok(())
+}
+
+/// Debit locked amount for a member and update circle aggregate.
+fn debit_member_locked(
+    storage: &mut dyn Storage,
+    circle_id: u64,
+    member: &Addr,
+    amount: Uint128,
+    total_locked: &mut Uint128,
+) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let existing = MEMBER_LOCKED_AMOUNTS
+        .may_load(storage, (circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let remaining = existing
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Locked amount underflow".to_string(),
+        })?;
+    if remaining.is_zero() {
+        MEMBER_LOCKED_AMOUNTS.remove(storage, (circle_id, member.clone()));
+    } else {
+        MEMBER_LOCKED_AMOUNTS.save(storage, (circle_id, member.clone()), &remaining)?;
+    }
+    *total_locked = total_locked
+        .checked_sub(amount)
+        .unwrap_or(Uint128::zero());
+    Ok(())
+}
+
+/// Whole rounds a member's prepaid balance still covers.
+fn prepaid_rounds(storage: &dyn Storage, circle: &Circle, member: &Addr) -> StdResult<u32> {
+    let balance = PREPAID_BALANCES
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    if circle.contribution_amount.is_zero() {
+        return Ok(0);
+    }
+    Ok((balance.u128() / circle.contribution_amount.u128()) as u32)
+}
+
+/// Add to a member's prepaid balance and the circle aggregate.
+fn credit_prepaid(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let existing = PREPAID_BALANCES
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let new_balance = existing
+        .checked_add(amount)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Prepaid balance overflow".to_string(),
+        })?;
+    PREPAID_BALANCES.save(storage, (circle.circle_id, member.clone()), &new_balance)?;
+    circle.total_prepaid = circle.total_prepaid.checked_add(amount).unwrap_or(circle.total_prepaid);
+    Ok(())
+}
+
+/// Remove a member's whole prepaid balance (exit, ejection, cancel, wind-down, finalize).
+/// Returns the amount so the caller can refund it.
+fn take_prepaid(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+) -> Result<Uint128, ContractError> {
+    let balance = PREPAID_BALANCES
+        .may_load(storage, (circle.circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    PREPAID_BALANCES.remove(storage, (circle.circle_id, member.clone()));
+    circle.total_prepaid = circle.total_prepaid.saturating_sub(balance);
+    Ok(balance)
+}
+
+/// Refund every outstanding prepaid balance of the circle via PENDING_PAYOUTS.
+fn refund_all_prepaid(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+) -> Result<(), ContractError> {
+    let holders: Vec<Addr> = PREPAID_BALANCES
+        .prefix(circle.circle_id)
+        .keys(storage, None, None, Order::Ascending)
+        .filter_map(|k| k.ok())
+        .collect();
+    for member in holders {
+        let amount = take_prepaid(storage, circle, &member)?;
+        credit_pending_payout(
+            storage,
+            circle.circle_id,
+            &member,
+            amount,
+            &mut circle.total_pending_payouts,
+        )?;
+    }
+    Ok(())
+}
+
+/// Remove a member's partial deposit for the current round and return it so the
+/// caller can refund it (round closed, exit, ejection, cancel, wind-down).
+fn take_partial_deposit(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+) -> Result<Uint128, ContractError> {
+    let key = (circle.circle_id, member.clone(), circle.current_cycle_index);
+    let partial = PARTIAL_DEPOSITS
+        .may_load(storage, key.clone())?
+        .unwrap_or(Uint128::zero());
+    if !partial.is_zero() {
+        PARTIAL_DEPOSITS.remove(storage, key);
+        circle.total_partial_deposits = circle.total_partial_deposits.saturating_sub(partial);
+    }
+    Ok(partial)
+}
+
+/// Refund each listed member's partial deposit for the current round via PENDING_PAYOUTS.
+fn refund_partial_deposits(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    members: &[Addr],
+) -> Result<(), ContractError> {
+    for member in members {
+        let partial = take_partial_deposit(storage, circle, member)?;
+        credit_pending_payout(
+            storage,
+            circle.circle_id,
+            member,
+            partial,
+            &mut circle.total_pending_payouts,
+        )?;
+    }
+    Ok(())
+}
+
+/// Last cycle member deposited for (from MEMBER_LAST_DEPOSITED_CYCLE or scan DEPOSITS for backward compat)
+fn last_deposited_cycle(storage: &dyn Storage, circle_id: u64, member: &Addr) -> StdResult<u32> {
+    Ok(MEMBER_LAST_DEPOSITED_CYCLE
+        .may_load(storage, (circle_id, member.clone()))?
+        .or_else(|| {
+            DEPOSITS
+                .prefix((circle_id, member.clone()))
+                .range(storage, None, None, Order::Descending)
+                .next()
+                .and_then(|r| r.ok())
+                .map(|(c, _)| c)
+        })
+        .unwrap_or(0))
+}
+
+/// Record a full deposit for the current round funded without a
+/// DepositContribution from the member (prepaid balance, auto-debit). On time
+/// unless a PerHour `grace_fee` was charged for coming in after the deadline;
+/// that fee goes to the penalty pool like a manual late deposit's.
+fn record_funded_deposit(
+    storage: &mut dyn Storage,
+    circle: &mut Circle,
+    member: &Addr,
+    timestamp: Timestamp,
+    grace_fee: Uint128,
+) -> Result<(), ContractError> {
+    let cycle = circle.current_cycle_index;
+    let on_time = grace_fee.is_zero();
+    DEPOSITS.save(
+        storage,
+        (circle.circle_id, member.clone(), cycle),
+        &DepositRecord {
+            member: member.clone(),
+            cycle,
+            amount: circle.contribution_amount,
+            timestamp,
+            on_time,
+        },
+    )?;
+    MEMBER_LAST_DEPOSITED_CYCLE.save(storage, (circle.circle_id, member.clone()), &cycle)?;
+    update_member_history(storage, member, |h| {
+        if on_time {
+            h.deposits_on_time += 1;
+        } else {
+            h.deposits_late += 1;
+        }
+        h.total_deposited += circle.contribution_amount;
+    })?;
+    circle.total_amount_locked = circle
+        .total_amount_locked
+        .checked_add(circle.contribution_amount)
+        .map_err(|_| ContractError::InvalidParameters {
+            msg: "Total amount overflow".to_string(),
+        })?;
+    if on_time {
+        circle.members_paid_this_cycle.push(member.clone());
+    } else {
+        circle.total_penalties_collected = circle
+            .total_penalties_collected
+            .checked_add(grace_fee)
+            .map_err(|_| ContractError::InvalidParameters {
+                msg: "Penalties overflow".to_string(),
+            })?;
+        PENALTIES.save(
+            storage,
+            (circle.circle_id, member.clone(), cycle),
+            &PenaltyRecord {
+                member: member.clone(),
+                cycle,
+                amount: grace_fee,
+                reason: format!(
+                    "Late deposit: funded after the deadline ({:?} late fees paid in tokens)",
+                    circle.late_fee_policy
+                ),
+                timestamp,
+            },
+        )?;
+        circle.members_late_this_cycle.push(member.clone());
+    }
+    release_payout_collateral(storage, circle, member)?;
+    Ok(())
+}
+
+/// Apply `update` to a member's cross-circle MEMBER_HISTORY record.
+fn update_member_history(
+    storage: &mut dyn Storage,
+    member: &Addr,
+    update: impl FnOnce(&mut MemberHistory),
+) -> StdResult<()> {
+    let mut history = MEMBER_HISTORY
+        .may_load(storage, member.clone())?
+        .unwrap_or_default();
+    update(&mut history);
+    MEMBER_HISTORY.save(storage, member.clone(), &history)
+}
+
+/// Count the circle in every starting member's history.
+fn record_circle_joined(storage: &mut dyn Storage, circle: &Circle) -> StdResult<()> {
+    for member in &circle.members_list {
+        update_member_history(storage, member, |h| h.circles_joined += 1)?;
+    }
+    Ok(())
+}
+
+/// Settle the current round from prepaid balances for active members who have not
+/// deposited yet. Runs before the missing-member pass of ProcessPayout / AdvanceRound,
+/// so a prepaid member is never late-fee'd or drained from their lock.
+fn consume_prepaid_for_round(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle: &mut Circle,
+    active_members: &[Addr],
+) -> Result<(), ContractError> {
+    let cycle = circle.current_cycle_index;
+    for member in active_members {
+        if DEPOSITS
+            .may_load(deps.storage, (circle.circle_id, member.clone(), cycle))?
+            .is_some()
+        {
+            continue;
+        }
+        let balance = PREPAID_BALANCES
+            .may_load(deps.storage, (circle.circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero());
+        if circle.contribution_amount.is_zero() || balance < circle.contribution_amount {
+            continue;
+        }
+        let remaining = balance - circle.contribution_amount;
+        if remaining.is_zero() {
+            PREPAID_BALANCES.remove(deps.storage, (circle.circle_id, member.clone()));
+        } else {
+            PREPAID_BALANCES.save(deps.storage, (circle.circle_id, member.clone()), &remaining)?;
+        }
+        circle.total_prepaid = circle.total_prepaid.saturating_sub(circle.contribution_amount);
+        record_funded_deposit(deps.storage, circle, member, env.block.time, Uint128::zero())?;
+
+        log_event(
+            deps,
+            env,
+            circle.circle_id,
+            "prepaid_deposit_applied",
+            &format!(
+                "Member {} cycle {} covered from prepaid balance ({} left)",
+                member, cycle, remaining
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+fn log_event(
+    deps: &mut DepsMut,
+    env: &Env,
+    circle_id: u64,
+    event_type: &str,
+    data: &str,
+) -> StdResult<()> {
+    let event_id = EVENT_COUNTER
+        .may_load(deps.storage, circle_id)?
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Event ID overflow"))?;
+
+    EVENTS.save(
+        deps.storage,
+        (circle_id, event_id),
+        &EventLog {
+            event_type: event_type.to_string(),
+            circle_id,
+            data: data.to_string(),
+            timestamp: env.block.time,
+        },
+    )?;
+    EVENT_COUNTER.save(deps.storage, circle_id, &event_id)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, from_json};
+    use crate::msg::InstantiateMsg;
+    use crate::state::PlatformConfig;
+
+    fn setup_platform_config(deps: &mut cosmwasm_std::OwnedDeps<cosmwasm_std::MemoryStorage, cosmwasm_std::testing::MockApi, cosmwasm_std::testing::MockQuerier>) {
+        PLATFORM_CONFIG
+            .save(
+                &mut deps.storage,
+                &PlatformConfig {
+                    platform_fee_percent: 100,
+                    platform_address: Addr::unchecked("platform"),
+                    attestation_registry: None,
+                },
+            )
+            .unwrap();
+    }
+
+    fn base_create_msg() -> ExecuteMsg {
+        ExecuteMsg::CreateCircle {
+            circle_name: "test".to_string(),
+            circle_description: "test".to_string(),
+            circle_image: None,
+            max_members: 3,
+            min_members_required: 2,
+            invite_only: false,
+            contribution_amount: Uint128::from(100u128),
+            denomination: None,
+            exit_penalty_percent: 2000,
+            late_fee_percent: 1000,
+            total_cycles: 2,
+            cycle_duration_days: 0,
+            cycle_duration_seconds: Some(300), // 5 min
+            start_date: None,
+            grace_period_hours: 0,
+            grace_period_seconds: Some(60), // 1 min
+            auto_start_when_full: false,
+            auto_start_type: None,
+            auto_start_date: None,
+            payout_order_type: PayoutOrderType::RandomOrder,
+            payout_order_list: None,
+            auto_payout_enabled: true,
+            manual_trigger_enabled: false,
+            emergency_stop_enabled: false,
+            auto_refund_if_min_not_met: false,
+            strict_mode: false,
+            visibility: Visibility::Public,
+            show_member_identities: true,
+            distribution_threshold: None,
+            creator_collateral_policy: None,
+            late_fee_policy: None,
+            join_requirements: None,
+            requires_attestation: false,
+            stake_locks: false,
+            reserve_premium_bps: None,
+            post_payout_collateral: false,
+            kind: CircleKind::Rotating,
+        }
+    }
+
+    // creator_lock = contribution * 2 (see compute_creator_lock)
+    fn creator_lock(_max_members: u32, contribution: u128) -> u128 {
+        contribution * 2
+    }
+
+    #[test]
+    fn compute_max_missed_base_works() {
+        // 20% exit + 10% late fee → (10000 - 2000) / 1000 = 8 missed allowed
+        assert_eq!(compute_max_missed_base(2000, 1000), 8);
+        // late_fee_percent = 0 → u32::MAX (no late fee → no missed-count cap)
+        assert_eq!(compute_max_missed_base(2000, 0), u32::MAX);
+    }
+
+    #[test]
+    fn max_missed_matches_fee_exhaustion_for_every_policy() {
+        // exit penalty 20% leaves 8000 bps of a one-contribution lock for fees
+        let budget = 8000u64;
+        let cases = [
+            (LateFeePolicy::FlatPerRound, 8),
+            // 1000 + 2000 + 3000 = 6000; a 4th miss would reach 10000
+            (LateFeePolicy::Escalating { step_bps: 1000 }, 3),
+            // 2h grace window at 500 bps/hour = 1000 per full miss
+            (LateFeePolicy::PerHour { bps_per_hour: 500 }, 8),
+            (LateFeePolicy::Capped { cap_bps: 8000 }, 8),
+            // fees stop at 30%, so only the round cap can eject
+            (LateFeePolicy::Capped { cap_bps: 3000 }, u32::MAX),
+        ];
+        for (policy, expected) in cases {
+            let max = compute_max_missed_for_policy(&policy, 2000, 1000, 7200);
+            assert_eq!(max, expected, "{:?}", policy);
+            if max != u32::MAX {
+                assert!(cumulative_late_fee_bps(&policy, 1000, 7200, max) <= budget);
+                let next = cumulative_late_fee_bps(&policy, 1000, 7200, max + 1);
+                assert!(next >= budget, "{:?}", policy);
+            }
+        }
+        // Flat keeps the original closed form
+        assert_eq!(
+            compute_max_missed_for_policy(&LateFeePolicy::FlatPerRound, 2000, 1000, 0),
+            compute_max_missed_base(2000, 1000)
+        );
+    }
+
+    #[test]
+    fn cap_max_missed_by_rounds_works() {
+        // total_rounds = 6, base = 8 → cap to 5 (so ejection happens before last round)
+        assert_eq!(cap_max_missed_by_rounds(8, 6), 5);
+        // total_rounds = 1 → cap = 1 (max(1, 0))
+        assert_eq!(cap_max_missed_by_rounds(8, 1), 1);
+        // base smaller than cap → unchanged
+        assert_eq!(cap_max_missed_by_rounds(2, 6), 2);
+    }
+
+    #[test]
+    fn compute_max_missed_scaled_shrinks_when_members_drop() {
+        // 5 active of 5 start → base
+        assert_eq!(compute_max_missed_scaled(compute_max_missed_base(2000, 1000), Some(5), 5), 8);
+        // 3 active of 5 start → base * 3/5 = 4
+        assert_eq!(compute_max_missed_scaled(compute_max_missed_base(2000, 1000), Some(5), 3), 4);
+        // never below 1
+        assert_eq!(compute_max_missed_scaled(compute_max_missed_base(2000, 1000), Some(100), 1), 1);
+        // None members_at_start → falls back to base
+        assert_eq!(compute_max_missed_scaled(compute_max_missed_base(2000, 1000), None, 3), 8);
+    }
+
+    #[test]
+    fn create_circle_rejects_grace_ge_cycle() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+
+        // grace == cycle → reject
+        let msg = ExecuteMsg::CreateCircle {
+            circle_name: "test".to_string(),
+            circle_description: "test".to_string(),
+            circle_image: None,
+            max_members: 3,
+            min_members_required: 2,
+            invite_only: false,
+            contribution_amount: Uint128::from(100u128),
+            denomination: None,
+            exit_penalty_percent: 2000,
+            late_fee_percent: 1000,
+            total_cycles: 2,
+            cycle_duration_days: 0,
+            cycle_duration_seconds: Some(300),
+            start_date: None,
+            grace_period_hours: 0,
+            grace_period_seconds: Some(300), // == cycle
+            auto_start_when_full: false,
+            auto_start_type: None,
+            auto_start_date: None,
+            payout_order_type: PayoutOrderType::RandomOrder,
+            payout_order_list: None,
+            auto_payout_enabled: true,
+            manual_trigger_enabled: false,
+            emergency_stop_enabled: false,
+            auto_refund_if_min_not_met: false,
+            strict_mode: false,
+            visibility: Visibility::Public,
+            show_member_identities: true,
+            distribution_threshold: None,
+            creator_collateral_policy: None,
+            late_fee_policy: None,
+            join_requirements: None,
+            requires_attestation: false,
+            stake_locks: false,
+            reserve_premium_bps: None,
+            post_payout_collateral: false,
+            kind: CircleKind::Rotating,
+        };
+        let info = mock_info("creator", &coins(creator_lock(3, 100), "usaf"));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        match err {
+            ContractError::InvalidParameters { msg } => {
+                assert!(msg.contains("grace_period"), "expected grace_period error, got: {}", msg);
+                assert!(msg.contains("strictly less than"), "got: {}", msg);
+            }
+            other => panic!("expected InvalidParameters, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn create_circle_accepts_grace_lt_cycle() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+        let info = mock_info("creator", &coins(creator_lock(3, 100), "usaf"));
+        let res = execute(deps.as_mut(), mock_env(), info, base_create_msg());
+        assert!(res.is_ok(), "expected success, got {:?}", res.err());
+    }
+
+    #[test]
+    fn auto_start_when_full_sets_members_at_start() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+
+        // Create with auto_start_when_full + by_members, max=2 so 1 join = full
+        let create_msg = ExecuteMsg::CreateCircle {
+            circle_name: "auto".to_string(),
+            circle_description: "auto".to_string(),
+            circle_image: None,
+            max_members: 2,
+            min_members_required: 2,
+            invite_only: false,
+            contribution_amount: Uint128::from(100u128),
+            denomination: None,
+            exit_penalty_percent: 2000,
+            late_fee_percent: 1000,
+            total_cycles: 2,
+            cycle_duration_days: 0,
+            cycle_duration_seconds: Some(300),
+            start_date: None,
+            grace_period_hours: 0,
+            grace_period_seconds: Some(60),
+            auto_start_when_full: true,
+            auto_start_type: Some("by_members".to_string()),
+            auto_start_date: None,
+            payout_order_type: PayoutOrderType::RandomOrder,
+            payout_order_list: None,
+            auto_payout_enabled: true,
+            manual_trigger_enabled: false,
+            emergency_stop_enabled: false,
+            auto_refund_if_min_not_met: false,
+            strict_mode: false,
+            visibility: Visibility::Public,
+            show_member_identities: true,
+            distribution_threshold: None,
+            creator_collateral_policy: None,
+            late_fee_policy: None,
+            join_requirements: None,
+            requires_attestation: false,
+            stake_locks: false,
+            reserve_premium_bps: None,
+            post_payout_collateral: false,
+            kind: CircleKind::Rotating,
+        };
+        let creator_info = mock_info("creator", &coins(creator_lock(2, 100), "usaf"));
+        execute(deps.as_mut(), mock_env(), creator_info, create_msg).unwrap();
+
+        // Second member joins → triggers auto-start
+        let join_msg = ExecuteMsg::JoinCircle {
+            circle_id: 1,
+            invite_code: None,
+        };
+        let join_info = mock_info("alice", &coins(100, "usaf"));
+        execute(deps.as_mut(), mock_env(), join_info, join_msg).unwrap();
+
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(matches!(circle.circle_status, CircleStatus::Running));
+        assert_eq!(circle.current_cycle_index, 1);
+        // members_at_start MUST be set on auto-start (regression: was None before)
+        assert_eq!(circle.members_at_start, Some(2));
+        // max_missed_payments_allowed: base 8, scaled 8*2/2=8, capped to total_rounds-1=3
+        assert_eq!(circle.max_missed_payments_allowed, 3);
+    }
+
+    #[test]
+    fn should_eject_uses_missed_count_cap() {
+        let mut deps = mock_dependencies();
+        let circle_id = 42u64;
+        let member = Addr::unchecked("alice");
+        let original_lock = Uint128::from(100u128);
+
+        // No missed payments yet → not ejected
+        assert!(!should_eject_member(
+            &deps.storage,
+            circle_id,
+            &member,
+            original_lock,
+            2000, // exit_penalty_percent 20%
+            3,    // max_missed_payments_allowed
+        ));
+
+        // 2 missed payments, max is 3 → still not ejected via missed_count, and accumulated late fees too low
+        MEMBER_MISSED_PAYMENTS
+            .save(
+                &mut deps.storage,
+                (circle_id, member.clone()),
+                &MemberMissedPayments {
+                    member: member.clone(),
+                    missed_count: 2,
+                    last_missed_cycle: Some(2),
+                    last_fee_round: None,
+                },
+            )
+            .unwrap();
+        assert!(!should_eject_member(
+            &deps.storage,
+            circle_id,
+            &member,
+            original_lock,
+            2000,
+            3,
+        ));
+
+        // 3 missed payments → hard cap triggers ejection regardless of fees
+        MEMBER_MISSED_PAYMENTS
+            .save(
+                &mut deps.storage,
+                (circle_id, member.clone()),
+                &MemberMissedPayments {
+                    member: member.clone(),
+                    missed_count: 3,
+                    last_missed_cycle: Some(3),
+                    last_fee_round: None,
+                },
+            )
+            .unwrap();
+        assert!(should_eject_member(
+            &deps.storage,
+            circle_id,
+            &member,
+            original_lock,
+            2000,
+            3,
+        ));
+    }
+
+    #[test]
+    fn should_eject_via_accumulated_fees_path_still_works() {
+        let mut deps = mock_dependencies();
+        let circle_id = 1u64;
+        let member = Addr::unchecked("bob");
+        let original_lock = Uint128::from(100u128);
+        // exit penalty 20% = 20. accumulated 80 → 80 + 20 = 100 ≥ 100 → eject.
+        MEMBER_ACCUMULATED_LATE_FEES
+            .save(
+                &mut deps.storage,
+                (circle_id, member.clone()),
+                &Uint128::from(80u128),
+            )
+            .unwrap();
+        // missed_count below cap so we exercise only the accumulated branch
+        assert!(should_eject_member(
+            &deps.storage,
+            circle_id,
+            &member,
+            original_lock,
+            2000,
+            100, // very high cap → can't trigger via missed_count
+        ));
+    }
+
+    #[test]
+    fn apply_running_state_sets_all_fields() {
+        let mut circle = Circle {
+            circle_id: 1,
+            circle_name: "x".to_string(),
+            circle_description: "x".to_string(),
+            circle_image: None,
+            creator_address: Addr::unchecked("creator"),
+            created_at: Timestamp::from_seconds(0),
+            updated_at: Timestamp::from_seconds(0),
+            max_members: 3,
+            min_members_required: 2,
+            invite_only: false,
+            members_list: vec![Addr::unchecked("a"), Addr::unchecked("b"), Addr::unchecked("c")],
+            pending_members: vec![],
+            contribution_amount: Uint128::from(100u128),
+            denomination: "usaf".to_string(),
+            payout_amount: Uint128::from(300u128),
+            exit_penalty_percent: 2000,
+            late_fee_percent: 1000,
+            platform_fee_percent: 100,
+            max_missed_payments_allowed: 0,
+            members_at_start: None,
+            total_cycles: 2,
+            cycle_duration_days: 0,
+            cycle_duration_seconds: 300,
+            start_date: None,
+            first_cycle_date: None,
+            next_payout_date: None,
+            end_date: None,
+            grace_period_hours: 0,
+            grace_period_seconds: 60,
+            auto_start_when_full: true,
+            auto_start_type: Some("by_members".to_string()),
+            auto_start_date: None,
+            payout_order_type: PayoutOrderType::RandomOrder,
+            payout_order_list: None,
+            auto_payout_enabled: true,
+            manual_trigger_enabled: false,
+            emergency_stop_enabled: false,
+            emergency_stop_triggered: false,
+            auto_refund_if_min_not_met: false,
+            strict_mode: false,
+            escrow_address: None,
+            total_amount_locked: Uint128::zero(),
+            total_penalties_collected: Uint128::zero(),
+            total_platform_fees_collected: Uint128::zero(),
+            total_pending_payouts: Uint128::zero(),
+            withdrawal_lock: false,
+            refund_mode: RefundMode::FullRefund,
+            creator_lock_amount: Uint128::from(130u128),
+            creator_collateral_policy: CollateralPolicy::default(),
+            late_fee_policy: LateFeePolicy::default(),
+            distribution_threshold: Some(DistributionThreshold::Total {}),
+            join_requirements: None,
+            requires_attestation: false,
+            stake_locks: false,
+            lock_stake: None,
+            reserve: None,
+            post_payout_collateral: false,
+            total_payout_collateral: Uint128::zero(),
+            predecessor_id: None,
+            successor_id: None,
+            kind: CircleKind::Rotating,
+            savings: None,
+            total_paused_seconds: 0,
+            total_prepaid: Uint128::zero(),
+            total_partial_deposits: Uint128::zero(),
+            circle_status: CircleStatus::Full,
+            current_cycle_index: 0,
+            cycles_completed: 0,
+            members_paid_this_cycle: vec![],
+            members_late_this_cycle: vec![],
+            visibility: Visibility::Public,
+            show_member_identities: true,
+        };
+
+        let now = Timestamp::from_seconds(1_700_000_000);
+        apply_running_state(&mut circle, now);
+
+        assert!(matches!(circle.circle_status, CircleStatus::Running));
+        assert_eq!(circle.current_cycle_index, 1);
+        assert_eq!(circle.start_date, Some(now));
+        assert_eq!(circle.first_cycle_date, Some(now));
+        assert_eq!(circle.next_payout_date, Some(now));
+        assert_eq!(circle.members_at_start, Some(3));
+        // total_rounds = 6, base 8, capped to 5
+        assert_eq!(circle.max_missed_payments_allowed, 5);
+        assert_eq!(
+            circle.end_date,
+            Some(Timestamp::from_seconds(now.seconds() + 300 * 6))
+        );
+    }
+
+    // Round-progression invariant is exercised end-to-end by ProcessPayout / AdvanceRound;
+    // a focused unit test would require constructing a full Running circle with deposits.
+    // The check is cheap and mostly a defense-in-depth assertion against future refactors.
 
-    if dust.is_zero() {
-        return Err(ContractError::NoPendingPayouts {});
+    #[test]
+    fn json_responses_round_trip() {
+        // Sanity check that base_create_msg serializes/deserializes properly.
+        let msg = base_create_msg();
+        let json = cosmwasm_std::to_json_binary(&msg).unwrap();
+        let parsed: ExecuteMsg = from_json(&json).unwrap();
+        assert_eq!(parsed, msg);
     }
 
-    // Credit the residual to the CREATOR's pending payouts so they can
-    // reclaim it with a normal `Withdraw` call. This is the correct owner
-    // for stuck creator-lock refunds (the most common cause of dust).
-    //
-    // After this credit, `total_pending_payouts` is non-zero again - so the
-    // circle goes BACK to Finalizing (if it had flipped to Completed) until
-    // the creator's `Withdraw` zeroes it. Documented invariant preserved:
-    // Completed only when bank balance == 0.
-    credit_pending_payout(
-        deps.storage,
-        circle_id,
-        &circle.creator_address,
-        dust,
-        &mut circle.total_pending_payouts,
-    )?;
-    if circle.circle_status == CircleStatus::Completed {
-        // We just made the chain truth non-zero again; reflect that.
-        circle.circle_status = CircleStatus::Finalizing;
+    #[test]
+    fn instantiate_smoke() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            platform_fee_percent: 100,
+            platform_address: Addr::unchecked("platform"),
+            attestation_registry: None,
+        };
+        let info = mock_info("creator", &[]);
+        let res = crate::contract::instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
     }
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "dust_swept",
-        &format!(
-            "{} usaf residual credited to creator {} (triggered by {}); creator can now Withdraw",
-            dust, circle.creator_address, info.sender
-        ),
-    )?;
+    type MockDeps = cosmwasm_std::OwnedDeps<
+        cosmwasm_std::MemoryStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    >;
+
+    /// `base_create_msg` as an invite-only Private circle (Public creation is disabled).
+    fn private_create_msg() -> ExecuteMsg {
+        let mut msg = base_create_msg();
+        if let ExecuteMsg::CreateCircle {
+            ref mut visibility,
+            ref mut invite_only,
+            ..
+        } = msg
+        {
+            *visibility = Visibility::Private;
+            *invite_only = true;
+        }
+        msg
+    }
 
-    // No outbound BankMsg - the creator will pull the funds via Withdraw,
-    // which keeps `total_pending_payouts` accounting consistent.
-    Ok(Response::new()
-        .add_attribute("action", "sweep_dust")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("recipient", circle.creator_address.to_string())
-        .add_attribute("amount", dust.to_string()))
-}
+    /// Create circle 1 (private, max 3 members, contribution 100) owned by "creator".
+    fn create_private_circle(deps: &mut MockDeps) {
+        setup_platform_config(deps);
+        let info = mock_info("creator", &coins(creator_lock(3, 100), "usaf"));
+        execute(deps.as_mut(), mock_env(), info, private_create_msg()).unwrap();
+    }
 
-// ---------------------------------------------------------------------------
-// Deposit Creator Reward - platform sends X% of total volume to creator
-// ---------------------------------------------------------------------------
+    fn join_with_code(deps: &mut MockDeps, member: &str, code: &str) -> Result<Response, ContractError> {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(member, &coins(100, "usaf")),
+            ExecuteMsg::JoinCircle {
+                circle_id: 1,
+                invite_code: Some(code.to_string()),
+            },
+        )
+    }
 
-/// Platform-funded reward for the creator, paid out of the platform / server
-/// wallet when a circle transitions to `Finalizing`. The caller attaches the
-/// reward amount as funds in the circle's denomination; the contract validates
-/// it, credits the creator's `PENDING_PAYOUTS`, and locks the per-circle
-/// idempotency flag so a duplicate broadcast cannot double-credit.
-///
-/// Design notes
-/// ------------
-///   - Permissionless: sending funds INTO the contract for someone else is
-///     harmless and lets any external party (DAO, partner, the server itself)
-///     boost a circle. The idempotency guard (CREATOR_REWARDS_CREDITED)
-///     prevents abuse.
-///   - Status gate is `Finalizing` (NOT Completed). At Finalizing the final
-///     round's PENDING_PAYOUTS are already credited; the creator's first
-///     `Withdraw` call after this credit will pick up both the round payout(s)
-///     AND the reward in one go. Pre-Finalizing the round flow hasn't run so
-///     the volume basis isn't fully known yet; post-Completed the bank should
-///     already be drained and adding to PENDING_PAYOUTS would resurrect the
-///     "Completed with non-zero balance" state we just fixed.
-///   - The reward is added to `total_pending_payouts` so the bank-vs-pending
-///     invariant on Withdraw stays consistent: the funds we just received
-///     are immediately backed by an equal pending credit.
-fn execute_deposit_creator_reward(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+    #[test]
+    fn invite_code_is_redeemed_and_consumed() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+
+        let creator = mock_info("creator", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            creator,
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("whatsapp-1234").to_uppercase(),
+                max_uses: 1,
+                expires_at: None,
+            },
+        )
+        .unwrap();
 
-    // Status gate: only at Finalizing. See doc-comment above.
-    if !matches!(circle.circle_status, CircleStatus::Finalizing) {
-        return Err(ContractError::InvalidCircleStatus {
-            expected: "Finalizing".to_string(),
-            actual: format!("{:?}", circle.circle_status),
-        });
-    }
+        // Wrong preimage is rejected, and without a code the invite gate still applies
+        let err = join_with_code(&mut deps, "alice", "wrong").unwrap_err();
+        assert_eq!(err, ContractError::InvalidInviteCode { circle_id: 1 });
 
-    // Idempotency: refuse a second reward credit for the same circle.
-    if CREATOR_REWARDS_CREDITED
-        .may_load(deps.storage, circle_id)?
-        .is_some()
-    {
-        return Err(ContractError::InvalidParameters {
-            msg: "Creator reward already credited for this circle".to_string(),
-        });
+        join_with_code(&mut deps, "alice", "whatsapp-1234").unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.members_list.contains(&Addr::unchecked("alice")));
+
+        // Single-use code is gone after redemption
+        assert!(INVITE_CODES
+            .may_load(&deps.storage, (1, hash_invite_code("whatsapp-1234")))
+            .unwrap()
+            .is_none());
+        let err = join_with_code(&mut deps, "bob", "whatsapp-1234").unwrap_err();
+        assert_eq!(err, ContractError::InvalidInviteCode { circle_id: 1 });
     }
 
-    // Pull attached funds in the circle's denomination.
-    let amount = must_pay(&info, &circle.denomination).map_err(|_| {
-        ContractError::InsufficientFunds {
-            required: "non-zero reward in circle denomination".to_string(),
-            sent: "0".to_string(),
-        }
-    })?;
-    if amount.is_zero() {
-        return Err(ContractError::InsufficientFunds {
-            required: "non-zero reward".to_string(),
-            sent: "0".to_string(),
-        });
+    #[test]
+    fn invite_code_rejected_after_expiry() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+
+        let env = mock_env();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("family"),
+                max_uses: 5,
+                expires_at: Some(env.block.time.plus_seconds(3600)),
+            },
+        )
+        .unwrap();
+
+        let mut late = mock_env();
+        late.block.time = env.block.time.plus_seconds(3600);
+        let err = execute(
+            deps.as_mut(),
+            late,
+            mock_info("alice", &coins(100, "usaf")),
+            ExecuteMsg::JoinCircle {
+                circle_id: 1,
+                invite_code: Some("family".to_string()),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InviteCodeExpired { circle_id: 1, .. }));
+    }
+
+    fn add_private_member(deps: &mut MockDeps, member: &str) -> Result<Response, ContractError> {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::AddPrivateMember {
+                circle_id: 1,
+                member_address: Addr::unchecked(member),
+                pseudonym: Some(format!("{}-nick", member)),
+                acceptance_window_seconds: Some(600),
+            },
+        )
     }
 
-    // Credit the creator's pending payouts. They will pick this up via the
-    // existing `Withdraw` flow alongside any final-round payouts.
-    credit_pending_payout(
-        deps.storage,
-        circle_id,
-        &circle.creator_address,
-        amount,
-        &mut circle.total_pending_payouts,
-    )?;
+    #[test]
+    fn add_private_member_requires_acceptance_with_lock() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        add_private_member(&mut deps, "alice").unwrap();
 
-    CREATOR_REWARDS_CREDITED.save(deps.storage, circle_id, &amount)?;
-    circle.updated_at = env.block.time;
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
+        // Reservation only: not a member yet, no status change
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.members_list, vec![Addr::unchecked("creator")]);
+        assert!(circle.pending_members.contains(&Addr::unchecked("alice")));
+        assert!(matches!(circle.circle_status, CircleStatus::Draft));
+
+        // Accepting without the join lock fails
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::AcceptInvite { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds { .. }));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(100, "usaf")),
+            ExecuteMsg::AcceptInvite { circle_id: 1 },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.members_list.contains(&Addr::unchecked("alice")));
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(&deps.storage, (1, Addr::unchecked("alice")))
+                .unwrap(),
+            Uint128::from(100u128)
+        );
+        assert!(!PRIVATE_RESERVATIONS.has(&deps.storage, (1, Addr::unchecked("alice"))));
+    }
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "creator_reward_credited",
-        &format!(
-            "{} usaf reward credited to creator {} by {}",
-            amount, circle.creator_address, info.sender
-        ),
-    )?;
+    #[test]
+    fn private_reservation_holds_seat_until_expiry() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        add_private_member(&mut deps, "alice").unwrap();
+        add_private_member(&mut deps, "bob").unwrap();
+
+        // max_members = 3: creator + two live reservations fill the circle
+        let err = add_private_member(&mut deps, "carol").unwrap_err();
+        assert_eq!(err, ContractError::CircleFull { max: 3 });
+
+        // After the window, bob can no longer accept and his seat is released
+        let mut late = mock_env();
+        late.block.time = late.block.time.plus_seconds(600);
+        let err = execute(
+            deps.as_mut(),
+            late.clone(),
+            mock_info("bob", &coins(100, "usaf")),
+            ExecuteMsg::AcceptInvite { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ReservationExpired { circle_id: 1, .. }));
+
+        execute(
+            deps.as_mut(),
+            late,
+            mock_info("creator", &[]),
+            ExecuteMsg::AddPrivateMember {
+                circle_id: 1,
+                member_address: Addr::unchecked("carol"),
+                pseudonym: None,
+                acceptance_window_seconds: None,
+            },
+        )
+        .unwrap();
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "deposit_creator_reward")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("creator", circle.creator_address.to_string())
-        .add_attribute("amount", amount.to_string())
-        .add_attribute("funder", info.sender.to_string()))
-}
+    #[test]
+    fn migrate_converts_unlocked_private_members_to_reservations() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        cw2::set_contract_version(&mut deps.storage, "crates.io:safrimba-contract", "0.1.0").unwrap();
 
-// ---------------------------------------------------------------------------
-// Private Circle Management
-// ---------------------------------------------------------------------------
+        // Legacy AddPrivateMember: pushed into members_list with no join lock
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.members_list.push(Addr::unchecked("legacy"));
+        circle.circle_status = CircleStatus::Open;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        PRIVATE_MEMBER_LIST
+            .save(&mut deps.storage, 1, &vec![Addr::unchecked("legacy")])
+            .unwrap();
 
-fn execute_add_private_member(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-    member_address: Addr,
-    pseudonym: Option<String>,
-) -> Result<Response, ContractError> {
-    let mut circle = CIRCLES.load(deps.storage, circle_id)?;
+        let res = crate::contract::migrate(deps.as_mut(), mock_env(), crate::msg::MigrateMsg {}).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "private_members_reserved" && a.value == "1"));
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can add private members".to_string(),
-        });
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.members_list, vec![Addr::unchecked("creator")]);
+        assert!(circle.pending_members.contains(&Addr::unchecked("legacy")));
+        assert!(matches!(circle.circle_status, CircleStatus::Draft));
+        assert!(PRIVATE_RESERVATIONS.has(&deps.storage, (1, Addr::unchecked("legacy"))));
+    }
+
+    fn fill_private_circle_via_code(deps: &mut MockDeps) {
+        create_private_circle(deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("group-code"),
+                max_uses: 10,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+        join_with_code(deps, "alice", "group-code").unwrap();
+        join_with_code(deps, "bob", "group-code").unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_000, "usaf"),
+        );
     }
 
-    if !matches!(circle.visibility, Visibility::Private) {
-        return Err(ContractError::InvalidParameters {
-            msg: "Circle must be private to use AddPrivateMember".to_string(),
-        });
+    fn join_waitlist(deps: &mut MockDeps, member: &str, prelock: u128) -> Result<Response, ContractError> {
+        let funds = if prelock == 0 { vec![] } else { coins(prelock, "usaf") };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(member, &funds),
+            ExecuteMsg::JoinWaitlist {
+                circle_id: 1,
+                invite_code: Some("group-code".to_string()),
+            },
+        )
     }
 
-    if circle.members_list.len() as u32 >= circle.max_members {
-        return Err(ContractError::CircleFull {
-            max: circle.max_members,
-        });
-    }
+    #[test]
+    fn waitlist_backfills_freed_seat_in_fifo_order() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        assert!(matches!(
+            CIRCLES.load(&deps.storage, 1).unwrap().circle_status,
+            CircleStatus::Full
+        ));
 
-    let validated = deps.api.addr_validate(member_address.as_str())?;
+        // Waitlist is only for circles without a free seat, and must be exact
+        let err = join_waitlist(&mut deps, "carol", 50).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds { .. }));
+        join_waitlist(&mut deps, "carol", 100).unwrap();
+        join_waitlist(&mut deps, "dave", 0).unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
 
-    if circle.members_list.contains(&validated) {
-        return Err(ContractError::AlreadyMember {
-            address: validated.to_string(),
-        });
+        // Carol pre-locked, so she takes the seat directly and the circle stays full
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.members_list.contains(&Addr::unchecked("carol")));
+        assert!(!circle.members_list.contains(&Addr::unchecked("alice")));
+        assert!(matches!(circle.circle_status, CircleStatus::Full));
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(&deps.storage, (1, Addr::unchecked("carol")))
+                .unwrap(),
+            Uint128::new(100)
+        );
+        let waitlist = WAITLIST.load(&deps.storage, 1).unwrap();
+        assert_eq!(waitlist.len(), 1);
+        assert_eq!(waitlist[0].member, Addr::unchecked("dave"));
+
+        // Dave has no pre-lock: the next freed seat becomes a reservation
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(PRIVATE_RESERVATIONS
+            .has(&deps.storage, (1, Addr::unchecked("dave"))));
+        assert!(WAITLIST.may_load(&deps.storage, 1).unwrap().is_none());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dave", &coins(100, "usaf")),
+            ExecuteMsg::AcceptInvite { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(CIRCLES
+            .load(&deps.storage, 1)
+            .unwrap()
+            .members_list
+            .contains(&Addr::unchecked("dave")));
     }
 
-    circle.members_list.push(validated.clone());
+    #[test]
+    fn waitlist_prelocks_refunded_on_leave_and_start() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        join_waitlist(&mut deps, "carol", 100).unwrap();
+        join_waitlist(&mut deps, "dave", 100).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dave", &[]),
+            ExecuteMsg::LeaveWaitlist { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.iter().any(|m| matches!(
+            &m.msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount })
+                if to_address == "carol" && amount == &coins(100, "usaf")
+        )));
+        assert!(WAITLIST.may_load(&deps.storage, 1).unwrap().is_none());
+    }
+
+    fn private_create_msg_with_policy(policy: CollateralPolicy) -> ExecuteMsg {
+        let mut msg = private_create_msg();
+        if let ExecuteMsg::CreateCircle {
+            ref mut creator_collateral_policy,
+            ..
+        } = msg
+        {
+            *creator_collateral_policy = Some(policy);
+        }
+        msg
+    }
 
-    let mut private_members = PRIVATE_MEMBER_LIST
-        .may_load(deps.storage, circle_id)?
-        .unwrap_or_default();
-    private_members.push(validated.clone());
-    PRIVATE_MEMBER_LIST.save(deps.storage, circle_id, &private_members)?;
+    #[test]
+    fn creator_lock_follows_quoted_collateral_policy() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
 
-    if let Some(pseudo) = pseudonym {
-        MEMBER_PSEUDONYMS.save(deps.storage, (circle_id, validated.clone()), &pseudo)?;
+        // 1 + 3 members * 10% = 1.3x contribution
+        let policy = CollateralPolicy::MemberScaled {
+            base_bps: 10000,
+            per_member_bps: 1000,
+        };
+        let quote: crate::msg::CreatorLockQuoteResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::QuoteCreatorLock {
+                    contribution_amount: Uint128::new(100),
+                    max_members: 3,
+                    creator_collateral_policy: Some(policy.clone()),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(quote.creator_lock_amount, Uint128::new(130));
+        assert_eq!(quote.platform_minimum, Uint128::new(100));
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(100, "usaf")),
+            private_create_msg_with_policy(policy.clone()),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds { .. }));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(130, "usaf")),
+            private_create_msg_with_policy(policy.clone()),
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.creator_lock_amount, Uint128::new(130));
+        assert_eq!(circle.total_amount_locked, Uint128::new(130));
+        assert_eq!(circle.creator_collateral_policy, policy);
     }
 
-    circle.updated_at = env.block.time;
+    #[test]
+    fn collateral_config_restricts_policies_and_sets_minimum() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
 
-    if circle.members_list.len() as u32 >= circle.max_members {
-        circle.circle_status = CircleStatus::Full;
-    } else if circle.circle_status == CircleStatus::Draft {
-        circle.circle_status = CircleStatus::Open;
+        let set_config = ExecuteMsg::SetCollateralConfig {
+            allowed_policies: vec![CollateralPolicyKind::FixedMultiple, CollateralPolicyKind::PotPercent],
+            min_lock_bps: 15000,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), set_config.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        execute(deps.as_mut(), mock_env(), mock_info("platform", &[]), set_config).unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(130, "usaf")),
+            private_create_msg_with_policy(CollateralPolicy::MemberScaled {
+                base_bps: 10000,
+                per_member_bps: 1000,
+            }),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::CollateralPolicyNotAllowed { .. }));
+
+        // 40% of a 300 pot = 120, below the 1.5x (150) floor
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(120, "usaf")),
+            private_create_msg_with_policy(CollateralPolicy::PotPercent { pot_bps: 4000 }),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::CollateralBelowMinimum { .. }));
+
+        // Default policy (2x) still clears the floor
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(creator_lock(3, 100), "usaf")),
+            private_create_msg(),
+        )
+        .unwrap();
     }
 
-    CIRCLES.save(deps.storage, circle_id, &circle)?;
-
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "private_member_added",
-        &format!("Private member {} added by {}", validated, info.sender),
-    )?;
+    fn bank_send_to(res: &Response, to: &str) -> Option<Uint128> {
+        res.messages.iter().find_map(|m| match &m.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) if to_address == to => {
+                Some(amount[0].amount)
+            }
+            _ => None,
+        })
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "add_private_member")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", validated))
-}
+    #[test]
+    fn transfer_creatorship_hands_over_creator_lock() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::TransferCreatorship {
+                circle_id: 1,
+                new_creator: Addr::unchecked("alice"),
+            },
+        )
+        .unwrap();
 
-fn execute_update_member_pseudonym(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-    member_address: Addr,
-    pseudonym: String,
-) -> Result<Response, ContractError> {
-    let circle = CIRCLES.load(deps.storage, circle_id)?;
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &coins(100, "usaf")),
+            ExecuteMsg::AcceptCreatorship { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // Creator lock is 2 x 100; alice's 100 join lock counts toward it
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(50, "usaf")),
+            ExecuteMsg::AcceptCreatorship { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds { .. }));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(100, "usaf")),
+            ExecuteMsg::AcceptCreatorship { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(bank_send_to(&res, "creator"), Some(Uint128::new(100)));
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can update pseudonyms".to_string(),
-        });
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.creator_address, Addr::unchecked("alice"));
+        assert_eq!(circle.creator_lock_amount, Uint128::new(200));
+        assert_eq!(circle.total_amount_locked, Uint128::new(400));
+        assert!(MEMBER_LOCKED_AMOUNTS
+            .may_load(&deps.storage, (1, Addr::unchecked("alice")))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(&deps.storage, (1, Addr::unchecked("creator")))
+                .unwrap(),
+            Uint128::new(100)
+        );
+        assert!(CREATOR_NOMINATIONS.may_load(&deps.storage, 1).unwrap().is_none());
     }
 
-    let validated = deps.api.addr_validate(member_address.as_str())?;
-    let is_member = circle.members_list.contains(&validated);
-    let is_pending = circle.pending_members.contains(&validated);
+    #[test]
+    fn member_majority_vote_nominates_new_creator() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+
+        let vote = |deps: &mut MockDeps, voter: &str| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(voter, &[]),
+                ExecuteMsg::VoteCreatorTransfer {
+                    circle_id: 1,
+                    nominee: Addr::unchecked("bob"),
+                },
+            )
+        };
 
-    if !is_member && !is_pending {
-        return Err(ContractError::InvalidParameters {
-            msg: "Address not found in circle members or pending invitations".to_string(),
-        });
+        let err = vote(&mut deps, "creator").unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // 2 non-creator voters: both must agree
+        vote(&mut deps, "alice").unwrap();
+        assert!(CREATOR_NOMINATIONS.may_load(&deps.storage, 1).unwrap().is_none());
+        vote(&mut deps, "bob").unwrap();
+        let nomination = CREATOR_NOMINATIONS.load(&deps.storage, 1).unwrap();
+        assert_eq!(nomination.nominee, Addr::unchecked("bob"));
+        assert_eq!(nomination.initiated_by, "vote");
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &coins(100, "usaf")),
+            ExecuteMsg::AcceptCreatorship { circle_id: 1 },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.creator_address, Addr::unchecked("bob"));
+        assert!(CREATOR_VOTES
+            .prefix(1)
+            .range(&deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
     }
 
-    MEMBER_PSEUDONYMS.save(deps.storage, (circle_id, validated.clone()), &pseudonym)?;
-
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "member_pseudonym_updated",
-        &format!("Pseudonym '{}' set for {}", pseudonym, validated),
-    )?;
+    #[test]
+    fn departing_creator_falls_back_to_earliest_member() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
 
-    Ok(Response::new()
-        .add_attribute("action", "update_member_pseudonym")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", validated)
-        .add_attribute("pseudonym", pseudonym))
-}
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(bank_send_to(&res, "creator"), Some(Uint128::new(200)));
 
-fn execute_block_member(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-    member_address: Addr,
-) -> Result<Response, ContractError> {
-    let circle = CIRCLES.load(deps.storage, circle_id)?;
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.creator_address, Addr::unchecked("alice"));
+        assert_eq!(circle.creator_lock_amount, Uint128::new(100));
+        assert_eq!(circle.total_amount_locked, Uint128::new(200));
+        assert!(matches!(circle.circle_status, CircleStatus::Open));
+
+        // The successor can now run admin actions
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+    }
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can block members".to_string(),
-        });
+    fn env_at(offset_secs: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(offset_secs);
+        env
     }
 
-    let validated = deps.api.addr_validate(member_address.as_str())?;
+    #[test]
+    fn wind_down_settles_net_positions_after_first_payout() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        // Pay out every round instead of the private default (last round only)
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        for member in ["creator", "alice", "bob"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &coins(100, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
+            .unwrap();
+        }
+        // Locks 200 + 100 + 100, round-1 deposits 300
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(700, "usaf"),
+        );
+        execute(
+            deps.as_mut(),
+            env_at(301),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ProcessPayout { circle_id: 1 },
+        )
+        .unwrap();
+        let paid: Vec<PayoutRecord> = PAYOUTS
+            .prefix((1, 1))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .map(|r| r.unwrap().1)
+            .collect();
+        assert_eq!(paid.len(), 1);
+        let recipient = paid[0].recipient.clone();
+        let pot = paid[0].amount; // 300 minus 1% platform fee
+
+        // Distribution happened: cancel is refused, wind-down is the way out
+        let err = execute(
+            deps.as_mut(),
+            env_at(310),
+            mock_info("creator", &[]),
+            ExecuteMsg::CancelCircle { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CancelNotAllowedAfterDistribution {});
+        // Not while the roster is intact, not even for the creator
+        for caller in ["alice", "creator"] {
+            let err = execute(
+                deps.as_mut(),
+                env_at(310),
+                mock_info(caller, &[]),
+                ExecuteMsg::WindDownCircle { circle_id: 1 },
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::Unauthorized { .. }));
+        }
+        // The roster falls below the minimum
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.min_members_required = 4;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+
+        let preview: crate::msg::WindDownPreviewResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                env_at(310),
+                crate::msg::QueryMsg::GetWindDownPreview { circle_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
 
-    if !circle.members_list.contains(&validated) {
-        return Err(ContractError::InvalidParameters {
-            msg: "Member not found in circle".to_string(),
-        });
-    }
+        execute(
+            deps.as_mut(),
+            env_at(310),
+            mock_info("creator", &[]),
+            ExecuteMsg::WindDownCircle { circle_id: 1 },
+        )
+        .unwrap();
 
-    let blocked_from_cycle = circle.current_cycle_index + 1;
-    BLOCKED_MEMBERS.save(deps.storage, (circle_id, validated.clone()), &blocked_from_cycle)?;
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(matches!(circle.circle_status, CircleStatus::WoundDown));
+        assert!(circle.total_amount_locked.is_zero());
+
+        // The early recipient covers what they owe (pot - 100 paid in) from
+        // their lock; everything the circle still holds (locks 400) is settled.
+        let mut settled = Uint128::zero();
+        for p in &preview.positions {
+            let pending = PENDING_PAYOUTS.load(&deps.storage, (1, p.member.clone())).unwrap();
+            if p.member == recipient {
+                assert_eq!(p.seized, (pot - Uint128::new(100)).min(p.lock));
+                assert_eq!(pending, pot + p.settlement);
+            } else {
+                assert_eq!(p.credit, Uint128::new(100));
+                assert_eq!(pending, p.settlement);
+                assert!(p.settlement > p.lock);
+            }
+            settled += p.settlement;
+        }
+        assert_eq!(settled, Uint128::new(400));
+    }
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "member_blocked",
-        &format!("Member {} blocked from cycle {}", validated, blocked_from_cycle),
-    )?;
+    #[test]
+    fn unpause_shifts_schedule_by_paused_duration() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let start = mock_env().block.time;
+        let before = CIRCLES.load(&deps.storage, 1).unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_at(100),
+            mock_info("creator", &[]),
+            ExecuteMsg::PauseCircle { circle_id: 1 },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(1100),
+            mock_info("creator", &[]),
+            ExecuteMsg::UnpauseCircle { circle_id: 1 },
+        )
+        .unwrap();
 
-    Ok(Response::new()
-        .add_attribute("action", "block_member")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("member", validated)
-        .add_attribute("blocked_from_cycle", blocked_from_cycle.to_string()))
-}
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.next_payout_date, Some(start.plus_seconds(1000)));
+        assert_eq!(
+            circle.end_date,
+            before.end_date.map(|t| t.plus_seconds(1000))
+        );
 
-fn execute_distribute_blocked_funds(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    circle_id: u64,
-    cycle: u32,
-) -> Result<Response, ContractError> {
-    let circle = CIRCLES.load(deps.storage, circle_id)?;
+        // Round 1 now ends at start + 1000 + 300, not start + 300
+        let err = execute(
+            deps.as_mut(),
+            env_at(1200),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ProcessPayout { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CycleNotReady {
+                next_date: start.plus_seconds(1300).seconds()
+            }
+        );
 
-    if info.sender != circle.creator_address {
-        return Err(ContractError::Unauthorized {
-            msg: "Only creator can distribute blocked funds".to_string(),
-        });
+        let history: crate::msg::PauseHistoryResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetPauseHistory { circle_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(history.total_paused_seconds, 1000);
+        assert_eq!(history.pauses.len(), 1);
+        assert_eq!(history.pauses[0].round, 1);
+        assert_eq!(history.pauses[0].resumed_at, Some(start.plus_seconds(1100)));
+
+        let calendar: crate::msg::DistributionCalendarResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetDistributionCalendar { circle_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let start_secs = start.seconds();
+        assert_eq!(calendar.rounds[0].deposit_deadline.seconds(), start_secs + 1000);
+        assert_eq!(calendar.rounds[0].distribution_date.seconds(), start_secs + 1300);
+        assert_eq!(calendar.rounds[1].deposit_deadline.seconds(), start_secs + 1300);
     }
 
-    let mut total_blocked_funds = Uint128::zero();
-    let mut blocked_in_cycle: Vec<(Addr, Uint128)> = Vec::new();
+    #[test]
+    fn prepaid_rounds_cover_future_deposits_and_refund_on_exit() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+
+        // 3 members x 2 cycles = 6 rounds: at most 5 can be paid ahead in round 1
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(700, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: Some(7) },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+
+        // Round 1 plus two prepaid rounds
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(300, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: Some(3) },
+        )
+        .unwrap();
+        assert_eq!(
+            PREPAID_BALANCES.load(&deps.storage, (1, Addr::unchecked("alice"))).unwrap(),
+            Uint128::new(200)
+        );
 
-    for member in &circle.members_list {
-        if let Some(blocked_cycle) =
-            BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, member.clone()))?
-        {
-            if blocked_cycle <= cycle {
-                if let Some(locked) =
-                    MEMBER_LOCKED_AMOUNTS.may_load(deps.storage, (circle_id, member.clone()))?
-                {
-                    total_blocked_funds += locked;
-                    blocked_in_cycle.push((member.clone(), locked));
-                }
+        for (round_end, balance) in [(301, 900), (601, 1100)] {
+            for member in ["creator", "bob"] {
+                execute(
+                    deps.as_mut(),
+                    env_at(round_end - 300),
+                    mock_info(member, &coins(100, "usaf")),
+                    ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+                )
+                .unwrap();
             }
+            deps.querier.update_balance(
+                cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+                coins(balance, "usaf"),
+            );
+            execute(
+                deps.as_mut(),
+                env_at(round_end),
+                mock_info("anyone", &[]),
+                ExecuteMsg::ProcessPayout { circle_id: 1 },
+            )
+            .unwrap();
         }
-    }
 
-    if total_blocked_funds.is_zero() {
-        return Err(ContractError::InvalidParameters {
-            msg: "No blocked funds to distribute".to_string(),
-        });
+        // Round 2 was settled from the prepaid balance, on time
+        let record = DEPOSITS
+            .load(&deps.storage, (1, Addr::unchecked("alice"), 2))
+            .unwrap();
+        assert!(record.on_time);
+        assert!(MEMBER_MISSED_PAYMENTS
+            .may_load(&deps.storage, (1, Addr::unchecked("alice")))
+            .unwrap()
+            .is_none());
+
+        let req: crate::msg::DepositRequirementResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetDepositRequirement {
+                    circle_id: 1,
+                    member: Addr::unchecked("alice"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(req.prepaid_balance, Uint128::new(100));
+        assert_eq!(req.prepaid_rounds, 1);
+
+        // Exit: lock 100 minus 20% penalty, plus the unused prepaid round in full
+        let res = execute(
+            deps.as_mut(),
+            env_at(610),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let refund = res
+            .attributes
+            .iter()
+            .find(|a| a.key == "refund_amount")
+            .unwrap();
+        assert_eq!(refund.value, "180");
+        assert!(CIRCLES.load(&deps.storage, 1).unwrap().total_prepaid.is_zero());
     }
 
-    let active_members: Vec<Addr> = circle
-        .members_list
-        .iter()
-        .filter(|m| {
-            BLOCKED_MEMBERS
-                .may_load(deps.storage, (circle_id, (*m).clone()))
-                .unwrap_or(None)
-                .map(|bc| bc > cycle)
-                .unwrap_or(true)
-        })
-        .filter(|m| {
-            DEPOSITS
-                .may_load(deps.storage, (circle_id, (*m).clone(), cycle))
-                .map(|d| d.is_some())
-                .unwrap_or(false)
-        })
-        .cloned()
-        .collect();
+    #[test]
+    fn partial_deposits_accumulate_until_round_is_covered() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+
+        let deposit = |deps: &mut MockDeps, member: &str, amount: u128| {
+            execute(
+                deps.as_mut(),
+                env_at(10),
+                mock_info(member, &coins(amount, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
+            .unwrap()
+        };
 
-    if active_members.is_empty() {
-        return Err(ContractError::InvalidParameters {
-            msg: "No active members to distribute to".to_string(),
-        });
+        let res = deposit(&mut deps, "alice", 40);
+        assert_eq!(res.attributes[0].value, "deposit_partial");
+        assert!(DEPOSITS
+            .may_load(&deps.storage, (1, Addr::unchecked("alice"), 1))
+            .unwrap()
+            .is_none());
+        let req: crate::msg::DepositRequirementResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetDepositRequirement {
+                    circle_id: 1,
+                    member: Addr::unchecked("alice"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(req.partial_deposited, Uint128::new(40));
+        assert_eq!(req.remaining_amount, Uint128::new(60));
+
+        // Second top-up completes the round
+        deposit(&mut deps, "alice", 60);
+        let record = DEPOSITS
+            .load(&deps.storage, (1, Addr::unchecked("alice"), 1))
+            .unwrap();
+        assert_eq!(record.amount, Uint128::new(100));
+        assert!(record.on_time);
+
+        // Bob never completes: his partial is refunded when the round closes
+        deposit(&mut deps, "creator", 100);
+        deposit(&mut deps, "bob", 30);
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.total_partial_deposits, Uint128::new(30));
+        assert_eq!(circle.total_amount_locked, Uint128::new(600));
+
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(730, "usaf"),
+        );
+        execute(
+            deps.as_mut(),
+            env_at(361),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ProcessPayout { circle_id: 1 },
+        )
+        .unwrap();
+
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.total_partial_deposits.is_zero());
+        let recipient = PAYOUTS
+            .prefix((1, 1))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .map(|r| r.unwrap().1.recipient)
+            .next()
+            .unwrap();
+        let bob_pending = PENDING_PAYOUTS
+            .load(&deps.storage, (1, Addr::unchecked("bob")))
+            .unwrap();
+        if recipient == Addr::unchecked("bob") {
+            assert!(bob_pending > Uint128::new(30));
+        } else {
+            assert_eq!(bob_pending, Uint128::new(30));
+        }
     }
 
-    let amount_per_member =
-        total_blocked_funds.multiply_ratio(1u128, active_members.len() as u128);
-    let remainder = total_blocked_funds
-        .checked_sub(amount_per_member * Uint128::from(active_members.len() as u128))
-        .unwrap_or(Uint128::zero());
+    #[test]
+    fn auto_debit_pulls_contribution_through_authz_exec() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        for member in ["alice", "bob"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &[]),
+                ExecuteMsg::SetAutoDebit { circle_id: 1, enabled: true },
+            )
+            .unwrap();
+        }
+        // Bob changes his mind
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::SetAutoDebit { circle_id: 1, enabled: false },
+        )
+        .unwrap();
+        let enrolled: crate::msg::AutoDebitMembersResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetAutoDebitMembers { circle_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(enrolled.members.len(), 1);
+        assert_eq!(enrolled.members[0].member, Addr::unchecked("alice"));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
 
-    let mut messages = Vec::new();
-    for (idx, member) in active_members.iter().enumerate() {
-        let mut amount = amount_per_member;
-        if idx == 0 {
-            amount = amount.checked_add(remainder).unwrap_or(amount);
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("keeper", &[]),
+            ExecuteMsg::AutoDebitContributions { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Stargate { type_url, value } => {
+                assert_eq!(type_url, crate::authz::MSG_EXEC_TYPE_URL);
+                let contract = cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+                // field 1 (grantee) = this contract
+                assert_eq!(value[0], 0x0a);
+                assert_eq!(value[1] as usize, contract.len());
+                assert_eq!(&value[2..2 + contract.len()], contract.as_bytes());
+                let expected_send = crate::authz::encode_msg_send(
+                    &Addr::unchecked("alice"),
+                    &Addr::unchecked(contract),
+                    &coins(100, "usaf"),
+                );
+                assert!(value
+                    .windows(expected_send.len())
+                    .any(|w| w == expected_send.as_slice()));
+                assert!(value
+                    .windows(crate::authz::MSG_SEND_TYPE_URL.len())
+                    .any(|w| w == crate::authz::MSG_SEND_TYPE_URL.as_bytes()));
+            }
+            other => panic!("unexpected message {:?}", other),
         }
-        messages.push(BankMsg::Send {
-            to_address: member.to_string(),
-            amount: vec![Coin {
-                denom: circle.denomination.clone(),
-                amount,
-            }],
-        });
+        auto_debit_reply(&mut deps, &res.messages[0], true);
+        let record = DEPOSITS
+            .load(&deps.storage, (1, Addr::unchecked("alice"), 1))
+            .unwrap();
+        assert!(record.on_time);
+
+        // Second run is a no-op: alice is already in for this round
+        let res = execute(
+            deps.as_mut(),
+            env_at(20),
+            mock_info("keeper", &[]),
+            ExecuteMsg::AutoDebitContributions { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+
+        // Past grace the window is closed
+        let err = execute(
+            deps.as_mut(),
+            env_at(61),
+            mock_info("keeper", &[]),
+            ExecuteMsg::AutoDebitContributions { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
     }
 
-    log_event(
-        &mut deps,
-        &env,
-        circle_id,
-        "blocked_funds_distributed",
-        &format!(
-            "Blocked funds {} distributed to {} active members",
-            total_blocked_funds,
-            active_members.len()
-        ),
-    )?;
-
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "distribute_blocked_funds")
-        .add_attribute("circle_id", circle_id.to_string())
-        .add_attribute("cycle", cycle.to_string())
-        .add_attribute("total_distributed", total_blocked_funds.to_string()))
-}
+    #[test]
+    fn per_hour_policy_charges_deposits_inside_grace_window() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.late_fee_policy = LateFeePolicy::PerHour { bps_per_hour: 500 };
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+
+        // At the deadline: no fee
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &coins(100, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+        )
+        .unwrap();
+        assert!(DEPOSITS.load(&deps.storage, (1, Addr::unchecked("bob"), 1)).unwrap().on_time);
+
+        // 10s past the deadline: one started hour at 5%
+        let req: crate::msg::DepositRequirementResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                env_at(10),
+                crate::msg::QueryMsg::GetDepositRequirement {
+                    circle_id: 1,
+                    member: Addr::unchecked("alice"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(req.late_fee_total, Uint128::new(5));
+        assert_eq!(req.required_amount, Uint128::new(105));
+
+        execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("alice", &coins(105, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+        )
+        .unwrap();
+        let record = DEPOSITS.load(&deps.storage, (1, Addr::unchecked("alice"), 1)).unwrap();
+        assert!(!record.on_time);
+        assert_eq!(record.amount, Uint128::new(100));
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.total_penalties_collected, Uint128::new(5));
+    }
 
-// ---------------------------------------------------------------------------
-// Refunds (liquid balance only)
-// ---------------------------------------------------------------------------
+    #[test]
+    fn late_fees_forgiven_by_vote_or_creator() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let alice = Addr::unchecked("alice");
+        MEMBER_ACCUMULATED_LATE_FEES
+            .save(&mut deps.storage, (1, alice.clone()), &Uint128::new(30))
+            .unwrap();
+        let forgive = |amount: u128| ExecuteMsg::ForgiveLateFees {
+            circle_id: 1,
+            member: Addr::unchecked("alice"),
+            amount: Uint128::new(amount),
+        };
 
-fn safe_refund_or_queue(
-    deps: DepsMut,
-    env: &Env,
-    _circle_id: u64,
-    member: &Addr,
-    amount: Uint128,
-    denom: &str,
-) -> Result<Vec<CosmosMsg>, ContractError> {
-    if amount.is_zero() {
-        return Ok(vec![]);
-    }
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), forgive(30)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
 
-    let balance = deps
-        .querier
-        .query_balance(&env.contract.address, denom)
-        .map_err(|e| ContractError::InvalidParameters {
-            msg: format!("Balance query failed: {}", e),
-        })?;
+        // Bob is the only other non-creator member, so his vote is a majority
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), forgive(10)).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "forgiven" && a.value == "10"));
+        assert_eq!(
+            MEMBER_ACCUMULATED_LATE_FEES.load(&deps.storage, (1, alice.clone())).unwrap(),
+            Uint128::new(20)
+        );
 
-    if balance.amount >= amount {
-        return Ok(vec![CosmosMsg::Bank(BankMsg::Send {
-            to_address: member.to_string(),
-            amount: vec![Coin {
-                denom: denom.to_string(),
-                amount,
-            }],
-        })]);
+        // The creator forgives directly; the amount is capped at what is owed
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), forgive(100)).unwrap();
+        assert!(MEMBER_ACCUMULATED_LATE_FEES
+            .may_load(&deps.storage, (1, alice))
+            .unwrap()
+            .is_none());
     }
 
-    Err(ContractError::InvalidParameters {
-        msg: "Insufficient liquid balance for refund".to_string(),
-    })
-}
+    #[test]
+    fn ejected_member_reinstated_after_paying_arrears() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let bob = Addr::unchecked("bob");
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        let max_missed_full = circle.max_missed_payments_allowed;
+        {
+            let mut dm = deps.as_mut();
+            eject_member_from_circle(&mut dm, &mock_env(), &mut circle, &bob).unwrap();
+        }
+        // One round passes without him
+        circle.current_cycle_index = 2;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        assert!(!circle.members_list.contains(&bob));
+        let penalties_before = circle.total_penalties_collected;
+
+        let err = execute(
+            deps.as_mut(),
+            env_at(400),
+            mock_info("bob", &coins(50, "usaf")),
+            ExecuteMsg::ReinstateMember { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientFunds {
+                required: "100".to_string(),
+                sent: "50".to_string()
+            }
+        );
+        // Overpaying is refused rather than silently kept
+        let err = execute(
+            deps.as_mut(),
+            env_at(400),
+            mock_info("bob", &coins(150, "usaf")),
+            ExecuteMsg::ReinstateMember { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+
+        execute(
+            deps.as_mut(),
+            env_at(400),
+            mock_info("bob", &coins(100, "usaf")),
+            ExecuteMsg::ReinstateMember { circle_id: 1 },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.members_list.contains(&bob));
+        assert!(circle.payout_order_list.unwrap().contains(&bob));
+        assert!(BLOCKED_MEMBERS.may_load(&deps.storage, (1, bob.clone())).unwrap().is_none());
+        assert_eq!(circle.total_penalties_collected, penalties_before + Uint128::new(100));
+        assert_eq!(circle.max_missed_payments_allowed, max_missed_full);
+
+        // Only ejected members can use this path
+        let err = execute(
+            deps.as_mut(),
+            env_at(400),
+            mock_info("alice", &coins(100, "usaf")),
+            ExecuteMsg::ReinstateMember { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+    }
 
-// ---------------------------------------------------------------------------
-// Internal Helpers
-// ---------------------------------------------------------------------------
+    #[test]
+    fn simulate_queries_match_exit_and_deposit_execution() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        MEMBER_ACCUMULATED_LATE_FEES
+            .save(&mut deps.storage, (1, alice.clone()), &Uint128::new(10))
+            .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("alice", &coins(30, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+        )
+        .unwrap();
+
+        let quote: crate::msg::ExitQuoteResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                env_at(20),
+                crate::msg::QueryMsg::SimulateExit {
+                    circle_id: 1,
+                    member: alice.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // lock 100 - (10 fees + 20 penalty) + 30 partial
+        assert_eq!(quote.penalty_kept, Uint128::new(30));
+        assert_eq!(quote.lock_refund, Uint128::new(70));
+        assert_eq!(quote.partial_refund, Uint128::new(30));
+        assert_eq!(quote.total_refund, Uint128::new(100));
+
+        let ejection: crate::msg::EjectionQuoteResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                env_at(20),
+                crate::msg::QueryMsg::SimulateEjection {
+                    circle_id: 1,
+                    member: alice.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(!ejection.eligible);
+        assert_eq!(ejection.lock_held, Uint128::new(70));
+        assert_eq!(ejection.partial_credited, Uint128::new(30));
+
+        let penalties_before = CIRCLES.load(&deps.storage, 1).unwrap().total_penalties_collected;
+        let res = execute(
+            deps.as_mut(),
+            env_at(20),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "refund_amount" && a.value == quote.total_refund.to_string()));
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.total_penalties_collected, penalties_before + quote.penalty_kept);
 
-fn generate_payout_order(circle: &mut Circle, env: &Env) {
-    if circle.payout_order_list.is_none() {
-        let members = circle.members_list.clone();
-        circle.payout_order_list = Some(match circle.payout_order_type {
-            PayoutOrderType::RandomOrder => {
-                let mut shuffled = members;
-                let seed = env.block.time.seconds() + circle.circle_id;
-                for i in 0..shuffled.len() {
-                    let j = (seed as usize + i * 7) % shuffled.len();
-                    shuffled.swap(i, j);
-                }
-                shuffled
-            }
-            PayoutOrderType::PredefinedOrder => members,
-        });
+        // Bob's lock was drained covering a round: the quote asks for the refill too
+        MEMBER_LOCKED_AMOUNTS
+            .save(&mut deps.storage, (1, bob.clone()), &Uint128::zero())
+            .unwrap();
+        let simulate = |deps: &MockDeps, rounds: Option<u32>| {
+            crate::contract::query(
+                deps.as_ref(),
+                env_at(20),
+                crate::msg::QueryMsg::SimulateDeposit {
+                    circle_id: 1,
+                    member: bob.clone(),
+                    rounds,
+                },
+            )
+        };
+        let dq: crate::msg::DepositQuoteResponse =
+            from_json(simulate(&deps, Some(2)).unwrap()).unwrap();
+        assert_eq!(dq.lock_refill, Uint128::new(100));
+        assert_eq!(dq.prepay_amount, Uint128::new(100));
+        assert_eq!(dq.required_amount, Uint128::new(300));
+        assert!(dq.on_time);
+        assert!(simulate(&deps, Some(0)).is_err());
+
+        execute(
+            deps.as_mut(),
+            env_at(20),
+            mock_info("bob", &coins(dq.required_amount.u128(), "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: Some(2) },
+        )
+        .unwrap();
+        assert_eq!(current_member_lock(&deps.storage, 1, &bob), Uint128::new(100));
+        // Current round paid: only a prepay quote is left, and plain deposits fail like execute
+        assert!(simulate(&deps, None).is_err());
     }
-}
 
-fn build_distribution_calendar(circle: &Circle, start_timestamp: Timestamp) -> String {
-    // Mirror `distribution_min_round_for_active` (and the matching helper in
-    // query.rs) so the emitted calendar matches the execute path. Diverging
-    // from execute previously broke the cron classifier — and showed phantom
-    // distribution rounds when `max_members` exceeded the actual roster
-    // (round_size was max_members, so round_in_cycle could legitimately reach
-    // it on cycle 2+ for a not-yet-full circle).
-    //
-    // Both the modulo (round_size) and the distribution gate must use the
-    // payout-order length — locked at start to the active roster size.
-    let round_size = circle.payout_order_list
-        .as_ref()
-        .map(|l| l.len() as u32)
-        .unwrap_or(circle.max_members)
-        .max(1);
-    let min_round_for_distribution: u32 = match circle.distribution_threshold {
-        None | Some(DistributionThreshold::Total {}) => round_size,
-        Some(DistributionThreshold::MinMembers { count }) => count,
-    };
+    #[test]
+    fn preview_create_circle_reports_every_error_and_projects_calendar() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+        // The preview takes the CreateCircle fields as-is.
+        let preview = |deps: &MockDeps, msg: ExecuteMsg| -> crate::msg::CreateCirclePreviewResponse {
+            let json = String::from_utf8(cosmwasm_std::to_json_vec(&msg).unwrap())
+                .unwrap()
+                .replacen("create_circle", "preview_create_circle", 1);
+            from_json(
+                crate::contract::query(deps.as_ref(), mock_env(), from_json(json.as_bytes()).unwrap())
+                    .unwrap(),
+            )
+            .unwrap()
+        };
 
-    let mut calendar_data = String::new();
-    if let Some(payout_order) = &circle.payout_order_list {
-        let mut round_number = 1u32;
-        for cycle in 1..=circle.total_cycles {
-            for recipient in payout_order.iter() {
-                let round_in_cycle = ((round_number - 1) % round_size) + 1;
-                let distribution_occurs = round_in_cycle >= min_round_for_distribution;
-                let round_offset_seconds = (round_number - 1) as u64 * circle.cycle_duration_secs();
-                let deposit_deadline = Timestamp::from_seconds(
-                    start_timestamp.seconds() + round_offset_seconds,
-                );
-                let distribution_date = Timestamp::from_seconds(
-                    start_timestamp.seconds() + round_offset_seconds + circle.cycle_duration_secs(),
-                );
-                if !calendar_data.is_empty() {
-                    calendar_data.push(',');
-                }
-                calendar_data.push_str(&format!(
-                    "{{round:{},cycle:{},deposit_deadline:{},distribution_date:{},distribution_occurs:{},recipient:\"{}\"}}",
-                    round_number,
-                    cycle,
-                    deposit_deadline.seconds(),
-                    distribution_date.seconds(),
-                    distribution_occurs,
-                    recipient
-                ));
-                round_number += 1;
-            }
+        // Public, zero late fee and grace == cycle: all three reported at once
+        let mut msg = base_create_msg();
+        if let ExecuteMsg::CreateCircle {
+            ref mut late_fee_percent,
+            ref mut grace_period_seconds,
+            ..
+        } = msg
+        {
+            *late_fee_percent = 0;
+            *grace_period_seconds = Some(300);
         }
+        let res = preview(&deps, msg);
+        assert_eq!(res.errors.len(), 3);
+        assert!(res.errors[0].contains("late_fee_percent"));
+        assert!(res.errors[1].contains("Public circles"));
+        assert!(res.errors[2].contains("grace_period"));
+        assert!(res.calendar.is_empty());
+
+        let res = preview(&deps, private_create_msg());
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.creator_lock_amount, Uint128::new(creator_lock(3, 100)));
+        assert_eq!(res.payout_amount, Uint128::new(300));
+        assert_eq!(res.denomination, "usaf");
+        assert_eq!(res.calendar.len(), 6);
+        let last = res.calendar.last().unwrap();
+        assert_eq!(last.cycle_number, 2);
+        assert_eq!(last.distribution_date, res.projected_start.plus_seconds(6 * 300));
+        assert!(last.distribution_occurs && !res.calendar[0].distribution_occurs);
+
+        // Matches what CreateCircle then stores
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(creator_lock(3, 100), "usaf")),
+            private_create_msg(),
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.max_missed_payments_allowed, res.max_missed_payments_allowed);
+        assert_eq!(circle.creator_lock_amount, res.creator_lock_amount);
     }
-    calendar_data
-}
 
-fn use_locked_amount_for_member(
-    storage: &mut dyn Storage,
-    circle_id: u64,
-    member: &Addr,
-    amount_needed: Uint128,
-) -> Result<Uint128, ContractError> {
-    if let Some(locked) = MEMBER_LOCKED_AMOUNTS.may_load(storage, (circle_id, member.clone()))? {
-        let used = if locked > amount_needed {
-            amount_needed
-        } else {
-            locked
+    #[test]
+    fn member_position_combines_lock_deposits_and_standing() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let position = |deps: &MockDeps, member: &str| -> crate::msg::MemberPositionResponse {
+            from_json(
+                crate::contract::query(
+                    deps.as_ref(),
+                    env_at(10),
+                    crate::msg::QueryMsg::GetMemberPosition {
+                        circle_id: 1,
+                        member: Addr::unchecked(member),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
         };
-        let remaining = locked
-            .checked_sub(used)
-            .map_err(|_| ContractError::InvalidParameters {
-                msg: "Locked amount underflow".to_string(),
-            })?;
-        if remaining.is_zero() {
-            MEMBER_LOCKED_AMOUNTS.remove(storage, (circle_id, member.clone()));
-        } else {
-            MEMBER_LOCKED_AMOUNTS.save(storage, (circle_id, member.clone()), &remaining)?;
-        }
-        return Ok(used);
+        let deposit = |deps: &mut MockDeps, amount: u128| {
+            execute(
+                deps.as_mut(),
+                env_at(10),
+                mock_info("alice", &coins(amount, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
+            .unwrap();
+        };
+
+        deposit(&mut deps, 40);
+        let pos = position(&deps, "alice");
+        assert_eq!(pos.original_lock, Uint128::new(100));
+        assert_eq!(pos.current_lock, Uint128::new(100));
+        assert_eq!(pos.partial_deposited, Uint128::new(40));
+        assert_eq!(pos.amount_due_now, Uint128::new(60));
+        assert_eq!(pos.rounds_owed, 1);
+        assert_eq!(pos.deposits_made, 0);
+        assert!(pos.is_active && pos.payout_slot.is_some());
+
+        deposit(&mut deps, 60);
+        let pos = position(&deps, "alice");
+        assert_eq!(pos.deposits_made, 1);
+        assert_eq!(pos.total_deposited, Uint128::new(100));
+        assert_eq!(pos.rounds_owed, 0);
+        assert_eq!(pos.amount_due_now, Uint128::zero());
+        assert_eq!(pos.net_position, cosmwasm_std::Int128::new(-100));
+
+        let creator = position(&deps, "creator");
+        assert_eq!(creator.original_lock, Uint128::new(creator_lock(3, 100)));
+        assert_eq!(creator.current_lock, creator.original_lock);
+
+        // Not in the circle: no lock at all
+        let outsider = position(&deps, "mallory");
+        assert!(outsider.original_lock.is_zero() && outsider.current_lock.is_zero());
     }
-    Ok(Uint128::zero())
-}
 
-/// Credit pending payout for a member and update circle aggregate.
-fn credit_pending_payout(
-    storage: &mut dyn Storage,
-    circle_id: u64,
-    member: &Addr,
-    amount: Uint128,
-    total_pending: &mut Uint128,
-) -> Result<(), ContractError> {
-    if amount.is_zero() {
-        return Ok(());
+    #[test]
+    fn member_history_tracks_deposits_misses_and_payouts_across_circles() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        for member in ["creator", "alice"] {
+            execute(
+                deps.as_mut(),
+                env_at(10),
+                mock_info(member, &coins(100, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
+            .unwrap();
+        }
+        // Bob misses round 1; it closes after the grace period
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_200, "usaf"),
+        );
+        execute(
+            deps.as_mut(),
+            env_at(361),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ProcessPayout { circle_id: 1 },
+        )
+        .unwrap();
+
+        let reputation = |deps: &MockDeps, member: &str| -> crate::msg::MemberReputationResponse {
+            from_json(
+                crate::contract::query(
+                    deps.as_ref(),
+                    mock_env(),
+                    crate::msg::QueryMsg::GetMemberReputation {
+                        member: Addr::unchecked(member),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let alice = reputation(&deps, "alice");
+        assert_eq!(alice.history.circles_joined, 1);
+        assert_eq!(alice.history.deposits_on_time, 1);
+        assert_eq!(alice.total_volume, Uint128::new(100));
+        assert_eq!(alice.on_time_ratio_bps, 10000);
+
+        let bob = reputation(&deps, "bob");
+        assert_eq!(bob.history.missed_rounds, 1);
+        assert_eq!(bob.on_time_ratio_bps, 0);
+        assert_eq!(bob.total_volume, Uint128::zero());
+
+        let paid: u32 = ["creator", "alice", "bob"]
+            .iter()
+            .map(|m| reputation(&deps, m).history.payouts_received)
+            .sum();
+        assert_eq!(paid, 1);
+
+        // Unknown addresses have a clean slate
+        let nobody = reputation(&deps, "nobody");
+        assert_eq!(nobody.history, crate::state::MemberHistory::default());
     }
-    let existing = PENDING_PAYOUTS
-        .may_load(storage, (circle_id, member.clone()))?
-        .unwrap_or(Uint128::zero());
-    let new_pending = existing
-        .checked_add(amount)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Pending payout overflow".to_string(),
-        })?;
-    PENDING_PAYOUTS.save(storage, (circle_id, member.clone()), &new_pending)?;
-    *total_pending = total_pending
-        .checked_add(amount)
-        .unwrap_or(*total_pending);
-    Ok(())
-}
 
-/// Debit pending payout for a member and update circle aggregate. Returns the amount debited.
-fn debit_pending_payout(
-    storage: &mut dyn Storage,
-    circle_id: u64,
-    member: &Addr,
-    total_pending: &mut Uint128,
-) -> Result<Uint128, ContractError> {
-    let pending = PENDING_PAYOUTS
-        .may_load(storage, (circle_id, member.clone()))?
-        .unwrap_or(Uint128::zero());
-    if pending.is_zero() {
-        return Ok(Uint128::zero());
+    #[test]
+    fn join_requirements_gate_on_history_and_attestation() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("group-code"),
+                max_uses: 10,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.join_requirements = Some(JoinRequirements {
+            min_circles_completed: 1,
+            no_ejection_within_seconds: Some(86_400),
+            attestation_contract: Some(Addr::unchecked("kyc")),
+            ..JoinRequirements::default()
+        });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        deps.querier.update_wasm(|query| match query {
+            cosmwasm_std::WasmQuery::Smart { msg, .. } => {
+                let AttestationQueryMsg::Attestation { address } = from_json(msg).unwrap();
+                let res = AttestationResponse {
+                    attested: address.as_str() != "bob",
+                };
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                    cosmwasm_std::to_json_binary(&res).unwrap(),
+                ))
+            }
+            _ => panic!("unexpected query"),
+        });
+
+        // No completed circle yet
+        let err = join_with_code(&mut deps, "alice", "group-code").unwrap_err();
+        assert!(matches!(err, ContractError::JoinRequirementsNotMet { circle_id: 1, .. }));
+
+        let veteran = MemberHistory {
+            circles_completed: 1,
+            ..MemberHistory::default()
+        };
+        for member in ["alice", "bob", "carol"] {
+            MEMBER_HISTORY
+                .save(&mut deps.storage, Addr::unchecked(member), &veteran)
+                .unwrap();
+        }
+        // Recently ejected elsewhere
+        MEMBER_HISTORY
+            .update(&mut deps.storage, Addr::unchecked("carol"), |h| -> StdResult<_> {
+                let mut h = h.unwrap();
+                h.ejections = 1;
+                h.last_ejected_at = Some(mock_env().block.time.minus_seconds(3_600));
+                Ok(h)
+            })
+            .unwrap();
+        let err = join_with_code(&mut deps, "carol", "group-code").unwrap_err();
+        assert!(matches!(err, ContractError::JoinRequirementsNotMet { .. }));
+
+        // History is fine but the attestation contract says no
+        let err = join_with_code(&mut deps, "bob", "group-code").unwrap_err();
+        assert!(matches!(err, ContractError::JoinRequirementsNotMet { .. }));
+
+        join_with_code(&mut deps, "alice", "group-code").unwrap();
+        assert!(CIRCLES
+            .load(&deps.storage, 1)
+            .unwrap()
+            .members_list
+            .contains(&Addr::unchecked("alice")));
     }
-    PENDING_PAYOUTS.remove(storage, (circle_id, member.clone()));
-    *total_pending = total_pending
-        .checked_sub(pending)
-        .unwrap_or(Uint128::zero());
-    Ok(pending)
-}
 
-/// Add locked amount for a member and update circle aggregate.
-fn add_member_locked(
-    storage: &mut dyn Storage,
-    circle_id: u64,
-    member: &Addr,
-    amount: Uint128,
-    total_locked: &mut Uint128,
-) -> Result<(), ContractError> {
-    if amount.is_zero() {
-        return Ok(());
+    #[test]
+    fn attestation_registry_gates_join_and_deposit() {
+        use crate::mock_registry::MockAttestationRegistry;
+
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+        let mut msg = private_create_msg();
+        if let ExecuteMsg::CreateCircle {
+            ref mut requires_attestation,
+            ..
+        } = msg
+        {
+            *requires_attestation = true;
+        }
+        let creator_funds = coins(creator_lock(3, 100), "usaf");
+
+        // No registry configured yet
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &creator_funds), msg.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+
+        let set_registry = |registry: Option<&str>| ExecuteMsg::SetAttestationRegistry {
+            registry: registry.map(Addr::unchecked),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), set_registry(Some("kyc")))
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        execute(deps.as_mut(), mock_env(), mock_info("platform", &[]), set_registry(Some("kyc")))
+            .unwrap();
+        MockAttestationRegistry::new(&["alice", "bob"]).install(&mut deps.querier, "kyc");
+
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &creator_funds), msg).unwrap();
+        assert!(CIRCLES.load(&deps.storage, 1).unwrap().requires_attestation);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("group-code"),
+                max_uses: 10,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+
+        let err = join_with_code(&mut deps, "carol", "group-code").unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::NotAttested { circle_id: 1, ref address } if address == "carol"
+        ));
+        join_with_code(&mut deps, "alice", "group-code").unwrap();
+        join_with_code(&mut deps, "bob", "group-code").unwrap();
+
+        // Attestation revoked after joining: deposits are refused too
+        MockAttestationRegistry::new(&["alice"]).install(&mut deps.querier, "kyc");
+        let deposit = |member: &str, deps: &mut MockDeps| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &coins(100, "usaf")),
+                ExecuteMsg::DepositContribution {
+                    circle_id: 1,
+                    rounds: None,
+                },
+            )
+        };
+        let err = deposit("bob", &mut deps).unwrap_err();
+        assert!(matches!(err, ContractError::NotAttested { .. }));
+
+        // Clearing the registry fails closed
+        execute(deps.as_mut(), mock_env(), mock_info("platform", &[]), set_registry(None)).unwrap();
+        let err = deposit("alice", &mut deps).unwrap_err();
+        assert!(matches!(err, ContractError::NotAttested { .. }));
     }
-    let existing = MEMBER_LOCKED_AMOUNTS
-        .may_load(storage, (circle_id, member.clone()))?
-        .unwrap_or(Uint128::zero());
-    let new_locked = existing
-        .checked_add(amount)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Locked amount overflow".to_string(),
-        })?;
-    MEMBER_LOCKED_AMOUNTS.save(storage, (circle_id, member.clone()), &new_locked)?;
-    *total_locked = total_locked
-        .checked_add(amount)
-        .unwrap_or(*total_locked);
-    Ok(())
-}
 
-/// Debit locked amount for a member and update circle aggregate.
-fn debit_member_locked(
-    storage: &mut dyn Storage,
-    circle_id: u64,
-    member: &Addr,
-    amount: Uint128,
-    total_locked: &mut Uint128,
-) -> Result<(), ContractError> {
-    if amount.is_zero() {
-        return Ok(());
+    #[test]
+    fn staked_locks_share_rewards_and_unbond_before_end() {
+        use cosmwasm_std::{FullDelegation, StakingMsg};
+
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::SetStakingConfig {
+                validators: vec!["val1".to_string()],
+                unbonding_period_secs: 600,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("platform", &[]),
+            ExecuteMsg::SetStakingConfig {
+                validators: vec!["val1".to_string()],
+                unbonding_period_secs: 600,
+            },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.stake_locks = true;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+
+        // Start delegates creator lock (200) + two member locks (100 each)
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(400, "usaf"),
+            })));
+        let stake = CIRCLES.load(&deps.storage, 1).unwrap().lock_stake.unwrap();
+        assert_eq!(stake.delegated, Uint128::new(400));
+
+        // 40 usaf of rewards split 2:1:1 by lock
+        let delegation = |rewards: u128| FullDelegation {
+            delegator: Addr::unchecked(cosmwasm_std::testing::MOCK_CONTRACT_ADDR),
+            validator: "val1".to_string(),
+            amount: Coin::new(400, "usaf"),
+            can_redelegate: Coin::new(400, "usaf"),
+            accumulated_rewards: coins(rewards, "usaf"),
+        };
+        deps.querier.update_staking("usaf", &[], &[delegation(40)]);
+        execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("anyone", &[]),
+            ExecuteMsg::SyncLockStake { circle_id: 1 },
+        )
+        .unwrap();
+        let pending = |deps: &MockDeps, member: &str| {
+            PENDING_PAYOUTS
+                .may_load(&deps.storage, (1, Addr::unchecked(member)))
+                .unwrap()
+                .unwrap_or_default()
+        };
+        assert_eq!(pending(&deps, "creator"), Uint128::new(20));
+        assert_eq!(pending(&deps, "alice"), Uint128::new(10));
+        assert_eq!(pending(&deps, "bob"), Uint128::new(10));
+        deps.querier.update_staking("usaf", &[], &[delegation(0)]);
+
+        // Within the unbonding period of end_date (start + 1800): everything unbonds
+        let res = execute(
+            deps.as_mut(),
+            env_at(1300),
+            mock_info("anyone", &[]),
+            ExecuteMsg::SyncLockStake { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(400, "usaf"),
+            })));
+        let stake = CIRCLES.load(&deps.storage, 1).unwrap().lock_stake.unwrap();
+        assert_eq!(stake.delegated, Uint128::zero());
+        assert_eq!(stake.unbonding, Uint128::new(400));
+
+        // Rewards are not backed by the unbonding locks: no need to wait
+        execute(
+            deps.as_mut(),
+            env_at(1400),
+            mock_info("creator", &[]),
+            ExecuteMsg::Withdraw { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(pending(&deps, "creator"), Uint128::zero());
     }
-    let existing = MEMBER_LOCKED_AMOUNTS
-        .may_load(storage, (circle_id, member.clone()))?
-        .unwrap_or(Uint128::zero());
-    let remaining = existing
-        .checked_sub(amount)
-        .map_err(|_| ContractError::InvalidParameters {
-            msg: "Locked amount underflow".to_string(),
-        })?;
-    if remaining.is_zero() {
-        MEMBER_LOCKED_AMOUNTS.remove(storage, (circle_id, member.clone()));
-    } else {
-        MEMBER_LOCKED_AMOUNTS.save(storage, (circle_id, member.clone()), &remaining)?;
+
+    #[test]
+    fn reserve_takes_premiums_and_covers_paid_member_ejection() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        circle.reserve = Some(CircleReserve {
+            premium_bps: 1000,
+            ..CircleReserve::default()
+        });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.payout_order_list = Some(vec![
+            Addr::unchecked("alice"),
+            Addr::unchecked("creator"),
+            Addr::unchecked("bob"),
+        ]);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(5_000, "usaf"),
+        );
+        let deposit = |deps: &mut MockDeps, member: &str, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info(member, &coins(100, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
+            .unwrap();
+        };
+        let payout = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("anyone", &[]),
+                ExecuteMsg::ProcessPayout { circle_id: 1 },
+            )
+            .unwrap();
+        };
+        let pending = |deps: &MockDeps, member: &str| {
+            PENDING_PAYOUTS
+                .may_load(&deps.storage, (1, Addr::unchecked(member)))
+                .unwrap()
+                .unwrap_or_default()
+        };
+
+        // Round 1: 10% of the 300 pot goes to the reserve, alice gets the rest (less 1% fee)
+        for member in ["creator", "alice", "bob"] {
+            deposit(&mut deps, member, 10);
+        }
+        payout(&mut deps, 300);
+        assert_eq!(pending(&deps, "alice"), Uint128::new(268));
+        let reserve = CIRCLES.load(&deps.storage, 1).unwrap().reserve.unwrap();
+        assert_eq!(reserve.balance, Uint128::new(30));
+
+        // Round 2: alice took the pot and defaults with a drained lock; the reserve
+        // pays what it can of her share instead of bob absorbing the loss.
+        MEMBER_LOCKED_AMOUNTS.remove(&mut deps.storage, (1, Addr::unchecked("alice")));
+        deposit(&mut deps, "creator", 310);
+        deposit(&mut deps, "bob", 310);
+        payout(&mut deps, 661);
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(!circle.members_list.contains(&Addr::unchecked("alice")));
+        // 200 - 20 premium + 30 covered = 210, less 1% fee
+        assert_eq!(pending(&deps, "bob"), Uint128::new(208));
+        let reserve = circle.reserve.unwrap();
+        assert_eq!(reserve.shortfalls_covered, Uint128::new(30));
+        assert_eq!(reserve.premiums_collected, Uint128::new(50));
+        assert_eq!(reserve.balance, Uint128::new(20));
     }
-    *total_locked = total_locked
-        .checked_sub(amount)
-        .unwrap_or(Uint128::zero());
-    Ok(())
-}
 
-fn log_event(
-    deps: &mut DepsMut,
-    env: &Env,
-    circle_id: u64,
-    event_type: &str,
-    data: &str,
-) -> StdResult<()> {
-    let event_id = EVENT_COUNTER
-        .may_load(deps.storage, circle_id)?
-        .unwrap_or(0)
-        .checked_add(1)
-        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Event ID overflow"))?;
+    #[test]
+    fn post_payout_collateral_withheld_used_on_miss_and_released_on_deposit() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        circle.post_payout_collateral = true;
+        circle.total_cycles = 1;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.payout_order_list = Some(vec![
+            Addr::unchecked("alice"),
+            Addr::unchecked("creator"),
+            Addr::unchecked("bob"),
+        ]);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(5_000, "usaf"),
+        );
+        let deposit = |deps: &mut MockDeps, member: &str, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info(member, &coins(100, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
+            .unwrap();
+        };
+        let payout = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("anyone", &[]),
+                ExecuteMsg::ProcessPayout { circle_id: 1 },
+            )
+            .unwrap();
+        };
+        let pending = |deps: &MockDeps, member: &str| {
+            PENDING_PAYOUTS
+                .may_load(&deps.storage, (1, Addr::unchecked(member)))
+                .unwrap()
+                .unwrap_or_default()
+        };
+        let collateral = |deps: &MockDeps, member: &str| {
+            PAYOUT_COLLATERAL
+                .may_load(&deps.storage, (1, Addr::unchecked(member)))
+                .unwrap()
+                .unwrap_or_default()
+        };
 
-    EVENTS.save(
-        deps.storage,
-        (circle_id, event_id),
-        &EventLog {
-            event_type: event_type.to_string(),
-            circle_id,
-            data: data.to_string(),
-            timestamp: env.block.time,
-        },
-    )?;
-    EVENT_COUNTER.save(deps.storage, circle_id, &event_id)?;
+        // Round 1: alice owes two more rounds, her lock covers one; the other is withheld.
+        for member in ["creator", "alice", "bob"] {
+            deposit(&mut deps, member, 10);
+        }
+        payout(&mut deps, 300);
+        assert_eq!(pending(&deps, "alice"), Uint128::new(197));
+        assert_eq!(collateral(&deps, "alice"), Uint128::new(100));
+
+        // Round 2: alice skips; her collateral pays the round and her lock is untouched.
+        deposit(&mut deps, "creator", 310);
+        deposit(&mut deps, "bob", 310);
+        payout(&mut deps, 661);
+        assert!(collateral(&deps, "alice").is_zero());
+        let covered = DEPOSITS
+            .load(&deps.storage, (1, Addr::unchecked("alice"), 2))
+            .unwrap();
+        assert_eq!(covered.amount, Uint128::new(100));
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(&deps.storage, (1, Addr::unchecked("alice")))
+                .unwrap(),
+            Uint128::new(100)
+        );
+        // The creator's lock is not drawn per round, so their last contribution is withheld.
+        assert_eq!(pending(&deps, "creator"), Uint128::new(197));
+        assert_eq!(collateral(&deps, "creator"), Uint128::new(100));
 
-    Ok(())
-}
+        // Round 3: the creator's deposit settles what they owed and releases the collateral.
+        deposit(&mut deps, "creator", 670);
+        assert_eq!(pending(&deps, "creator"), Uint128::new(297));
+        assert!(collateral(&deps, "creator").is_zero());
+        assert!(CIRCLES
+            .load(&deps.storage, 1)
+            .unwrap()
+            .total_payout_collateral
+            .is_zero());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_json};
-    use crate::msg::InstantiateMsg;
-    use crate::state::PlatformConfig;
+    #[test]
+    fn guarantor_posts_lock_share_absorbs_penalty_and_is_refunded_first() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("group-code"),
+                max_uses: 10,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("platform", &[]),
+            ExecuteMsg::SetGuarantorConfig { max_members_backed: 2 },
+        )
+        .unwrap();
+        let guarantee = |deps: &mut MockDeps, member: &str, amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &[]),
+                ExecuteMsg::NominateGuarantor {
+                    circle_id: 1,
+                    guarantor: Addr::unchecked("gary"),
+                },
+            )
+            .unwrap();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gary", &coins(amount, "usaf")),
+                ExecuteMsg::GuaranteeMember {
+                    circle_id: 1,
+                    member: Addr::unchecked(member),
+                },
+            )
+        };
+        guarantee(&mut deps, "carol", 60).unwrap();
+        guarantee(&mut deps, "dave", 50).unwrap();
+        assert!(matches!(
+            guarantee(&mut deps, "erin", 10),
+            Err(ContractError::GuarantorLimitReached { max: 2, .. })
+        ));
 
-    fn setup_platform_config(deps: &mut cosmwasm_std::OwnedDeps<cosmwasm_std::MemoryStorage, cosmwasm_std::testing::MockApi, cosmwasm_std::testing::MockQuerier>) {
-        PLATFORM_CONFIG
-            .save(
-                &mut deps.storage,
-                &PlatformConfig {
-                    platform_fee_percent: 100,
-                    platform_address: Addr::unchecked("platform"),
+        // Guaranteed members attach only the rest of the lock.
+        let join = |deps: &mut MockDeps, member: &str, amount: u128| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &coins(amount, "usaf")),
+                ExecuteMsg::JoinCircle {
+                    circle_id: 1,
+                    invite_code: Some("group-code".to_string()),
                 },
             )
+        };
+        assert!(matches!(
+            join(&mut deps, "carol", 30),
+            Err(ContractError::InsufficientFunds { .. })
+        ));
+        join(&mut deps, "carol", 40).unwrap();
+        join(&mut deps, "dave", 50).unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_000, "usaf"),
+        );
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(&deps.storage, (1, Addr::unchecked("carol")))
+                .unwrap(),
+            Uint128::new(100)
+        );
+
+        // Carol leaves before start: gary's 60 comes back first, carol gets the rest.
+        let quote =
+            quote_exit(&deps.storage, &CIRCLES.load(&deps.storage, 1).unwrap(), &Addr::unchecked("carol"))
+                .unwrap();
+        assert_eq!(quote.guarantor_refund, Uint128::new(60));
+        assert_eq!(quote.total_refund, Uint128::new(40));
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("carol", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "carol".to_string(),
+                amount: coins(40, "usaf"),
+            })
+        );
+        assert_eq!(
+            PENDING_PAYOUTS.load(&deps.storage, (1, Addr::unchecked("gary"))).unwrap(),
+            Uint128::new(60)
+        );
+
+        // Dave is ejected: the 10 late fees + 20% exit penalty come out of gary's stake.
+        MEMBER_ACCUMULATED_LATE_FEES
+            .save(&mut deps.storage, (1, Addr::unchecked("dave")), &Uint128::new(10))
             .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        eject_member_from_circle(&mut deps.as_mut(), &mock_env(), &mut circle, &Addr::unchecked("dave"))
+            .unwrap();
+        let exposure = crate::query::query_guarantor_exposure(
+            deps.as_ref(),
+            mock_env(),
+            Addr::unchecked("gary"),
+        )
+        .unwrap();
+        assert_eq!(exposure.members_backed, 1);
+        assert_eq!(exposure.total_posted, Uint128::new(50));
+        assert_eq!(exposure.total_stake, Uint128::new(20));
+    }
+
+    #[test]
+    fn renew_circle_links_seasons_and_rolls_pending_into_locks() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(deps.as_ref().storage, 1).unwrap();
+        circle.circle_status = CircleStatus::Finalizing;
+        circle.total_pending_payouts = Uint128::new(300);
+        CIRCLES.save(deps.as_mut().storage, 1, &circle).unwrap();
+        for who in ["creator", "alice"] {
+            PENDING_PAYOUTS
+                .save(deps.as_mut().storage, (1, Addr::unchecked(who)), &Uint128::new(150))
+                .unwrap();
+        }
+
+        // Only the creator may renew.
+        let renew = ExecuteMsg::RenewCircle {
+            circle_id: 1,
+            overrides: CircleOverrides::default(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), renew.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // Creator lock is 200: 150 rolled from pending, 50 attached.
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(50, "usaf")),
+            renew.clone(),
+        )
+        .unwrap();
+        let previous = CIRCLES.load(deps.as_ref().storage, 1).unwrap();
+        let next = CIRCLES.load(deps.as_ref().storage, 2).unwrap();
+        assert_eq!(previous.successor_id, Some(2));
+        assert_eq!(next.predecessor_id, Some(1));
+        assert_eq!(next.contribution_amount, Uint128::new(100));
+        assert_eq!(
+            next.pending_members,
+            vec![Addr::unchecked("alice"), Addr::unchecked("bob")]
+        );
+        assert_eq!(previous.total_pending_payouts, Uint128::new(150));
+        assert!(PENDING_PAYOUTS
+            .may_load(deps.as_ref().storage, (1, Addr::unchecked("creator")))
+            .unwrap()
+            .is_none());
+
+        // A second renewal of the same season is rejected.
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), renew).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+
+        // Alice joins with no funds: her join lock comes out of her payout.
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::JoinRenewal { circle_id: 1 },
+        )
+        .unwrap();
+        let next = CIRCLES.load(deps.as_ref().storage, 2).unwrap();
+        assert!(next.members_list.contains(&Addr::unchecked("alice")));
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(deps.as_ref().storage, (2, Addr::unchecked("alice")))
+                .unwrap(),
+            Uint128::new(100)
+        );
+        assert_eq!(
+            PENDING_PAYOUTS
+                .load(deps.as_ref().storage, (1, Addr::unchecked("alice")))
+                .unwrap(),
+            Uint128::new(50)
+        );
+
+        // Bob has nothing to roll and must attach his lock.
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::JoinRenewal { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds { .. }));
     }
 
-    fn base_create_msg() -> ExecuteMsg {
-        ExecuteMsg::CreateCircle {
-            circle_name: "test".to_string(),
-            circle_description: "test".to_string(),
+    fn template_params() -> CircleTemplateParams {
+        CircleTemplateParams {
+            circle_name: "monthly".to_string(),
+            circle_description: "template".to_string(),
             circle_image: None,
             max_members: 3,
             min_members_required: 2,
-            invite_only: false,
+            invite_only: true,
             contribution_amount: Uint128::from(100u128),
             denomination: None,
             exit_penalty_percent: 2000,
             late_fee_percent: 1000,
             total_cycles: 2,
             cycle_duration_days: 0,
-            cycle_duration_seconds: Some(300), // 5 min
-            start_date: None,
+            cycle_duration_seconds: Some(300),
             grace_period_hours: 0,
-            grace_period_seconds: Some(60), // 1 min
+            grace_period_seconds: Some(60),
             auto_start_when_full: false,
             auto_start_type: None,
-            auto_start_date: None,
             payout_order_type: PayoutOrderType::RandomOrder,
-            payout_order_list: None,
             auto_payout_enabled: true,
             manual_trigger_enabled: false,
             emergency_stop_enabled: false,
             auto_refund_if_min_not_met: false,
             strict_mode: false,
-            visibility: Visibility::Public,
+            visibility: Visibility::Private,
             show_member_identities: true,
             distribution_threshold: None,
+            creator_collateral_policy: None,
+            late_fee_policy: None,
+            join_requirements: None,
+            requires_attestation: false,
+            stake_locks: false,
+            reserve_premium_bps: None,
+            post_payout_collateral: false,
+            kind: CircleKind::Rotating,
         }
     }
 
-    // creator_lock = contribution * 2 (see compute_creator_lock)
-    fn creator_lock(_max_members: u32, contribution: u128) -> u128 {
-        contribution * 2
+    #[test]
+    fn circle_created_from_template_with_overrides() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+        let save = |params: CircleTemplateParams| ExecuteMsg::SaveTemplate {
+            template_id: None,
+            template_name: "Monthly 3".to_string(),
+            params,
+        };
+
+        // Templates are validated like CreateCircle.
+        let mut bad = template_params();
+        bad.min_members_required = 5;
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), save(bad)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("platform", &[]), save(template_params()))
+            .unwrap();
+        assert_eq!(res.attributes[0], ("action", "save_template"));
+        assert!(!res.attributes.iter().any(|a| a.key == "method"));
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), save(template_params())).unwrap();
+
+        // Only the owner updates a template.
+        let update = ExecuteMsg::SaveTemplate {
+            template_id: Some(1),
+            template_name: "Renamed".to_string(),
+            params: template_params(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), update).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let platform_templates: crate::msg::TemplatesResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetTemplates {
+                    owner: Some(Addr::unchecked("platform")),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(platform_templates.templates.len(), 1);
+        assert_eq!(platform_templates.templates[0].template_id, 1);
+
+        // Creator lock for the overridden contribution (2x 50).
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &coins(creator_lock(3, 50), "usaf")),
+            ExecuteMsg::CreateCircleFromTemplate {
+                template_id: 1,
+                overrides: CircleOverrides {
+                    circle_name: Some("Bob's circle".to_string()),
+                    contribution_amount: Some(Uint128::new(50)),
+                    ..Default::default()
+                },
+            },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(circle.creator_address, Addr::unchecked("bob"));
+        assert_eq!(circle.circle_name, "Bob's circle");
+        assert_eq!(circle.circle_description, "template");
+        assert_eq!(circle.contribution_amount, Uint128::new(50));
+        assert_eq!(circle.exit_penalty_percent, 2000);
+
+        // The platform may delete anyone's template.
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("platform", &[]),
+            ExecuteMsg::DeleteTemplate { template_id: 2 },
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0], ("action", "delete_template"));
+        assert!(TEMPLATES.may_load(deps.as_ref().storage, 2).unwrap().is_none());
     }
 
     #[test]
-    fn compute_max_missed_base_works() {
-        // 20% exit + 10% late fee → (10000 - 2000) / 1000 = 8 missed allowed
-        assert_eq!(compute_max_missed_base(2000, 1000), 8);
-        // late_fee_percent = 0 → u32::MAX (no late fee → no missed-count cap)
-        assert_eq!(compute_max_missed_base(2000, 0), u32::MAX);
+    fn accumulating_circle_lends_from_pool_and_shares_out_with_interest() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.kind = CircleKind::Accumulating {
+            interest_bps_per_round: 1000,
+            max_loan_bps: 20000,
+            loan_approval: LoanApproval::Creator,
+        };
+        circle.savings = Some(SavingsPool::default());
+        circle.total_cycles = 1;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(5_000, "usaf"),
+        );
+        let run_round = |deps: &mut MockDeps, start: u64| {
+            for member in ["creator", "alice", "bob"] {
+                execute(
+                    deps.as_mut(),
+                    env_at(start + 10),
+                    mock_info(member, &coins(100, "usaf")),
+                    ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+                )
+                .unwrap();
+            }
+            execute(
+                deps.as_mut(),
+                env_at(start + 301),
+                mock_info("anyone", &[]),
+                ExecuteMsg::ProcessPayout { circle_id: 1 },
+            )
+            .unwrap();
+        };
+        let pool = |deps: &MockDeps| CIRCLES.load(&deps.storage, 1).unwrap().savings.unwrap();
+
+        // Round 1: nobody takes the pot; 300 less the 1% fee goes to the pool.
+        run_round(&mut deps, 0);
+        assert_eq!(pool(&deps).balance, Uint128::new(297));
+        assert!(PAYOUTS.prefix((1, 1)).range(&deps.storage, None, None, Order::Ascending).next().is_none());
+
+        // Alice may borrow up to 2x her savings of 100; only the creator approves.
+        let request = |deps: &mut MockDeps, amount: u128| {
+            execute(
+                deps.as_mut(),
+                env_at(320),
+                mock_info("alice", &[]),
+                ExecuteMsg::RequestLoan { circle_id: 1, amount: Uint128::new(amount) },
+            )
+        };
+        assert!(request(&mut deps, 201).is_err());
+        let res = request(&mut deps, 150).unwrap();
+        assert_eq!(res.attributes[0], ("action", "request_loan"));
+        let approve = ExecuteMsg::ApproveLoan {
+            circle_id: 1,
+            borrower: Addr::unchecked("alice"),
+        };
+        let err = execute(deps.as_mut(), env_at(330), mock_info("bob", &[]), approve.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        let res = execute(deps.as_mut(), env_at(330), mock_info("creator", &[]), approve).unwrap();
+        assert_eq!(res.attributes[0], ("action", "approve_loan"));
+        assert_eq!(bank_send_to(&res, "alice"), Some(Uint128::new(150)));
+        assert_eq!(pool(&deps).balance, Uint128::new(147));
+
+        // A borrower cannot leave with the loan outstanding.
+        let err = execute(
+            deps.as_mut(),
+            env_at(340),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+
+        // Round 2 closes: one round of 10% interest (15) accrues; 65 pays it and 50 principal.
+        run_round(&mut deps, 300);
+        let res = execute(
+            deps.as_mut(),
+            env_at(620),
+            mock_info("alice", &coins(65, "usaf")),
+            ExecuteMsg::RepayLoan { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0], ("action", "repay_loan"));
+        let loan = LOANS.load(&deps.storage, (1, Addr::unchecked("alice"))).unwrap();
+        assert_eq!(loan.principal, Uint128::new(100));
+        assert!(loan.interest_due.is_zero());
+        assert_eq!(pool(&deps).interest_earned, Uint128::new(15));
+
+        // Round 3 is the last: the pool (806 cash + 100 owed) is shared by savings
+        // (302 each) and alice's remaining principal comes out of her share.
+        run_round(&mut deps, 600);
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.circle_status, CircleStatus::Finalizing);
+        assert!(circle.savings.unwrap().balance.is_zero());
+        assert!(LOANS.may_load(&deps.storage, (1, Addr::unchecked("alice"))).unwrap().is_none());
+        let pending = |member: &str| {
+            PENDING_PAYOUTS
+                .load(&deps.storage, (1, Addr::unchecked(member)))
+                .unwrap()
+        };
+        // Share plus the returned join lock of 100.
+        assert_eq!(pending("alice"), Uint128::new(302));
+        assert_eq!(pending("bob"), Uint128::new(402));
     }
 
     #[test]
-    fn cap_max_missed_by_rounds_works() {
-        // total_rounds = 6, base = 8 → cap to 5 (so ejection happens before last round)
-        assert_eq!(cap_max_missed_by_rounds(8, 6), 5);
-        // total_rounds = 1 → cap = 1 (max(1, 0))
-        assert_eq!(cap_max_missed_by_rounds(8, 1), 1);
-        // base smaller than cap → unchanged
-        assert_eq!(cap_max_missed_by_rounds(2, 6), 2);
+    fn expired_reservations_are_pruned_and_reopen_the_circle() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("group-code"),
+                max_uses: 10,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+        add_private_member(&mut deps, "alice").unwrap();
+        add_private_member(&mut deps, "bob").unwrap();
+        assert_eq!(
+            CIRCLES.load(&deps.storage, 1).unwrap().circle_status,
+            CircleStatus::Full
+        );
+
+        // Both seats are held: a code holder is turned away.
+        let err = join_with_code(&mut deps, "carol", "group-code").unwrap_err();
+        assert!(matches!(err, ContractError::InvalidCircleStatus { .. }));
+
+        // Once the window passes the reservations no longer hold the seats.
+        let mut late = mock_env();
+        late.block.time = late.block.time.plus_seconds(600);
+        execute(
+            deps.as_mut(),
+            late,
+            mock_info("carol", &coins(100, "usaf")),
+            ExecuteMsg::JoinCircle {
+                circle_id: 1,
+                invite_code: Some("group-code".to_string()),
+            },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.members_list.contains(&Addr::unchecked("carol")));
+        assert_eq!(circle.circle_status, CircleStatus::Open);
+        assert!(circle.pending_members.is_empty());
+        assert!(!PRIVATE_RESERVATIONS.has(&deps.storage, (1, Addr::unchecked("alice"))));
+        assert!(!PRIVATE_RESERVATIONS.has(&deps.storage, (1, Addr::unchecked("bob"))));
     }
 
     #[test]
-    fn compute_max_missed_scaled_shrinks_when_members_drop() {
-        // 5 active of 5 start → base
-        assert_eq!(compute_max_missed_scaled(2000, 1000, Some(5), 5), 8);
-        // 3 active of 5 start → base * 3/5 = 4
-        assert_eq!(compute_max_missed_scaled(2000, 1000, Some(5), 3), 4);
-        // never below 1
-        assert_eq!(compute_max_missed_scaled(2000, 1000, Some(100), 1), 1);
-        // None members_at_start → falls back to base
-        assert_eq!(compute_max_missed_scaled(2000, 1000, None, 3), 8);
+    fn direct_join_leaves_waitlist_and_is_not_promoted_twice() {
+        let mut deps = mock_dependencies();
+        create_private_circle(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::RegisterInviteCode {
+                circle_id: 1,
+                code_hash: hash_invite_code("group-code"),
+                max_uses: 10,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+        join_with_code(&mut deps, "alice", "group-code").unwrap();
+        // Bob's reservation holds the last seat, so Carol can only queue
+        add_private_member(&mut deps, "bob").unwrap();
+        join_waitlist(&mut deps, "carol", 100).unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_000, "usaf"),
+        );
+
+        // Bob's reservation lapses and Carol joins directly: her pre-lock goes back
+        let res = execute(
+            deps.as_mut(),
+            env_at(601),
+            mock_info("carol", &coins(100, "usaf")),
+            ExecuteMsg::JoinCircle {
+                circle_id: 1,
+                invite_code: Some("group-code".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(bank_send_to(&res, "carol"), Some(Uint128::new(100)));
+        assert!(WAITLIST.may_load(&deps.storage, 1).unwrap().is_none());
+
+        // A freed seat no longer re-admits Carol off a stale entry
+        execute(
+            deps.as_mut(),
+            env_at(602),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(
+            circle.members_list,
+            vec![Addr::unchecked("creator"), Addr::unchecked("carol")]
+        );
+        assert_eq!(
+            MEMBER_LOCKED_AMOUNTS
+                .load(&deps.storage, (1, Addr::unchecked("carol")))
+                .unwrap(),
+            Uint128::new(100)
+        );
     }
 
     #[test]
-    fn create_circle_rejects_grace_ge_cycle() {
+    fn waitlist_promotion_into_last_seat_auto_starts() {
         let mut deps = mock_dependencies();
-        setup_platform_config(&mut deps);
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.auto_start_when_full = true;
+        circle.auto_start_type = Some("by_members".to_string());
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        join_waitlist(&mut deps, "carol", 100).unwrap();
+        join_waitlist(&mut deps, "dave", 100).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
 
-        // grace == cycle → reject
-        let msg = ExecuteMsg::CreateCircle {
-            circle_name: "test".to_string(),
-            circle_description: "test".to_string(),
-            circle_image: None,
-            max_members: 3,
-            min_members_required: 2,
-            invite_only: false,
-            contribution_amount: Uint128::from(100u128),
-            denomination: None,
-            exit_penalty_percent: 2000,
-            late_fee_percent: 1000,
-            total_cycles: 2,
-            cycle_duration_days: 0,
-            cycle_duration_seconds: Some(300),
-            start_date: None,
-            grace_period_hours: 0,
-            grace_period_seconds: Some(300), // == cycle
-            auto_start_when_full: false,
-            auto_start_type: None,
-            auto_start_date: None,
-            payout_order_type: PayoutOrderType::RandomOrder,
-            payout_order_list: None,
-            auto_payout_enabled: true,
-            manual_trigger_enabled: false,
-            emergency_stop_enabled: false,
-            auto_refund_if_min_not_met: false,
-            strict_mode: false,
-            visibility: Visibility::Public,
-            show_member_identities: true,
-            distribution_threshold: None,
-        };
-        let info = mock_info("creator", &coins(creator_lock(3, 100), "usaf"));
-        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-        match err {
-            ContractError::InvalidParameters { msg } => {
-                assert!(msg.contains("grace_period"), "expected grace_period error, got: {}", msg);
-                assert!(msg.contains("strictly less than"), "got: {}", msg);
-            }
-            other => panic!("expected InvalidParameters, got {:?}", other),
-        }
+        // Carol fills the circle exactly as a direct join would: it starts and
+        // Dave's pre-lock is refunded.
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(matches!(circle.circle_status, CircleStatus::Running));
+        assert!(circle.members_list.contains(&Addr::unchecked("carol")));
+        assert_eq!(bank_send_to(&res, "dave"), Some(Uint128::new(100)));
+        assert!(WAITLIST.may_load(&deps.storage, 1).unwrap().is_none());
     }
 
     #[test]
-    fn create_circle_accepts_grace_lt_cycle() {
+    fn creator_exit_before_start_reports_member_and_creator_lock() {
         let mut deps = mock_dependencies();
-        setup_platform_config(&mut deps);
-        let info = mock_info("creator", &coins(creator_lock(3, 100), "usaf"));
-        let res = execute(deps.as_mut(), mock_env(), info, base_create_msg());
-        assert!(res.is_ok(), "expected success, got {:?}", res.err());
+        fill_private_circle_via_code(&mut deps);
+        // A creator who also holds a member lock gets both back
+        MEMBER_LOCKED_AMOUNTS
+            .save(&mut deps.storage, (1, Addr::unchecked("creator")), &Uint128::new(100))
+            .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.total_amount_locked += Uint128::new(100);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let refund = res
+            .attributes
+            .iter()
+            .find(|a| a.key == "refund_amount")
+            .unwrap();
+        assert_eq!(refund.value, "300");
     }
 
     #[test]
-    fn auto_start_when_full_sets_members_at_start() {
+    fn wind_down_scales_locks_when_funds_fall_short() {
         let mut deps = mock_dependencies();
-        setup_platform_config(&mut deps);
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
 
-        // Create with auto_start_when_full + by_members, max=2 so 1 join = full
-        let create_msg = ExecuteMsg::CreateCircle {
-            circle_name: "auto".to_string(),
-            circle_description: "auto".to_string(),
-            circle_image: None,
-            max_members: 2,
-            min_members_required: 2,
-            invite_only: false,
-            contribution_amount: Uint128::from(100u128),
-            denomination: None,
-            exit_penalty_percent: 2000,
-            late_fee_percent: 1000,
-            total_cycles: 2,
-            cycle_duration_days: 0,
-            cycle_duration_seconds: Some(300),
-            start_date: None,
-            grace_period_hours: 0,
-            grace_period_seconds: Some(60),
-            auto_start_when_full: true,
-            auto_start_type: Some("by_members".to_string()),
-            auto_start_date: None,
-            payout_order_type: PayoutOrderType::RandomOrder,
-            payout_order_list: None,
-            auto_payout_enabled: true,
-            manual_trigger_enabled: false,
-            emergency_stop_enabled: false,
-            auto_refund_if_min_not_met: false,
-            strict_mode: false,
-            visibility: Visibility::Public,
-            show_member_identities: true,
-            distribution_threshold: None,
+        // Locks 200 + 100 + 100, but only half of it is spendable
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        let positions = compute_wind_down(&deps.storage, &circle, Uint128::new(200)).unwrap();
+        let settled: Vec<(String, Uint128)> = positions
+            .iter()
+            .map(|p| (p.member.to_string(), p.settlement))
+            .collect();
+        assert_eq!(
+            settled,
+            vec![
+                ("creator".to_string(), Uint128::new(100)),
+                ("alice".to_string(), Uint128::new(50)),
+                ("bob".to_string(), Uint128::new(50)),
+            ]
+        );
+    }
+
+    #[test]
+    fn prepay_only_deposit_rejects_overpayment() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(100, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+        )
+        .unwrap();
+
+        // Round 1 is paid: two more rounds cost 200, and the extra 50 is refused
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(250, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: Some(2) },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+        assert!(PREPAID_BALANCES
+            .may_load(&deps.storage, (1, Addr::unchecked("alice")))
+            .unwrap()
+            .is_none());
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &coins(200, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: Some(2) },
+        )
+        .unwrap();
+        assert_eq!(
+            PREPAID_BALANCES.load(&deps.storage, (1, Addr::unchecked("alice"))).unwrap(),
+            Uint128::new(200)
+        );
+    }
+
+    #[test]
+    fn completing_partial_deposit_rejects_overpayment() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let deposit = |deps: &mut MockDeps, amount: u128| {
+            execute(
+                deps.as_mut(),
+                env_at(10),
+                mock_info("alice", &coins(amount, "usaf")),
+                ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+            )
         };
-        let creator_info = mock_info("creator", &coins(creator_lock(2, 100), "usaf"));
-        execute(deps.as_mut(), mock_env(), creator_info, create_msg).unwrap();
 
-        // Second member joins → triggers auto-start
-        let join_msg = ExecuteMsg::JoinCircle { circle_id: 1 };
-        let join_info = mock_info("alice", &coins(100, "usaf"));
-        execute(deps.as_mut(), mock_env(), join_info, join_msg).unwrap();
+        deposit(&mut deps, 40).unwrap();
+        // 60 is still owed; 80 would leave 20 unaccounted for
+        let err = deposit(&mut deps, 80).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+        assert_eq!(
+            PARTIAL_DEPOSITS
+                .load(&deps.storage, (1, Addr::unchecked("alice"), 1))
+                .unwrap(),
+            Uint128::new(40)
+        );
 
+        deposit(&mut deps, 60).unwrap();
         let circle = CIRCLES.load(&deps.storage, 1).unwrap();
-        assert!(matches!(circle.circle_status, CircleStatus::Running));
-        assert_eq!(circle.current_cycle_index, 1);
-        // members_at_start MUST be set on auto-start (regression: was None before)
-        assert_eq!(circle.members_at_start, Some(2));
-        // max_missed_payments_allowed: base 8, scaled 8*2/2=8, capped to total_rounds-1=3
-        assert_eq!(circle.max_missed_payments_allowed, 3);
+        assert_eq!(circle.total_amount_locked, Uint128::new(500));
+        assert!(circle.total_partial_deposits.is_zero());
+    }
+
+    fn auto_debit_reply(deps: &mut MockDeps, pull: &SubMsg, succeeded: bool) -> Response {
+        let result = if succeeded {
+            SubMsgResult::Ok(cosmwasm_std::SubMsgResponse { events: vec![], data: None })
+        } else {
+            SubMsgResult::Err("authorization not found".to_string())
+        };
+        crate::contract::reply(deps.as_mut(), env_at(10), Reply { id: pull.id, result }).unwrap()
+    }
+
+    #[test]
+    fn failed_auto_debit_grant_only_skips_that_member() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        for member in ["alice", "bob"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &[]),
+                ExecuteMsg::SetAutoDebit { circle_id: 1, enabled: true },
+            )
+            .unwrap();
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        // Alice already sent part of the round herself
+        execute(
+            deps.as_mut(),
+            env_at(5),
+            mock_info("alice", &coins(40, "usaf")),
+            ExecuteMsg::DepositContribution { circle_id: 1, rounds: None },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("keeper", &[]),
+            ExecuteMsg::AutoDebitContributions { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert!(res.messages.iter().all(|m| m.reply_on == cosmwasm_std::ReplyOn::Always));
+
+        // Alice revoked her grant; Bob's pull still goes through
+        let failed = auto_debit_reply(&mut deps, &res.messages[0], false);
+        assert_eq!(failed.attributes[0].value, "auto_debit_failed");
+        auto_debit_reply(&mut deps, &res.messages[1], true);
+
+        assert!(DEPOSITS
+            .may_load(&deps.storage, (1, Addr::unchecked("alice"), 1))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            PARTIAL_DEPOSITS
+                .load(&deps.storage, (1, Addr::unchecked("alice"), 1))
+                .unwrap(),
+            Uint128::new(40)
+        );
+        assert!(DEPOSITS
+            .load(&deps.storage, (1, Addr::unchecked("bob"), 1))
+            .unwrap()
+            .on_time);
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.members_paid_this_cycle, vec![Addr::unchecked("bob")]);
+        assert_eq!(circle.total_partial_deposits, Uint128::new(40));
+        assert!(AUTO_DEBIT_PULLS.is_empty(&deps.storage));
+    }
+
+    #[test]
+    fn auto_debit_skips_members_without_attestation() {
+        use crate::mock_registry::MockAttestationRegistry;
+
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        for member in ["alice", "bob"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(member, &[]),
+                ExecuteMsg::SetAutoDebit { circle_id: 1, enabled: true },
+            )
+            .unwrap();
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("platform", &[]),
+            ExecuteMsg::SetAttestationRegistry { registry: Some(Addr::unchecked("kyc")) },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.requires_attestation = true;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        // Alice's attestation lapsed after she joined
+        MockAttestationRegistry::new(&["bob"]).install(&mut deps.querier, "kyc");
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("keeper", &[]),
+            ExecuteMsg::AutoDebitContributions { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let pull = AUTO_DEBIT_PULLS.load(&deps.storage, res.messages[0].id).unwrap();
+        assert_eq!(pull.member, Addr::unchecked("bob"));
+    }
+
+    #[test]
+    fn accumulated_late_fees_query_follows_late_fee_policy() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        // 10% late fee stepping up 10% per consecutive miss: 10, 20, 30, ...
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.late_fee_policy = LateFeePolicy::Escalating { step_bps: 1000 };
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        MEMBER_MISSED_PAYMENTS
+            .save(
+                &mut deps.storage,
+                (1, Addr::unchecked("alice")),
+                &MemberMissedPayments {
+                    member: Addr::unchecked("alice"),
+                    missed_count: 1,
+                    last_missed_cycle: Some(1),
+                    last_fee_round: Some(1),
+                },
+            )
+            .unwrap();
+
+        let fees: crate::msg::AccumulatedLateFeesResponse = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                crate::msg::QueryMsg::GetMemberAccumulatedLateFees {
+                    circle_id: 1,
+                    member: Addr::unchecked("alice"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // The second miss costs 20, not the flat 10; 10 + 20 + 30 plus the 20%
+        // exit penalty fits in the lock, a fourth miss would not.
+        assert_eq!(fees.late_fee_per_round, Uint128::new(20));
+        assert_eq!(fees.rounds_until_ejection, 2);
     }
 
     #[test]
-    fn should_eject_uses_missed_count_cap() {
+    fn per_hour_auto_debit_after_deadline_charges_grace_fee() {
         let mut deps = mock_dependencies();
-        let circle_id = 42u64;
-        let member = Addr::unchecked("alice");
-        let original_lock = Uint128::from(100u128);
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.late_fee_policy = LateFeePolicy::PerHour { bps_per_hour: 500 };
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::SetAutoDebit { circle_id: 1, enabled: true },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+
+        // 10s past the deadline: the pull carries one started hour at 5%
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("keeper", &[]),
+            ExecuteMsg::AutoDebitContributions { circle_id: 1 },
+        )
+        .unwrap();
+        let pull = AUTO_DEBIT_PULLS.load(&deps.storage, res.messages[0].id).unwrap();
+        assert_eq!(pull.amount, Uint128::new(105));
+        assert_eq!(pull.grace_fee, Uint128::new(5));
+        auto_debit_reply(&mut deps, &res.messages[0], true);
+
+        let record = DEPOSITS.load(&deps.storage, (1, Addr::unchecked("alice"), 1)).unwrap();
+        assert!(!record.on_time);
+        assert_eq!(record.amount, Uint128::new(100));
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.total_penalties_collected, Uint128::new(5));
+        assert_eq!(circle.members_late_this_cycle, vec![Addr::unchecked("alice")]);
+    }
 
-        // No missed payments yet → not ejected
-        assert!(!should_eject_member(
-            &deps.storage,
-            circle_id,
-            &member,
-            original_lock,
-            2000, // exit_penalty_percent 20%
-            3,    // max_missed_payments_allowed
-        ));
+    #[test]
+    fn waitlist_applies_join_requirements_on_entry_and_promotion() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        join_waitlist(&mut deps, "carol", 100).unwrap();
+        join_waitlist(&mut deps, "dave", 100).unwrap();
+
+        // Requirements tightened after they queued: nobody has completed a circle
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.join_requirements = Some(JoinRequirements {
+            min_circles_completed: 1,
+            ..JoinRequirements::default()
+        });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        let err = join_waitlist(&mut deps, "erin", 100).unwrap_err();
+        assert!(matches!(err, ContractError::JoinRequirementsNotMet { .. }));
 
-        // 2 missed payments, max is 3 → still not ejected via missed_count, and accumulated late fees too low
-        MEMBER_MISSED_PAYMENTS
+        // Dave has since completed one elsewhere; Carol is skipped and refunded
+        MEMBER_HISTORY
             .save(
                 &mut deps.storage,
-                (circle_id, member.clone()),
-                &MemberMissedPayments {
-                    member: member.clone(),
-                    missed_count: 2,
-                    last_missed_cycle: Some(2),
-                    last_fee_round: None,
+                Addr::unchecked("dave"),
+                &MemberHistory {
+                    circles_completed: 1,
+                    ..MemberHistory::default()
                 },
             )
             .unwrap();
-        assert!(!should_eject_member(
-            &deps.storage,
-            circle_id,
-            &member,
-            original_lock,
-            2000,
-            3,
-        ));
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(bank_send_to(&res, "carol"), Some(Uint128::new(100)));
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(!circle.members_list.contains(&Addr::unchecked("carol")));
+        assert!(circle.members_list.contains(&Addr::unchecked("dave")));
+        assert!(WAITLIST.may_load(&deps.storage, 1).unwrap().is_none());
+    }
+
+    fn stake_private_circle(deps: &mut MockDeps) {
+        fill_private_circle_via_code(deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("platform", &[]),
+            ExecuteMsg::SetStakingConfig {
+                validators: vec!["val1".to_string()],
+                unbonding_period_secs: 600,
+            },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.stake_locks = true;
+        circle.distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.payout_order_list = Some(vec![
+            Addr::unchecked("creator"),
+            Addr::unchecked("alice"),
+            Addr::unchecked("bob"),
+        ]);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+    }
 
-        // 3 missed payments → hard cap triggers ejection regardless of fees
-        MEMBER_MISSED_PAYMENTS
-            .save(
-                &mut deps.storage,
-                (circle_id, member.clone()),
-                &MemberMissedPayments {
-                    member: member.clone(),
-                    missed_count: 3,
-                    last_missed_cycle: Some(3),
-                    last_fee_round: None,
+    #[test]
+    fn staked_default_holds_only_lock_backed_share_until_unbonded() {
+        let mut deps = mock_dependencies();
+        stake_private_circle(&mut deps);
+        for member in ["creator", "alice"] {
+            execute(
+                deps.as_mut(),
+                env_at(10),
+                mock_info(member, &coins(100, "usaf")),
+                ExecuteMsg::DepositContribution {
+                    circle_id: 1,
+                    rounds: None,
                 },
             )
             .unwrap();
-        assert!(should_eject_member(
-            &deps.storage,
-            circle_id,
-            &member,
-            original_lock,
-            2000,
-            3,
-        ));
+        }
+        // Locks are all delegated: the pot (396 after fees) is only partly liquid
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(300, "usaf"),
+        );
+
+        // Bob's lock covers his round and is undelegated in the same payout
+        let res = execute(
+            deps.as_mut(),
+            env_at(301),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ProcessPayout { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(100, "usaf"),
+            })));
+        let stake = CIRCLES.load(&deps.storage, 1).unwrap().lock_stake.unwrap();
+        assert_eq!(stake.unbonding, Uint128::new(100));
+        assert_eq!(
+            stake.unbonding_matures_at,
+            Some(env_at(901).block.time)
+        );
+
+        // The liquid part is withdrawable now; the 96 backed by Bob's lock waits
+        let withdraw = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("creator", &[]),
+                ExecuteMsg::Withdraw { circle_id: 1 },
+            )
+        };
+        let res = withdraw(&mut deps, 400).unwrap();
+        assert_eq!(bank_send_to(&res, "creator"), Some(Uint128::new(300)));
+        let err = withdraw(&mut deps, 500).unwrap_err();
+        assert!(matches!(err, ContractError::LockStakeUnbonding { circle_id: 1, matures_at }
+            if matures_at == env_at(901).block.time.seconds()));
+        let res = withdraw(&mut deps, 901).unwrap();
+        assert_eq!(bank_send_to(&res, "creator"), Some(Uint128::new(96)));
+        assert!(LOCK_BACKED_PENDING
+            .may_load(&deps.storage, (1, Addr::unchecked("creator")))
+            .unwrap()
+            .is_none());
     }
 
     #[test]
-    fn should_eject_via_accumulated_fees_path_still_works() {
+    fn staked_final_round_waits_for_unbonded_locks() {
         let mut deps = mock_dependencies();
-        let circle_id = 1u64;
-        let member = Addr::unchecked("bob");
-        let original_lock = Uint128::from(100u128);
-        // exit penalty 20% = 20. accumulated 80 → 80 + 20 = 100 ≥ 100 → eject.
-        MEMBER_ACCUMULATED_LATE_FEES
-            .save(
-                &mut deps.storage,
-                (circle_id, member.clone()),
-                &Uint128::from(80u128),
+        stake_private_circle(&mut deps);
+
+        // Jump to the last round (6 of 6) with every deposit in
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.current_cycle_index = 6;
+        circle.next_payout_date = Some(env_at(1500).block.time);
+        for member in ["creator", "alice", "bob"] {
+            DEPOSITS
+                .save(
+                    &mut deps.storage,
+                    (1, Addr::unchecked(member), 6),
+                    &DepositRecord {
+                        member: Addr::unchecked(member),
+                        cycle: 6,
+                        amount: Uint128::new(100),
+                        timestamp: env_at(1510).block.time,
+                        on_time: true,
+                    },
+                )
+                .unwrap();
+        }
+        circle.total_amount_locked += Uint128::new(300);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(300, "usaf"),
+        );
+        let process = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("anyone", &[]),
+                ExecuteMsg::ProcessPayout { circle_id: 1 },
+            )
+        };
+
+        // First call only undelegates the locks
+        let res = process(&mut deps, 1801).unwrap();
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(400, "usaf"),
+            })));
+        assert!(res.attributes.iter().any(|a| a.key == "locks_unbonding_until"
+            && a.value == env_at(2401).block.time.seconds().to_string()));
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.circle_status, CircleStatus::Running);
+        assert!(PAYOUTS
+            .prefix((1, 6))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
+
+        let err = process(&mut deps, 2000).unwrap_err();
+        assert!(matches!(err, ContractError::LockStakeUnbonding { circle_id: 1, .. }));
+
+        // Locks are back in the bank: the round pays out and every lock is returned
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(700, "usaf"),
+        );
+        process(&mut deps, 2401).unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert_eq!(circle.circle_status, CircleStatus::Finalizing);
+        assert!(circle.lock_stake.unwrap().unbonding_matures_at.is_none());
+        assert!(PENDING_PAYOUTS
+            .load(&deps.storage, (1, Addr::unchecked("creator")))
+            .unwrap()
+            >= Uint128::new(200));
+    }
+
+    #[test]
+    fn rolling_over_the_last_pending_payout_completes_the_circle() {
+        let finalizing = |fees: u128| {
+            let mut deps = mock_dependencies();
+            fill_private_circle_via_code(&mut deps);
+            let mut circle = CIRCLES.load(deps.as_ref().storage, 1).unwrap();
+            circle.circle_status = CircleStatus::Finalizing;
+            circle.total_pending_payouts = Uint128::new(300);
+            circle.total_platform_fees_collected = Uint128::new(fees);
+            CIRCLES.save(deps.as_mut().storage, 1, &circle).unwrap();
+            PENDING_PAYOUTS
+                .save(deps.as_mut().storage, (1, Addr::unchecked("creator")), &Uint128::new(200))
+                .unwrap();
+            PENDING_PAYOUTS
+                .save(deps.as_mut().storage, (1, Addr::unchecked("alice")), &Uint128::new(100))
+                .unwrap();
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                ExecuteMsg::RenewCircle {
+                    circle_id: 1,
+                    overrides: CircleOverrides::default(),
+                },
             )
             .unwrap();
-        // missed_count below cap so we exercise only the accumulated branch
-        assert!(should_eject_member(
-            &deps.storage,
-            circle_id,
-            &member,
-            original_lock,
-            2000,
-            100, // very high cap → can't trigger via missed_count
-        ));
+            deps
+        };
+        let status = |deps: &MockDeps| CIRCLES.load(deps.as_ref().storage, 1).unwrap().circle_status;
+
+        // Alice's payout is still pending after the creator rolls theirs over
+        let mut deps = finalizing(0);
+        assert_eq!(status(&deps), CircleStatus::Finalizing);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::JoinRenewal { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(status(&deps), CircleStatus::Completed);
+
+        // Undrained platform fees keep the circle Finalizing
+        let mut deps = finalizing(5);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::JoinRenewal { circle_id: 1 },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(deps.as_ref().storage, 1).unwrap();
+        assert!(circle.total_pending_payouts.is_zero());
+        assert_eq!(circle.circle_status, CircleStatus::Finalizing);
     }
 
     #[test]
-    fn apply_running_state_sets_all_fields() {
-        let mut circle = Circle {
-            circle_id: 1,
-            circle_name: "x".to_string(),
-            circle_description: "x".to_string(),
-            circle_image: None,
-            creator_address: Addr::unchecked("creator"),
-            created_at: Timestamp::from_seconds(0),
-            updated_at: Timestamp::from_seconds(0),
-            max_members: 3,
-            min_members_required: 2,
-            invite_only: false,
-            members_list: vec![Addr::unchecked("a"), Addr::unchecked("b"), Addr::unchecked("c")],
-            pending_members: vec![],
-            contribution_amount: Uint128::from(100u128),
-            denomination: "usaf".to_string(),
-            payout_amount: Uint128::from(300u128),
-            exit_penalty_percent: 2000,
-            late_fee_percent: 1000,
-            platform_fee_percent: 100,
-            max_missed_payments_allowed: 0,
-            members_at_start: None,
-            total_cycles: 2,
-            cycle_duration_days: 0,
-            cycle_duration_seconds: 300,
-            start_date: None,
-            first_cycle_date: None,
-            next_payout_date: None,
-            end_date: None,
-            grace_period_hours: 0,
-            grace_period_seconds: 60,
-            auto_start_when_full: true,
-            auto_start_type: Some("by_members".to_string()),
-            auto_start_date: None,
-            payout_order_type: PayoutOrderType::RandomOrder,
-            payout_order_list: None,
-            auto_payout_enabled: true,
-            manual_trigger_enabled: false,
-            emergency_stop_enabled: false,
-            emergency_stop_triggered: false,
-            auto_refund_if_min_not_met: false,
-            strict_mode: false,
-            escrow_address: None,
-            total_amount_locked: Uint128::zero(),
-            total_penalties_collected: Uint128::zero(),
-            total_platform_fees_collected: Uint128::zero(),
-            total_pending_payouts: Uint128::zero(),
-            withdrawal_lock: false,
-            refund_mode: RefundMode::FullRefund,
-            creator_lock_amount: Uint128::from(130u128),
-            distribution_threshold: Some(DistributionThreshold::Total {}),
-            circle_status: CircleStatus::Full,
-            current_cycle_index: 0,
-            cycles_completed: 0,
-            members_paid_this_cycle: vec![],
-            members_late_this_cycle: vec![],
-            visibility: Visibility::Public,
-            show_member_identities: true,
+    fn accumulating_circle_rejects_threshold_and_advance_round() {
+        let mut deps = mock_dependencies();
+        setup_platform_config(&mut deps);
+        let mut msg = private_create_msg();
+        if let ExecuteMsg::CreateCircle {
+            ref mut kind,
+            ref mut distribution_threshold,
+            ..
+        } = msg
+        {
+            *kind = CircleKind::Accumulating {
+                interest_bps_per_round: 1000,
+                max_loan_bps: 20000,
+                loan_approval: LoanApproval::Creator,
+            };
+            *distribution_threshold = Some(DistributionThreshold::MinMembers { count: 1 });
+        }
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &coins(creator_lock(3, 100), "usaf")),
+            msg,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { ref msg }
+            if msg.contains("distribution_threshold does not apply")));
+
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.kind = CircleKind::Accumulating {
+            interest_bps_per_round: 1000,
+            max_loan_bps: 20000,
+            loan_approval: LoanApproval::Creator,
         };
+        circle.savings = Some(SavingsPool::default());
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env_at(400),
+            mock_info("creator", &[]),
+            ExecuteMsg::AdvanceRound { circle_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidParameters { .. }));
+        assert_eq!(CIRCLES.load(&deps.storage, 1).unwrap().current_cycle_index, 1);
+    }
 
-        let now = Timestamp::from_seconds(1_700_000_000);
-        apply_running_state(&mut circle, now);
+    #[test]
+    fn staked_exit_refund_waits_for_its_lock_to_unbond() {
+        let mut deps = mock_dependencies();
+        stake_private_circle(&mut deps);
+        // Everything liquid belongs to other circles: this circle's locks are delegated
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_000, "usaf"),
+        );
 
-        assert!(matches!(circle.circle_status, CircleStatus::Running));
-        assert_eq!(circle.current_cycle_index, 1);
-        assert_eq!(circle.start_date, Some(now));
-        assert_eq!(circle.first_cycle_date, Some(now));
-        assert_eq!(circle.next_payout_date, Some(now));
-        assert_eq!(circle.members_at_start, Some(3));
-        // total_rounds = 6, base 8, capped to 5
-        assert_eq!(circle.max_missed_payments_allowed, 5);
+        // Alice's lock (100 - 20 exit penalty) is undelegated on the way out
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("alice", &[]),
+            ExecuteMsg::ExitCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(100, "usaf"),
+            })));
         assert_eq!(
-            circle.end_date,
-            Some(Timestamp::from_seconds(now.seconds() + 300 * 6))
+            LOCK_BACKED_PENDING
+                .load(&deps.storage, (1, Addr::unchecked("alice")))
+                .unwrap(),
+            Uint128::new(80)
         );
-    }
 
-    // Round-progression invariant is exercised end-to-end by ProcessPayout / AdvanceRound;
-    // a focused unit test would require constructing a full Running circle with deposits.
-    // The check is cheap and mostly a defense-in-depth assertion against future refactors.
+        let withdraw = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("alice", &[]),
+                ExecuteMsg::Withdraw { circle_id: 1 },
+            )
+        };
+        let err = withdraw(&mut deps, 20).unwrap_err();
+        assert!(matches!(err, ContractError::LockStakeUnbonding { circle_id: 1, matures_at }
+            if matures_at == env_at(610).block.time.seconds()));
+        let res = withdraw(&mut deps, 610).unwrap();
+        assert_eq!(bank_send_to(&res, "alice"), Some(Uint128::new(80)));
+    }
 
     #[test]
-    fn json_responses_round_trip() {
-        // Sanity check that base_create_msg serializes/deserializes properly.
-        let msg = base_create_msg();
-        let json = cosmwasm_std::to_json_binary(&msg).unwrap();
-        let parsed: ExecuteMsg = from_json(&json).unwrap();
-        assert_eq!(parsed, msg);
+    fn staked_wind_down_returns_full_locks_once_unbonded() {
+        let mut deps = mock_dependencies();
+        stake_private_circle(&mut deps);
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.min_members_required = 4;
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        // All 400 of locks are delegated; nothing of this circle is liquid
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(0, "usaf"),
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("alice", &[]),
+            ExecuteMsg::WindDownCircle { circle_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(400, "usaf"),
+            })));
+        // Locks come back in full rather than scaled to the liquid balance
+        for (member, lock) in [("creator", 200u128), ("alice", 100), ("bob", 100)] {
+            let who = Addr::unchecked(member);
+            assert_eq!(PENDING_PAYOUTS.load(&deps.storage, (1, who.clone())).unwrap(), Uint128::new(lock));
+            assert_eq!(LOCK_BACKED_PENDING.load(&deps.storage, (1, who)).unwrap(), Uint128::new(lock));
+        }
+
+        // Other circles' liquid funds do not pay them out early
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_000, "usaf"),
+        );
+        let withdraw = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("bob", &[]),
+                ExecuteMsg::Withdraw { circle_id: 1 },
+            )
+        };
+        let err = withdraw(&mut deps, 20).unwrap_err();
+        assert!(matches!(err, ContractError::LockStakeUnbonding { circle_id: 1, .. }));
+        let res = withdraw(&mut deps, 610).unwrap();
+        assert_eq!(bank_send_to(&res, "bob"), Some(Uint128::new(100)));
     }
 
     #[test]
-    fn instantiate_smoke() {
+    fn staked_creatorship_refund_waits_for_undelegation() {
         let mut deps = mock_dependencies();
-        let msg = InstantiateMsg {
-            platform_fee_percent: 100,
-            platform_address: Addr::unchecked("platform"),
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("platform", &[]),
+            ExecuteMsg::SetStakingConfig {
+                validators: vec!["val1".to_string()],
+                unbonding_period_secs: 600,
+            },
+        )
+        .unwrap();
+        // Alice holds a full creator-sized lock, so she takes over without a top-up
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.stake_locks = true;
+        circle.total_amount_locked += Uint128::new(100);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        MEMBER_LOCKED_AMOUNTS
+            .save(&mut deps.storage, (1, Addr::unchecked("alice")), &Uint128::new(200))
+            .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        deps.querier.update_balance(
+            cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+            coins(1_000, "usaf"),
+        );
+        execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("creator", &[]),
+            ExecuteMsg::TransferCreatorship {
+                circle_id: 1,
+                new_creator: Addr::unchecked("alice"),
+            },
+        )
+        .unwrap();
+
+        // Locks drop from 500 to 400: the refunded 100 is undelegated, not sent
+        let res = execute(
+            deps.as_mut(),
+            env_at(10),
+            mock_info("alice", &[]),
+            ExecuteMsg::AcceptCreatorship { circle_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(bank_send_to(&res, "creator"), None);
+        assert!(res.messages.iter().any(|m| m.msg
+            == CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "val1".to_string(),
+                amount: Coin::new(100, "usaf"),
+            })));
+        let stake = CIRCLES.load(&deps.storage, 1).unwrap().lock_stake.unwrap();
+        assert_eq!(stake.delegated, Uint128::new(400));
+
+        let withdraw = |deps: &mut MockDeps, secs: u64| {
+            execute(
+                deps.as_mut(),
+                env_at(secs),
+                mock_info("creator", &[]),
+                ExecuteMsg::Withdraw { circle_id: 1 },
+            )
         };
-        let info = mock_info("creator", &[]);
-        let res = crate::contract::instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        let err = withdraw(&mut deps, 20).unwrap_err();
+        assert!(matches!(err, ContractError::LockStakeUnbonding { circle_id: 1, .. }));
+        let res = withdraw(&mut deps, 610).unwrap();
+        assert_eq!(bank_send_to(&res, "creator"), Some(Uint128::new(100)));
+    }
+
+    #[test]
+    fn advance_round_draws_paid_members_collateral_before_ejecting() {
+        let mut deps = mock_dependencies();
+        fill_private_circle_via_code(&mut deps);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::StartCircle { circle_id: 1 },
+        )
+        .unwrap();
+        let alice = Addr::unchecked("alice");
+
+        // First round of cycle 2 (Total threshold: no distribution yet). Alice took
+        // the cycle-1 pot, has 100 of it as collateral and only 50 of lock left.
+        let mut circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        circle.current_cycle_index = 4;
+        circle.total_payout_collateral = Uint128::new(100);
+        CIRCLES.save(&mut deps.storage, 1, &circle).unwrap();
+        PAYOUTS
+            .save(
+                &mut deps.storage,
+                (1, 3, alice.clone()),
+                &PayoutRecord {
+                    cycle: 3,
+                    recipient: alice.clone(),
+                    amount: Uint128::new(900),
+                    timestamp: env_at(0).block.time,
+                    transaction_hash: None,
+                },
+            )
+            .unwrap();
+        PAYOUT_COLLATERAL
+            .save(&mut deps.storage, (1, alice.clone()), &Uint128::new(100))
+            .unwrap();
+        MEMBER_LOCKED_AMOUNTS
+            .save(&mut deps.storage, (1, alice.clone()), &Uint128::new(50))
+            .unwrap();
+        for member in ["creator", "bob"] {
+            DEPOSITS
+                .save(
+                    &mut deps.storage,
+                    (1, Addr::unchecked(member), 4),
+                    &DepositRecord {
+                        member: Addr::unchecked(member),
+                        cycle: 4,
+                        amount: Uint128::new(100),
+                        timestamp: env_at(10).block.time,
+                        on_time: true,
+                    },
+                )
+                .unwrap();
+        }
+
+        execute(
+            deps.as_mut(),
+            env_at(400),
+            mock_info("anyone", &[]),
+            ExecuteMsg::AdvanceRound { circle_id: 1 },
+        )
+        .unwrap();
+        let circle = CIRCLES.load(&deps.storage, 1).unwrap();
+        assert!(circle.members_list.contains(&alice));
+        assert_eq!(circle.current_cycle_index, 5);
+        assert!(circle.total_payout_collateral.is_zero());
+        assert_eq!(payout_collateral_of(&deps.storage, 1, &alice), Uint128::zero());
+        assert_eq!(current_member_lock(&deps.storage, 1, &alice), Uint128::new(50));
+        assert_eq!(
+            DEPOSITS.load(&deps.storage, (1, alice, 4)).unwrap().amount,
+            Uint128::new(100)
+        );
     }
 }
@@ -1,6 +1,9 @@
+pub mod authz;
 pub mod contract;
 pub mod error;
 pub mod execute;
+#[cfg(test)]
+pub(crate) mod mock_registry;
 pub mod msg;
 pub mod query;
 pub mod state;
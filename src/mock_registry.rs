@@ -0,0 +1,52 @@
+//! Minimal attestation registry for tests: answers `AttestationQueryMsg` from a
+//! fixed allow-list, wired into `MockQuerier` in place of a deployed contract.
+
+use cosmwasm_std::testing::MockQuerier;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, ContractResult, QuerierResult, SystemError, SystemResult,
+    WasmQuery,
+};
+
+use crate::msg::{AttestationQueryMsg, AttestationResponse};
+
+#[derive(Clone, Debug, Default)]
+pub struct MockAttestationRegistry {
+    pub attested: Vec<Addr>,
+}
+
+impl MockAttestationRegistry {
+    pub fn new(attested: &[&str]) -> Self {
+        MockAttestationRegistry {
+            attested: attested.iter().map(|a| Addr::unchecked(*a)).collect(),
+        }
+    }
+
+    pub fn query(&self, msg: AttestationQueryMsg) -> AttestationResponse {
+        match msg {
+            AttestationQueryMsg::Attestation { address } => AttestationResponse {
+                attested: self.attested.contains(&address),
+            },
+        }
+    }
+
+    /// Serve smart queries to `contract_addr` from this registry; any other wasm
+    /// query is unsupported.
+    pub fn install(self, querier: &mut MockQuerier, contract_addr: &str) {
+        let registry_addr = contract_addr.to_string();
+        querier.update_wasm(move |query| -> QuerierResult {
+            match query {
+                WasmQuery::Smart { contract_addr, msg } if *contract_addr == registry_addr => {
+                    match from_json::<AttestationQueryMsg>(msg) {
+                        Ok(msg) => SystemResult::Ok(ContractResult::Ok(
+                            to_json_binary(&self.query(msg)).unwrap(),
+                        )),
+                        Err(e) => SystemResult::Ok(ContractResult::Err(e.to_string())),
+                    }
+                }
+                _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: "wasm query outside the attestation registry".to_string(),
+                }),
+            }
+        });
+    }
+}
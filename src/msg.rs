@@ -1,14 +1,20 @@
-use cosmwasm_std::{Addr, Uint128, Timestamp};
+use cosmwasm_std::{Addr, Int128, Uint128, Timestamp};
 use cosmwasm_schema::QueryResponses;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::{CircleStatus, DistributionThreshold, PayoutOrderType, Visibility};
+use crate::state::{
+    CircleKind, CircleStatus, CircleTemplate, Loan, SavingsPool, CircleTemplateParams, CollateralConfig, CollateralPolicy, CollateralPolicyKind, DistributionThreshold, JoinRequirements, StakingConfig,
+    LateFeePolicy,
+    PayoutOrderType, Visibility,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct InstantiateMsg {
     pub platform_fee_percent: u64, // Basis points
     pub platform_address: Addr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_registry: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -16,6 +22,7 @@ pub struct MigrateMsg {}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     // Circle Management
     CreateCircle {
@@ -68,10 +75,49 @@ pub enum ExecuteMsg {
         /// For Public circles this is forced to Total regardless of input.
         #[serde(skip_serializing_if = "Option::is_none")]
         distribution_threshold: Option<DistributionThreshold>,
+        /// How creator_lock_amount is derived; must be one of the platform-approved kinds.
+        /// When omitted, defaults to `FixedMultiple { multiple_bps: 20000 }` (2x contribution).
+        /// Use `QuoteCreatorLock` to get the exact amount to attach.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        creator_collateral_policy: Option<CollateralPolicy>,
+        /// How late fees grow; defaults to `FlatPerRound` (late_fee_percent per missed round).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        late_fee_policy: Option<LateFeePolicy>,
+        /// Reputation / attestation rules joiners must meet (see `JoinRequirements`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        join_requirements: Option<JoinRequirements>,
+        /// Regulated circle: JoinCircle / AcceptInvite / JoinWaitlist / DepositContribution
+        /// require the sender to be attested by the platform attestation registry.
+        #[serde(default)]
+        requires_attestation: bool,
+        /// usaf circles only: delegate the locks (never round contributions) to a
+        /// platform-approved validator while the circle runs. Rewards go to lock holders
+        /// via Withdraw; the locks are unbonded before `end_date`.
+        #[serde(default)]
+        stake_locks: bool,
+        /// Enable a circle reserve: penalties plus this many basis points of each round's
+        /// contributions cover rounds owed by ejected members who were already paid.
+        /// Leftover goes to members who never missed. None = no reserve.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reserve_premium_bps: Option<u64>,
+        /// Once a member has received the pot, withhold from it whatever their lock
+        /// does not cover of the contributions they still owe; released back one
+        /// contribution per deposit and used first if they miss a round.
+        #[serde(default)]
+        post_payout_collateral: bool,
+        /// `Accumulating` runs the circle as an ASCA: no rotating pot, members borrow
+        /// from the pooled contributions and the last round shares everything out.
+        /// Such circles take no `distribution_threshold`.
+        #[serde(default)]
+        kind: CircleKind,
     },
-    /// Join a circle — must attach exactly contribution_amount in usaf as join deposit (locked as security)
+    /// Join a circle — must attach exactly contribution_amount in usaf as join deposit (locked as security).
+    /// For invite-only / private circles, callers not in `pending_members` may pass the plaintext
+    /// `invite_code` whose SHA-256 matches a code registered via `RegisterInviteCode`.
     JoinCircle {
         circle_id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        invite_code: Option<String>,
     },
     /// Accept an invite — must attach exactly contribution_amount in usaf as join deposit
     AcceptInvite {
@@ -81,6 +127,34 @@ pub enum ExecuteMsg {
         circle_id: u64,
         member_address: Addr,
     },
+    /// Queue for a seat in a circle with no free seats (pre-start). Optionally attach exactly
+    /// `contribution_amount` to pre-lock the join deposit: a pre-locked entrant is admitted
+    /// directly when a seat frees up; otherwise they get a seat reservation to accept with funds.
+    /// Pre-locked funds are refunded if the circle starts or is cancelled without them. The
+    /// circle's join requirements apply on entry and again when a seat frees up.
+    JoinWaitlist {
+        circle_id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        invite_code: Option<String>,
+    },
+    /// Leave the waitlist; any pre-locked deposit is refunded.
+    LeaveWaitlist {
+        circle_id: u64,
+    },
+    /// Creator-only. Register a shareable invite code by its SHA-256 digest (64 hex chars).
+    /// The plaintext code is never stored on-chain; each redemption via `JoinCircle` consumes one use.
+    RegisterInviteCode {
+        circle_id: u64,
+        code_hash: String,
+        max_uses: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expires_at: Option<Timestamp>,
+    },
+    /// Creator-only. Remove a registered invite code before it is exhausted or expires.
+    RevokeInviteCode {
+        circle_id: u64,
+        code_hash: String,
+    },
     /// Exit circle. Before start: full refund. After start: only if strict_mode is false — refund locked minus accumulated late fees minus exit penalty. If strict_mode is true, ExitCircle is not allowed after start (members may only leave via automatic ejection).
     ExitCircle {
         circle_id: u64,
@@ -88,15 +162,33 @@ pub enum ExecuteMsg {
     StartCircle {
         circle_id: u64,
     },
-    /// Deposit contribution for current round. Attach exactly contribution_amount usaf. Late deposits are accepted — late fee is tracked against locked amount.
+    /// Deposit contribution for current round. Late deposits are accepted — late fee is tracked against locked amount.
+    /// Smaller amounts accumulate as a partial deposit until the round's required amount is reached.
+    /// `rounds` > 1 prepays future rounds (attach rounds × contribution_amount on top of any catch-up);
+    /// the prepaid balance covers each round automatically at ProcessPayout / AdvanceRound.
     DepositContribution {
         circle_id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rounds: Option<u32>,
+    },
+    /// Member-only. Opt in or out of auto-debit. Opting in also requires an authz grant
+    /// (SendAuthorization for MsgSend) from the member to this contract.
+    SetAutoDebit {
+        circle_id: u64,
+        enabled: bool,
+    },
+    /// Keeper path, anyone can call until the current round's grace period ends. Pulls the
+    /// outstanding contribution of every opted-in member who has not deposited yet via authz MsgExec.
+    /// A member whose pull fails (revoked grant, low balance) stays missing; the others still go through.
+    AutoDebitContributions {
+        circle_id: u64,
     },
     /// Trigger round payout. Anyone can call when manual_trigger_enabled=false and now>=next_payout_date.
     ProcessPayout {
         circle_id: u64,
     },
     /// Advance to next round without payout. Callable when all deposited and round_in_cycle < min_round_for_distribution (e.g. Total threshold at round 1).
+    /// Not for `Accumulating` circles, which bank every round through ProcessPayout.
     AdvanceRound {
         circle_id: u64,
     },
@@ -108,6 +200,19 @@ pub enum ExecuteMsg {
     CheckAndEject {
         circle_id: u64,
     },
+    /// Waive up to `amount` of a member's accumulated late fees. Applied at once when sent by
+    /// the creator or the platform (arbiter); from any other active member it is a vote, applied
+    /// once a majority of the other non-creator members back the same amount.
+    ForgiveLateFees {
+        circle_id: u64,
+        member: Addr,
+        amount: Uint128,
+    },
+    /// Ejected member only. Re-post the join lock and pay arrears (contribution_amount for each
+    /// round spent ejected) to rejoin a Running or Paused circle.
+    ReinstateMember {
+        circle_id: u64,
+    },
     PauseCircle {
         circle_id: u64,
     },
@@ -149,11 +254,16 @@ pub enum ExecuteMsg {
         circle_id: u64,
     },
     // Private Circle and Member Management
+    /// Creator-only. Reserve a seat for `member_address`. The member is NOT added until they
+    /// call `AcceptInvite` with the `contribution_amount` join lock attached, before the
+    /// reservation expires (`acceptance_window_seconds`, default 7 days).
     AddPrivateMember {
         circle_id: u64,
         member_address: Addr,
         #[serde(skip_serializing_if = "Option::is_none")]
         pseudonym: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        acceptance_window_seconds: Option<u64>,
     },
     UpdateMemberPseudonym {
         circle_id: u64,
@@ -168,10 +278,156 @@ pub enum ExecuteMsg {
         circle_id: u64,
         cycle: u32,
     },
+    /// Settle a Running/Paused circle that cannot finish. Each member's net position
+    /// (contributions paid minus payouts received and late fees) is settled: members paid
+    /// early cover what they owe from their locks, everyone else is credited to
+    /// PENDING_PAYOUTS. Creator or any member, once the roster is below
+    /// `min_members_required`.
+    WindDownCircle {
+        circle_id: u64,
+    },
+    // Creator Succession
+    /// Creator-only. Nominate an active member as the new creator. The handover completes
+    /// when the nominee calls `AcceptCreatorship`.
+    TransferCreatorship {
+        circle_id: u64,
+        new_creator: Addr,
+    },
+    /// Member vote to replace the creator (e.g. lost key). Once a strict majority of active
+    /// non-creator members vote for the same nominee, that nominee may `AcceptCreatorship`.
+    VoteCreatorTransfer {
+        circle_id: u64,
+        nominee: Addr,
+    },
+    /// Nominee-only. Take over as creator. The nominee's join lock moves into
+    /// `creator_lock_amount` and they attach the top-up to the circle's collateral policy;
+    /// the outgoing creator keeps `contribution_amount` as their join lock and is refunded the rest.
+    AcceptCreatorship {
+        circle_id: u64,
+    },
+    // Platform Administration
+    /// Platform-address only. Set which collateral policy kinds creators may choose and
+    /// the minimum creator lock (basis points of contribution_amount). Affects new circles only.
+    SetCollateralConfig {
+        allowed_policies: Vec<CollateralPolicyKind>,
+        min_lock_bps: u64,
+    },
+    /// Platform-address only. Set (or clear with None) the attestation registry that
+    /// circles with `requires_attestation` query. Clearing it blocks those circles' joins and deposits.
+    SetAttestationRegistry {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        registry: Option<Addr>,
+    },
+    /// Platform-address only. Validators circles with `stake_locks` delegate to (chosen by
+    /// circle_id) and the chain unbonding period. Affects circles started afterwards.
+    SetStakingConfig {
+        validators: Vec<String>,
+        unbonding_period_secs: u64,
+    },
+    /// Platform-address only. How many members one guarantor may back at once.
+    SetGuarantorConfig {
+        max_members_backed: u32,
+    },
+    // Guarantors
+    /// Prospective member (before joining). Name the address expected to post part or
+    /// all of the join lock via `GuaranteeMember`; replaces an earlier nomination.
+    NominateGuarantor {
+        circle_id: u64,
+        guarantor: Addr,
+    },
+    /// Nominated guarantor only. Attach up to contribution_amount in usaf as the member's
+    /// lock (the consent); the member then joins with the rest. Refunded through Withdraw
+    /// when the lock is returned, less penalties drawn if the member is ejected.
+    GuaranteeMember {
+        circle_id: u64,
+        member: Addr,
+    },
+    /// Guarantor only, while the member has not joined. Take the posted stake back.
+    RevokeGuarantee {
+        circle_id: u64,
+        member: Addr,
+    },
+    // Renewal
+    /// Creator-only, once the circle is Finalizing or Completed. Create its successor with
+    /// the same parameters (except `overrides`) and invite the current members. The new
+    /// creator lock is taken from the creator's pending payout first; attach the rest.
+    RenewCircle {
+        circle_id: u64,
+        #[serde(default)]
+        overrides: CircleOverrides,
+    },
+    /// Member of a renewed circle (`circle_id` is the finished one). Join its successor
+    /// with the join lock taken from the pending payout here first; attach the rest.
+    JoinRenewal {
+        circle_id: u64,
+    },
+    /// Save a named bundle of CreateCircle parameters, validated like CreateCircle.
+    /// `template_id` updates an existing template (owner only); None creates one.
+    SaveTemplate {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        template_id: Option<u64>,
+        template_name: String,
+        params: CircleTemplateParams,
+    },
+    /// Owner or platform address. Remove a template; circles created from it are unaffected.
+    DeleteTemplate {
+        template_id: u64,
+    },
+    /// CreateCircle with the template's parameters, `overrides` applied on top.
+    /// Attach the creator lock as for CreateCircle.
+    CreateCircleFromTemplate {
+        template_id: u64,
+        #[serde(default)]
+        overrides: CircleOverrides,
+    },
+    /// Accumulating circles, Running. Ask to borrow `amount` from the savings pool, up to
+    /// `max_loan_bps` of the sender's savings; one open loan per member.
+    RequestLoan {
+        circle_id: u64,
+        amount: Uint128,
+    },
+    /// Creator, or one member's vote under `LoanApproval::MemberVote` (disbursed once a
+    /// majority of the other members voted). Sends the principal to the borrower.
+    ApproveLoan {
+        circle_id: u64,
+        borrower: Addr,
+    },
+    /// Repay part or all of the sender's loan (interest first); attach the amount.
+    RepayLoan {
+        circle_id: u64,
+    },
+    /// Permissionless. Credit the circle's accrued staking rewards to lock holders and
+    /// undelegate whatever is due: locks consumed so far, or everything once the circle
+    /// is within the unbonding period of `end_date` or no longer running.
+    SyncLockStake {
+        circle_id: u64,
+    },
+}
+
+/// Parameters `RenewCircle` and `CreateCircleFromTemplate` may change; everything else
+/// is copied from the finished circle or the template.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+pub struct CircleOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circle_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circle_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contribution_amount: Option<Uint128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_members: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_members_required: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_cycles: Option<u32>,
+    /// Neither templates nor finished circles carry a start date; None leaves it to the creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<Timestamp>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum QueryMsg {
     // Circle Queries
     #[returns(CircleResponse)]
@@ -219,6 +475,93 @@ pub enum QueryMsg {
     GetMemberAccumulatedLateFees { circle_id: u64, member: Addr },
     #[returns(DepositRequirementResponse)]
     GetDepositRequirement { circle_id: u64, member: Addr },
+    /// Creator lock that CreateCircle would require for these parameters.
+    #[returns(CreatorLockQuoteResponse)]
+    QuoteCreatorLock {
+        contribution_amount: Uint128,
+        max_members: u32,
+        creator_collateral_policy: Option<CollateralPolicy>,
+    },
+    #[returns(CollateralConfig)]
+    GetCollateralConfig {},
+    #[returns(StakingConfig)]
+    GetStakingConfig {},
+    /// Open guarantees of `guarantor` across circles and the platform limit.
+    #[returns(GuarantorExposureResponse)]
+    GetGuarantorExposure { guarantor: Addr },
+    /// Circle templates by id, optionally only those saved by `owner` (the platform
+    /// address for platform templates).
+    /// Open loans of an accumulating circle with its savings pool.
+    #[returns(LoansResponse)]
+    GetLoans { circle_id: u64 },
+    #[returns(TemplatesResponse)]
+    GetTemplates {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        owner: Option<Addr>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        start_after: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+    /// Dry run of CreateCircle: takes the same fields and reports every validation
+    /// error at once plus the derived terms and projected calendar.
+    #[returns(CreateCirclePreviewResponse)]
+    PreviewCreateCircle {
+        circle_name: String,
+        circle_description: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        circle_image: Option<String>,
+        max_members: u32,
+        min_members_required: u32,
+        invite_only: bool,
+        contribution_amount: Uint128,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        denomination: Option<String>,
+        exit_penalty_percent: u64,
+        late_fee_percent: u64,
+        total_cycles: u32,
+        cycle_duration_days: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cycle_duration_seconds: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_date: Option<Timestamp>,
+        grace_period_hours: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        grace_period_seconds: Option<u64>,
+        auto_start_when_full: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        auto_start_type: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        auto_start_date: Option<Timestamp>,
+        payout_order_type: PayoutOrderType,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payout_order_list: Option<Vec<Addr>>,
+        auto_payout_enabled: bool,
+        manual_trigger_enabled: bool,
+        emergency_stop_enabled: bool,
+        auto_refund_if_min_not_met: bool,
+        strict_mode: bool,
+        visibility: Visibility,
+        show_member_identities: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        distribution_threshold: Option<DistributionThreshold>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        creator_collateral_policy: Option<CollateralPolicy>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        late_fee_policy: Option<LateFeePolicy>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        join_requirements: Option<JoinRequirements>,
+        #[serde(default)]
+        requires_attestation: bool,
+        #[serde(default)]
+        stake_locks: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reserve_premium_bps: Option<u64>,
+        #[serde(default)]
+        post_payout_collateral: bool,
+        #[serde(default)]
+        kind: CircleKind,
+    },
 
     // Event Queries
     #[returns(EventsResponse)]
@@ -229,6 +572,12 @@ pub enum QueryMsg {
     GetCircleStats { circle_id: u64 },
     #[returns(MemberStatsResponse)]
     GetMemberStats { circle_id: u64, member: Addr },
+    /// Payment history of an address across every circle of this contract.
+    #[returns(MemberReputationResponse)]
+    GetMemberReputation { member: Addr },
+    /// Full statement of a member's standing in one circle, read in a single pass.
+    #[returns(MemberPositionResponse)]
+    GetMemberPosition { circle_id: u64, member: Addr },
 
     // Locking and Private Circle Queries
     #[returns(MemberLockedAmountResponse)]
@@ -239,6 +588,34 @@ pub enum QueryMsg {
     GetMemberPseudonym { circle_id: u64, member: Addr },
     #[returns(PrivateMembersResponse)]
     GetPrivateMembers { circle_id: u64 },
+    #[returns(InviteCodesResponse)]
+    GetInviteCodes { circle_id: u64 },
+    #[returns(PrivateReservationsResponse)]
+    GetPrivateReservations { circle_id: u64 },
+    #[returns(WaitlistResponse)]
+    GetWaitlist { circle_id: u64 },
+    /// What WindDownCircle would credit to each member right now.
+    #[returns(WindDownPreviewResponse)]
+    GetWindDownPreview { circle_id: u64 },
+    /// Itemized refund ExitCircle would pay `member` right now.
+    #[returns(ExitQuoteResponse)]
+    SimulateExit { circle_id: u64, member: Addr },
+    /// What ejecting `member` right now would withhold, hold back and credit.
+    #[returns(EjectionQuoteResponse)]
+    SimulateEjection { circle_id: u64, member: Addr },
+    /// What DepositContribution (with `rounds`) would require from `member` right now.
+    #[returns(DepositQuoteResponse)]
+    SimulateDeposit {
+        circle_id: u64,
+        member: Addr,
+        rounds: Option<u32>,
+    },
+    #[returns(AutoDebitMembersResponse)]
+    GetAutoDebitMembers { circle_id: u64 },
+    #[returns(PauseHistoryResponse)]
+    GetPauseHistory { circle_id: u64 },
+    #[returns(CreatorSuccessionResponse)]
+    GetCreatorSuccession { circle_id: u64 },
     #[returns(DistributionCalendarResponse)]
     GetDistributionCalendar { circle_id: u64 },
     #[returns(ArchivedDateResponse)]
@@ -334,6 +711,85 @@ pub struct MemberStatsResponse {
     pub accumulated_late_fees: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MemberReputationResponse {
+    pub member: Addr,
+    /// See `MemberHistory::on_time_ratio_bps`.
+    pub on_time_ratio_bps: u64,
+    pub circles_completed: u32,
+    pub ejections: u32,
+    /// Contributions paid in across all circles.
+    pub total_volume: Uint128,
+    pub history: crate::state::MemberHistory,
+}
+
+/// GetMemberPosition: everything a member has in, out and outstanding in one circle.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MemberPositionResponse {
+    pub member: Addr,
+    /// Lock posted at join (creator: creator_lock_amount) and what is left of it
+    /// after rounds covered from it.
+    pub original_lock: Uint128,
+    pub current_lock: Uint128,
+    pub deposits_made: u32,
+    pub total_deposited: Uint128,
+    /// Rounds since the member's last deposit, up to and including the current one.
+    pub rounds_owed: u32,
+    /// What DepositContribution would ask for right now (after any partial deposit).
+    pub amount_due_now: Uint128,
+    pub prepaid_balance: Uint128,
+    pub partial_deposited: Uint128,
+    pub payouts_received: u32,
+    pub total_received: Uint128,
+    pub pending_payout: Uint128,
+    /// Withheld from the member's payout to cover the contributions they still owe.
+    pub payout_collateral: Uint128,
+    /// Late fees accrued on missed rounds and not yet settled.
+    pub late_fees_accumulated: Uint128,
+    /// Late fees and arrears already paid (PENALTIES records).
+    pub penalties_paid: Uint128,
+    pub missed_count: u32,
+    /// 1-based position in the payout order, if the member is in it.
+    pub payout_slot: Option<u32>,
+    pub is_active: bool,
+    pub blocked_from_cycle: Option<u32>,
+    /// total_received - total_deposited: positive once the member has taken out
+    /// more than they put in.
+    pub net_position: Int128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GuaranteeExposure {
+    pub circle_id: u64,
+    pub member: Addr,
+    pub posted: Uint128,
+    /// Left after penalties drawn at ejection: what the guarantor still stands to get back.
+    pub stake: Uint128,
+    pub joined: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GuarantorExposureResponse {
+    pub guarantor: Addr,
+    pub members_backed: u32,
+    pub max_members_backed: u32,
+    pub total_posted: Uint128,
+    pub total_stake: Uint128,
+    pub guarantees: Vec<GuaranteeExposure>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LoansResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub savings: Option<SavingsPool>,
+    pub loans: Vec<Loan>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TemplatesResponse {
+    pub templates: Vec<CircleTemplate>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct MemberLockedAmountResponse {
     pub amount: Uint128,
@@ -354,6 +810,84 @@ pub struct PrivateMembersResponse {
     pub members: Vec<Addr>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InviteCodesResponse {
+    pub invite_codes: Vec<crate::state::InviteCode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PrivateReservationsResponse {
+    pub reservations: Vec<crate::state::PrivateReservation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CreatorLockQuoteResponse {
+    pub policy: CollateralPolicy,
+    /// Exact amount to attach to CreateCircle.
+    pub creator_lock_amount: Uint128,
+    /// Platform floor for these parameters (min_lock_bps of contribution_amount).
+    pub platform_minimum: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PauseHistoryResponse {
+    pub pauses: Vec<crate::state::PauseRecord>,
+    pub total_paused_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct WindDownPosition {
+    pub member: Addr,
+    /// Deposits made so far (including rounds covered from the lock).
+    pub paid_in: Uint128,
+    /// Payouts received so far.
+    pub received: Uint128,
+    pub late_fees: Uint128,
+    /// Join lock held (creator: creator_lock_amount) plus any post-payout collateral.
+    pub lock: Uint128,
+    /// Taken from the lock to cover what the member owes (received + fees - paid_in).
+    pub seized: Uint128,
+    /// Owed debt the lock could not cover; absorbed by the other members.
+    pub shortfall: Uint128,
+    /// Net owed to the member (paid_in - received - fees), before pool scaling.
+    pub credit: Uint128,
+    /// Total credited to PENDING_PAYOUTS: remaining lock + paid credit + share of any surplus.
+    pub settlement: Uint128,
+    /// Part of the remaining lock credited to the member's guarantor (not in `settlement`).
+    pub guarantor_refund: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct WindDownPreviewResponse {
+    pub positions: Vec<WindDownPosition>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CreatorVote {
+    pub voter: Addr,
+    pub nominee: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CreatorSuccessionResponse {
+    pub creator: Addr,
+    pub nomination: Option<crate::state::CreatorNomination>,
+    pub votes: Vec<CreatorVote>,
+    /// Votes for one nominee needed to nominate them (strict majority of active non-creator members).
+    pub votes_required: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct WaitlistResponse {
+    /// FIFO order: the first entry is promoted next.
+    pub entries: Vec<crate::state::WaitlistEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AutoDebitMembersResponse {
+    pub members: Vec<crate::state::AutoDebitEnrollment>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct DistributionCalendarResponse {
     pub rounds: Vec<CalendarRound>,
@@ -382,8 +916,9 @@ pub struct PendingPayoutResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct AccumulatedLateFeesResponse {
     pub amount: Uint128,
-    /// Number of rounds currently used to calculate (missed_rounds * late_fee_per_round)
+    /// Consecutive missed rounds since the member's last catch-up
     pub missed_rounds: u32,
+    /// Fee the next missed round would add under the circle's late-fee policy
     pub late_fee_per_round: Uint128,
     pub exit_penalty: Uint128,
     pub locked_amount: Uint128,
@@ -398,9 +933,118 @@ pub struct DepositRequirementResponse {
     pub can_deposit: bool,
     pub contribution_amount: Uint128,
     pub late_fee_total: Uint128,
+    /// Unused prepaid balance and how many whole rounds it covers.
+    #[serde(default)]
+    pub prepaid_balance: Uint128,
+    #[serde(default)]
+    pub prepaid_rounds: u32,
+    /// Amount already sent toward the current round; the deposit completes once
+    /// `remaining_amount` reaches zero.
+    #[serde(default)]
+    pub partial_deposited: Uint128,
+    #[serde(default)]
+    pub remaining_amount: Uint128,
+}
+
+/// PreviewCreateCircle: what CreateCircle would do with the given fields.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CreateCirclePreviewResponse {
+    /// Every error CreateCircle would raise, in the order it checks; empty when valid.
+    pub errors: Vec<String>,
+    pub denomination: String,
+    /// Exact amount to attach to CreateCircle.
+    pub creator_lock_amount: Uint128,
+    pub max_missed_payments_allowed: u32,
+    /// Payout per round once the circle is full (contribution_amount × max_members).
+    pub payout_amount: Uint128,
+    /// `start_date` when given, otherwise the current block time.
+    pub projected_start: Timestamp,
+    /// End date CreateCircle would store (None without a `start_date`).
+    pub end_date: Option<Timestamp>,
+    /// Every round of a full circle from `projected_start`; recipients are only known
+    /// for a predefined payout order. Empty when there are errors.
+    pub calendar: Vec<CalendarRound>,
+}
+
+/// Query a circle's `JoinRequirements::attestation_contract` and the platform
+/// attestation registry must answer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationQueryMsg {
+    Attestation { address: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AttestationResponse {
+    pub attested: bool,
+}
+
+/// SimulateExit: same numbers ExitCircle settles with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ExitQuoteResponse {
+    pub member: Addr,
+    /// After start the exit penalty and accumulated late fees are withheld from the lock.
+    pub started: bool,
+    pub locked: Uint128,
+    pub late_fees_withheld: Uint128,
+    pub exit_penalty: Uint128,
+    /// Fees + penalty actually kept by the pool (capped at `locked`).
+    pub penalty_kept: Uint128,
+    pub lock_refund: Uint128,
+    pub prepaid_refund: Uint128,
+    pub partial_refund: Uint128,
+    /// Creator only: lock returned before start, or split among the members after start.
+    pub creator_lock_refund: Uint128,
+    pub creator_lock_forfeited: Uint128,
+    /// Post-payout collateral kept by the pool when leaving after being paid.
+    pub collateral_forfeited: Uint128,
+    /// Part of `lock_refund` credited to the member's guarantor instead.
+    pub guarantor_refund: Uint128,
+    pub total_refund: Uint128,
+}
+
+/// SimulateEjection: same numbers an ejection settles with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct EjectionQuoteResponse {
+    pub member: Addr,
+    /// Whether CheckAndEject would eject the member now.
+    pub eligible: bool,
+    pub missed_count: u32,
+    pub max_missed_payments_allowed: u32,
+    pub locked: Uint128,
+    pub late_fees_withheld: Uint128,
+    pub exit_penalty: Uint128,
+    pub penalty_kept: Uint128,
+    /// Rest of the lock; stays in the circle until DistributeBlockedFunds.
+    pub lock_held: Uint128,
+    /// Returned through PENDING_PAYOUTS (claimable via Withdraw).
+    pub prepaid_credited: Uint128,
+    pub partial_credited: Uint128,
+    pub creator_lock_forfeited: Uint128,
+}
+
+/// SimulateDeposit: same breakdown DepositContribution charges.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DepositQuoteResponse {
+    pub member: Addr,
+    pub cycle: u32,
+    /// Zero when the current round is already paid and the call only prepays.
+    pub contribution_amount: Uint128,
+    pub missed_rounds: u32,
+    pub missed_round_fees: Uint128,
+    /// PerHour policy: fee for depositing inside the grace window.
+    pub grace_late_fee: Uint128,
+    /// Amount drained from the lock covering missed rounds, to be put back.
+    pub lock_refill: Uint128,
+    pub prepay_rounds: u32,
+    pub prepay_amount: Uint128,
+    pub required_amount: Uint128,
+    pub partial_deposited: Uint128,
+    pub remaining_amount: Uint128,
+    pub on_time: bool,
 }
 
-/// Returned by GetContractVersion. api_version 1 = v1, 2 = v2; frontend maps to capabilities.
+/// Returned by GetContractVersion./// Returned by GetContractVersion. api_version 1 = v1, 2 = v2; frontend maps to capabilities.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct ContractVersionResponse {
     pub api_version: u8,
@@ -1,19 +1,32 @@
-use cosmwasm_std::{Addr, Deps, Env, Order, StdResult, Uint128, Timestamp};
+use cosmwasm_std::{Addr, Deps, Env, Int128, Order, StdResult, Uint128, Timestamp};
 use cw_storage_plus::Bound;
 
+use crate::execute::{
+    compute_wind_down, creator_votes_required, current_member_lock, grace_time_late_fee, late_fees_for_misses, max_missed_base_for_circle,
+    check_circle_terms, original_lock_for_member, pause_shift_for_round, quote_creator_lock, quote_deposit, quote_ejection, quote_exit,
+};
 use crate::msg::{
     AccumulatedLateFeesResponse, BalanceResponse, CircleResponse, CirclesResponse, CycleResponse,
     DepositRequirementResponse, DepositsResponse, EventsResponse, MemberStatsResponse, MembersResponse,
     PayoutsResponse, PenaltiesResponse, PendingPayoutResponse, RefundsResponse,
     StatusResponse, CircleStatsResponse, MemberLockedAmountResponse,
     BlockedMembersResponse, MemberPseudonymResponse, PrivateMembersResponse,
-    DistributionCalendarResponse, ArchivedDateResponse, CalendarRound,
+    DistributionCalendarResponse, ArchivedDateResponse, CalendarRound, InviteCodesResponse,
+    PrivateReservationsResponse, WaitlistResponse, CreatorLockQuoteResponse,
+    CreatorSuccessionResponse, CreatorVote, WindDownPreviewResponse, PauseHistoryResponse,
+    AutoDebitMembersResponse, ExitQuoteResponse, EjectionQuoteResponse, DepositQuoteResponse,
+    CreateCirclePreviewResponse, MemberPositionResponse, MemberReputationResponse,
+    GuaranteeExposure, GuarantorExposureResponse, LoansResponse, TemplatesResponse,
 };
 use crate::state::{
-    Circle, CircleStatus, CIRCLES, DEPOSITS, EVENTS, EVENT_COUNTER, PAYOUTS,
+    Circle, CircleKind, CircleStatus, CIRCLES, DEPOSITS, EVENTS, EVENT_COUNTER, PAYOUTS,
     PENALTIES, REFUNDS, MEMBER_LOCKED_AMOUNTS, MEMBER_ACCUMULATED_LATE_FEES,
     MEMBER_LAST_DEPOSITED_CYCLE, MEMBER_MISSED_PAYMENTS, BLOCKED_MEMBERS, MEMBER_PSEUDONYMS,
-    PRIVATE_MEMBER_LIST, PENDING_PAYOUTS, DistributionThreshold,
+    PRIVATE_MEMBER_LIST, PENDING_PAYOUTS, PREPAID_BALANCES, PARTIAL_DEPOSITS, DistributionThreshold, INVITE_CODES,
+    PRIVATE_RESERVATIONS, WAITLIST, CollateralConfig, CollateralPolicy, COLLATERAL_CONFIG,
+    CREATOR_NOMINATIONS, CREATOR_VOTES, PAUSE_HISTORY, AUTO_DEBIT, LateFeePolicy, PayoutOrderType,
+    Visibility, MEMBER_HISTORY, JoinRequirements, StakingConfig, STAKING_CONFIG,
+    PAYOUT_COLLATERAL, GUARANTEES, GUARANTOR_BACKINGS, GUARANTOR_CONFIG, LOANS, TEMPLATES,
 };
 
 pub fn query_circle(deps: Deps, _env: Env, circle_id: u64) -> StdResult<CircleResponse> {
@@ -389,6 +402,142 @@ pub fn query_member_stats(
     })
 }
 
+/// Cross-circle reputation: on-time ratio, completions, ejections and volume.
+pub fn query_member_reputation(
+    deps: Deps,
+    _env: Env,
+    member: Addr,
+) -> StdResult<MemberReputationResponse> {
+    let history = MEMBER_HISTORY
+        .may_load(deps.storage, member.clone())?
+        .unwrap_or_default();
+    Ok(MemberReputationResponse {
+        member,
+        on_time_ratio_bps: history.on_time_ratio_bps(),
+        circles_completed: history.circles_completed,
+        ejections: history.ejections,
+        total_volume: history.total_deposited,
+        history,
+    })
+}
+
+/// One consistent statement of a member's position in a circle (lock, deposits,
+/// payouts, fees, standing), replacing several per-field queries.
+pub fn query_member_position(
+    deps: Deps,
+    env: Env,
+    circle_id: u64,
+    member: Addr,
+) -> StdResult<MemberPositionResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let is_creator = member == circle.creator_address;
+
+    // Same lock figures the execute paths use for ejection and settlement.
+    let in_circle =
+        is_creator || MEMBER_LOCKED_AMOUNTS.has(deps.storage, (circle_id, member.clone()));
+    let original_lock = if in_circle {
+        original_lock_for_member(&circle, &member)
+    } else {
+        Uint128::zero()
+    };
+    let mut current_lock = current_member_lock(deps.storage, circle_id, &member);
+    if is_creator {
+        current_lock += circle.creator_lock_amount;
+    }
+
+    let mut deposits_made = 0u32;
+    let mut total_deposited = Uint128::zero();
+    for item in DEPOSITS
+        .prefix((circle_id, member.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, deposit) = item?;
+        deposits_made += 1;
+        total_deposited += deposit.amount;
+    }
+
+    let mut payouts_received = 0u32;
+    let mut total_received = Uint128::zero();
+    for round in 1..=circle.current_cycle_index {
+        if let Some(payout) = PAYOUTS.may_load(deps.storage, (circle_id, round, member.clone()))? {
+            payouts_received += 1;
+            total_received += payout.amount;
+        }
+    }
+
+    let mut penalties_paid = Uint128::zero();
+    for item in PENALTIES
+        .prefix((circle_id, member.clone()))
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, penalty) = item?;
+        penalties_paid += penalty.amount;
+    }
+
+    let blocked_from_cycle = BLOCKED_MEMBERS.may_load(deps.storage, (circle_id, member.clone()))?;
+    let is_active = circle.members_list.contains(&member);
+
+    let last_deposited = MEMBER_LAST_DEPOSITED_CYCLE
+        .may_load(deps.storage, (circle_id, member.clone()))?
+        .unwrap_or(0);
+    let rounds_owed = if is_active
+        && matches!(circle.circle_status, CircleStatus::Running | CircleStatus::Paused)
+    {
+        circle.current_cycle_index.saturating_sub(last_deposited)
+    } else {
+        0
+    };
+    // Same quote DepositContribution charges from; zero whenever it would refuse.
+    let amount_due_now = quote_deposit(deps.storage, &circle, &member, env.block.time, None)
+        .map(|q| q.remaining_amount)
+        .unwrap_or(Uint128::zero());
+
+    let payout_slot = circle
+        .payout_order_list
+        .as_ref()
+        .and_then(|order| order.iter().position(|m| *m == member))
+        .map(|idx| idx as u32 + 1);
+
+    let signed = |v: Uint128| Int128::new(i128::try_from(v.u128()).unwrap_or(i128::MAX));
+    let net_position = signed(total_received).saturating_sub(signed(total_deposited));
+
+    Ok(MemberPositionResponse {
+        original_lock,
+        current_lock,
+        deposits_made,
+        total_deposited,
+        rounds_owed,
+        amount_due_now,
+        prepaid_balance: PREPAID_BALANCES
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero()),
+        partial_deposited: PARTIAL_DEPOSITS
+            .may_load(deps.storage, (circle_id, member.clone(), circle.current_cycle_index))?
+            .unwrap_or(Uint128::zero()),
+        payouts_received,
+        total_received,
+        pending_payout: PENDING_PAYOUTS
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero()),
+        payout_collateral: PAYOUT_COLLATERAL
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero()),
+        late_fees_accumulated: MEMBER_ACCUMULATED_LATE_FEES
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .unwrap_or(Uint128::zero()),
+        penalties_paid,
+        missed_count: MEMBER_MISSED_PAYMENTS
+            .may_load(deps.storage, (circle_id, member.clone()))?
+            .map(|m| m.missed_count)
+            .unwrap_or(0),
+        payout_slot,
+        is_active,
+        blocked_from_cycle,
+        net_position,
+        member,
+    })
+}
+
 pub fn query_pending_payout(
     deps: Deps,
     _env: Env,
@@ -415,9 +564,6 @@ pub fn query_member_accumulated_late_fees(
         .may_load(deps.storage, (circle_id, member.clone()))?
         .unwrap_or(Uint128::zero());
 
-    let late_fee_per_round = circle
-        .contribution_amount
-        .multiply_ratio(circle.late_fee_percent, 10000u64);
     let exit_penalty = locked_amount.multiply_ratio(circle.exit_penalty_percent, 10000u64);
 
     let missed_info = MEMBER_MISSED_PAYMENTS
@@ -429,17 +575,20 @@ pub fn query_member_accumulated_late_fees(
             last_fee_round: None,
         });
 
-    let total_deduction_so_far = amount + exit_penalty;
-    let rounds_until_ejection = if total_deduction_so_far >= locked_amount || late_fee_per_round.is_zero() {
-        0
-    } else {
-        let remaining = locked_amount - total_deduction_so_far;
-        (remaining.u128() / late_fee_per_round.u128()) as u32
+    // Under the circle's late-fee policy: the fee the next miss would add, and
+    // how many more misses fit before either ejection trigger fires.
+    let missed = missed_info.missed_count;
+    let late_fee_per_round = late_fees_for_misses(&circle, missed + 1)
+        .saturating_sub(late_fees_for_misses(&circle, missed));
+    let max_missed = match circle.max_missed_payments_allowed {
+        0 => max_missed_base_for_circle(&circle),
+        cap => cap.min(max_missed_base_for_circle(&circle)),
     };
+    let rounds_until_ejection = max_missed.saturating_sub(missed);
 
     Ok(AccumulatedLateFeesResponse {
         amount,
-        missed_rounds: missed_info.missed_count,
+        missed_rounds: missed,
         late_fee_per_round,
         exit_penalty,
         locked_amount,
@@ -449,7 +598,7 @@ pub fn query_member_accumulated_late_fees(
 
 pub fn query_deposit_requirement(
     deps: Deps,
-    _env: Env,
+    env: Env,
     circle_id: u64,
     member: Addr,
 ) -> StdResult<DepositRequirementResponse> {
@@ -481,10 +630,8 @@ pub fn query_deposit_requirement(
         .saturating_sub(last_deposited_cycle)
         .saturating_sub(1);
 
-    let late_fee_per_round = circle
-        .contribution_amount
-        .multiply_ratio(circle.late_fee_percent, 10000u64);
-    let late_fee_total = late_fee_per_round * Uint128::from(rounds_missed as u128);
+    let late_fee_total = late_fees_for_misses(&circle, rounds_missed)
+        + grace_time_late_fee(&circle, env.block.time);
     let required_amount = circle
         .contribution_amount
         .checked_add(late_fee_total)
@@ -494,12 +641,190 @@ pub fn query_deposit_requirement(
         && !already_deposited
         && rounds_missed < circle.max_missed_payments_allowed;
 
+    let prepaid_balance = PREPAID_BALANCES
+        .may_load(deps.storage, (circle_id, member.clone()))?
+        .unwrap_or(Uint128::zero());
+    let prepaid_rounds = if circle.contribution_amount.is_zero() {
+        0
+    } else {
+        (prepaid_balance.u128() / circle.contribution_amount.u128()) as u32
+    };
+
+    let partial_deposited = PARTIAL_DEPOSITS
+        .may_load(deps.storage, (circle_id, member.clone(), circle.current_cycle_index))?
+        .unwrap_or(Uint128::zero());
+
     Ok(DepositRequirementResponse {
         required_amount,
         missed_rounds: rounds_missed,
         can_deposit,
         contribution_amount: circle.contribution_amount,
         late_fee_total,
+        prepaid_balance,
+        prepaid_rounds,
+        partial_deposited,
+        remaining_amount: required_amount.saturating_sub(partial_deposited),
+    })
+}
+
+/// Creator lock CreateCircle would require. Errors the same way creation would if the
+/// policy is not platform-approved or falls below the platform minimum.
+pub fn query_quote_creator_lock(
+    deps: Deps,
+    _env: Env,
+    contribution_amount: Uint128,
+    max_members: u32,
+    creator_collateral_policy: Option<CollateralPolicy>,
+) -> StdResult<CreatorLockQuoteResponse> {
+    let policy = creator_collateral_policy.unwrap_or_default();
+    let (creator_lock_amount, platform_minimum) =
+        quote_creator_lock(deps.storage, &policy, contribution_amount, max_members)
+            .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+    Ok(CreatorLockQuoteResponse {
+        policy,
+        creator_lock_amount,
+        platform_minimum,
+    })
+}
+
+/// Dry run of CreateCircle: all validation errors at once, the derived terms and
+/// the projected calendar of a full circle. Uses the same checks as the handler.
+#[allow(clippy::too_many_arguments)]
+pub fn query_preview_create_circle(
+    deps: Deps,
+    env: Env,
+    max_members: u32,
+    min_members_required: u32,
+    contribution_amount: Uint128,
+    denomination: Option<String>,
+    exit_penalty_percent: u64,
+    late_fee_percent: u64,
+    total_cycles: u32,
+    cycle_duration_days: u32,
+    cycle_duration_seconds: Option<u64>,
+    start_date: Option<Timestamp>,
+    grace_period_hours: u32,
+    grace_period_seconds: Option<u64>,
+    payout_order_type: PayoutOrderType,
+    payout_order_list: Option<Vec<Addr>>,
+    visibility: Visibility,
+    distribution_threshold: Option<DistributionThreshold>,
+    creator_collateral_policy: Option<CollateralPolicy>,
+    late_fee_policy: Option<LateFeePolicy>,
+    join_requirements: Option<JoinRequirements>,
+    requires_attestation: bool,
+    stake_locks: bool,
+    reserve_premium_bps: Option<u64>,
+    kind: CircleKind,
+) -> StdResult<CreateCirclePreviewResponse> {
+    let (terms, errors) = check_circle_terms(
+        deps.storage,
+        max_members,
+        min_members_required,
+        total_cycles,
+        contribution_amount,
+        denomination.as_deref(),
+        exit_penalty_percent,
+        late_fee_percent,
+        late_fee_policy,
+        cycle_duration_days,
+        cycle_duration_seconds,
+        grace_period_hours,
+        grace_period_seconds,
+        start_date,
+        &visibility,
+        distribution_threshold,
+        payout_order_list.as_deref(),
+        creator_collateral_policy,
+        join_requirements.as_ref(),
+        requires_attestation,
+        stake_locks,
+        reserve_premium_bps,
+        &kind,
+    );
+    let projected_start = start_date.unwrap_or(env.block.time);
+
+    // Same round layout as `query_distribution_calendar`, for a circle that fills
+    // up to max_members; only a predefined order names the recipients up front.
+    let mut calendar = vec![];
+    if errors.is_empty() {
+        let order = match payout_order_type {
+            PayoutOrderType::PredefinedOrder => payout_order_list.unwrap_or_default(),
+            PayoutOrderType::RandomOrder => vec![],
+        };
+        let min_round_for_distribution = match terms.distribution_threshold {
+            None | Some(DistributionThreshold::Total {}) => max_members,
+            Some(DistributionThreshold::MinMembers { count }) => count,
+        };
+        let mut round_number = 1u32;
+        for cycle in 1..=total_cycles {
+            for slot in 0..max_members {
+                let round_in_cycle = slot + 1;
+                let round_offset_seconds = (round_number - 1) as u64 * terms.cycle_secs;
+                calendar.push(CalendarRound {
+                    round_number,
+                    cycle_number: cycle,
+                    deposit_deadline: projected_start.plus_seconds(round_offset_seconds),
+                    distribution_date: projected_start
+                        .plus_seconds(round_offset_seconds + terms.cycle_secs),
+                    distribution_occurs: round_in_cycle >= min_round_for_distribution,
+                    recipient: order.get(slot as usize).cloned(),
+                });
+                round_number += 1;
+            }
+        }
+    }
+
+    Ok(CreateCirclePreviewResponse {
+        errors: errors.iter().map(|e| e.to_string()).collect(),
+        denomination: terms.denomination,
+        creator_lock_amount: terms.creator_lock,
+        max_missed_payments_allowed: terms.max_missed_payments_allowed,
+        payout_amount: terms.payout_amount,
+        projected_start,
+        end_date: terms.end_date,
+        calendar,
+    })
+}
+
+pub fn query_collateral_config(deps: Deps, _env: Env) -> StdResult<CollateralConfig> {
+    Ok(COLLATERAL_CONFIG.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_staking_config(deps: Deps, _env: Env) -> StdResult<StakingConfig> {
+    Ok(STAKING_CONFIG.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_guarantor_exposure(
+    deps: Deps,
+    _env: Env,
+    guarantor: Addr,
+) -> StdResult<GuarantorExposureResponse> {
+    let keys: Vec<(u64, Addr)> = GUARANTOR_BACKINGS
+        .sub_prefix(guarantor.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    let mut guarantees = vec![];
+    for (circle_id, member) in keys {
+        let g = GUARANTEES.load(deps.storage, (circle_id, member.clone()))?;
+        guarantees.push(GuaranteeExposure {
+            circle_id,
+            member,
+            posted: g.posted,
+            stake: g.stake,
+            joined: g.joined,
+        });
+    }
+    Ok(GuarantorExposureResponse {
+        guarantor,
+        members_backed: guarantees.len() as u32,
+        max_members_backed: GUARANTOR_CONFIG
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .max_members_backed,
+        total_posted: guarantees.iter().map(|g| g.posted).sum(),
+        total_stake: guarantees.iter().map(|g| g.stake).sum(),
+        guarantees,
     })
 }
 
@@ -567,6 +892,169 @@ pub fn query_private_members(
     })
 }
 
+/// Seats reserved via AddPrivateMember and not yet accepted (expired entries included;
+/// compare `expires_at` against the current time).
+pub fn query_private_reservations(
+    deps: Deps,
+    _env: Env,
+    circle_id: u64,
+) -> StdResult<PrivateReservationsResponse> {
+    let reservations: StdResult<Vec<_>> = PRIVATE_RESERVATIONS
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, r)| r))
+        .collect();
+
+    Ok(PrivateReservationsResponse {
+        reservations: reservations?,
+    })
+}
+
+/// Waitlist entries in FIFO order (head is promoted first).
+pub fn query_waitlist(deps: Deps, _env: Env, circle_id: u64) -> StdResult<WaitlistResponse> {
+    let entries = WAITLIST.may_load(deps.storage, circle_id)?.unwrap_or_default();
+    Ok(WaitlistResponse { entries })
+}
+
+/// Pause intervals (oldest first) and the total the schedule has been shifted by.
+pub fn query_pause_history(deps: Deps, _env: Env, circle_id: u64) -> StdResult<PauseHistoryResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    Ok(PauseHistoryResponse {
+        pauses: PAUSE_HISTORY.may_load(deps.storage, circle_id)?.unwrap_or_default(),
+        total_paused_seconds: circle.total_paused_seconds,
+    })
+}
+
+/// Members opted into auto-debit for this circle.
+pub fn query_auto_debit_members(
+    deps: Deps,
+    _env: Env,
+    circle_id: u64,
+) -> StdResult<AutoDebitMembersResponse> {
+    let members = AUTO_DEBIT
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, e)| e))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AutoDebitMembersResponse { members })
+}
+
+/// Settlement WindDownCircle would apply now (same computation, nothing written).
+pub fn query_wind_down_preview(
+    deps: Deps,
+    env: Env,
+    circle_id: u64,
+) -> StdResult<WindDownPreviewResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &circle.denomination)?;
+    // WindDownCircle refunds prepaid balances and partial deposits before settling, so exclude them here too.
+    // Staked locks are undelegated by WindDownCircle and count toward its budget.
+    let staked = circle
+        .lock_stake
+        .as_ref()
+        .map(|stake| match stake.unbonding_matures_at {
+            Some(at) if env.block.time < at => stake.delegated + stake.unbonding,
+            _ => stake.delegated,
+        })
+        .unwrap_or_default();
+    let spendable = (balance.amount + staked)
+        .saturating_sub(circle.total_pending_payouts)
+        .saturating_sub(circle.total_prepaid)
+        .saturating_sub(circle.total_partial_deposits);
+    let positions = compute_wind_down(deps.storage, &circle, spendable)
+        .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+    Ok(WindDownPreviewResponse { positions })
+}
+
+/// Itemized refund ExitCircle would pay `member` now (same quote the handler settles from).
+pub fn query_simulate_exit(
+    deps: Deps,
+    _env: Env,
+    circle_id: u64,
+    member: Addr,
+) -> StdResult<ExitQuoteResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    quote_exit(deps.storage, &circle, &member)
+        .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))
+}
+
+/// What an ejection of `member` now would withhold, hold back and credit.
+pub fn query_simulate_ejection(
+    deps: Deps,
+    _env: Env,
+    circle_id: u64,
+    member: Addr,
+) -> StdResult<EjectionQuoteResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    quote_ejection(deps.storage, &circle, &member)
+        .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))
+}
+
+/// What DepositContribution would require from `member` now. Errors where the
+/// deposit would, including the max-missed rejection.
+pub fn query_simulate_deposit(
+    deps: Deps,
+    env: Env,
+    circle_id: u64,
+    member: Addr,
+    rounds: Option<u32>,
+) -> StdResult<DepositQuoteResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let quote = quote_deposit(deps.storage, &circle, &member, env.block.time, rounds)
+        .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+    if !quote.contribution_amount.is_zero()
+        && quote.missed_rounds >= circle.max_missed_payments_allowed
+    {
+        return Err(cosmwasm_std::StdError::generic_err(
+            crate::error::ContractError::MaxMissedPaymentsExceeded {
+                max: circle.max_missed_payments_allowed,
+            }
+            .to_string(),
+        ));
+    }
+    Ok(quote)
+}
+
+/// Current creator, pending handover nomination and succession votes cast so far.
+pub fn query_creator_succession(
+    deps: Deps,
+    _env: Env,
+    circle_id: u64,
+) -> StdResult<CreatorSuccessionResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let votes: StdResult<Vec<_>> = CREATOR_VOTES
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(voter, nominee)| CreatorVote { voter, nominee }))
+        .collect();
+
+    Ok(CreatorSuccessionResponse {
+        nomination: CREATOR_NOMINATIONS.may_load(deps.storage, circle_id)?,
+        votes: votes?,
+        votes_required: creator_votes_required(&circle),
+        creator: circle.creator_address,
+    })
+}
+
+/// Registered invite codes (hashes only) with remaining uses and expiry.
+pub fn query_invite_codes(
+    deps: Deps,
+    _env: Env,
+    circle_id: u64,
+) -> StdResult<InviteCodesResponse> {
+    let invite_codes: StdResult<Vec<_>> = INVITE_CODES
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, code)| code))
+        .collect();
+
+    Ok(InviteCodesResponse {
+        invite_codes: invite_codes?,
+    })
+}
+
 /// Returns the full calendar with `distribution_occurs` set per round:
 /// None => every round; Total => only last round of each cycle (100% of all members); MinMembers(N) => from round N to end of cycle.
 pub fn query_distribution_calendar(
@@ -617,6 +1105,9 @@ pub fn query_distribution_calendar(
         .filter_map(|r| r.ok())
         .collect();
 
+    // Completed pauses push back every round from the one they interrupted.
+    let pauses = PAUSE_HISTORY.may_load(deps.storage, circle_id)?.unwrap_or_default();
+
     if let Some(payout_order) = &circle.payout_order_list {
         // Round size for the cycle modulo is the number of recipients in the
         // payout order — same value used by `execute` for round_in_cycle —
@@ -629,7 +1120,8 @@ pub fn query_distribution_calendar(
                 let round_in_cycle = ((round_number - 1) % round_size) + 1;
                 let distribution_occurs = round_in_cycle >= min_round_for_distribution;
 
-                let round_offset_seconds = (round_number - 1) as u64 * circle.cycle_duration_secs();
+                let round_offset_seconds = (round_number - 1) as u64 * circle.cycle_duration_secs()
+                    + pause_shift_for_round(&pauses, round_number);
                 let deposit_deadline = Timestamp::from_seconds(
                     start_timestamp.seconds() + round_offset_seconds
                 );
@@ -681,3 +1173,39 @@ pub fn query_archived_date(
         archived_date,
     })
 }
+
+pub fn query_loans(deps: Deps, _env: Env, circle_id: u64) -> StdResult<LoansResponse> {
+    let circle = CIRCLES.load(deps.storage, circle_id)?;
+    let loans = LOANS
+        .prefix(circle_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, loan)| loan))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(LoansResponse {
+        savings: circle.savings,
+        loans,
+    })
+}
+
+pub fn query_templates(
+    deps: Deps,
+    _env: Env,
+    owner: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TemplatesResponse> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let templates = TEMPLATES
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match (item, &owner) {
+            (Ok((_, t)), Some(owner)) => t.owner == *owner,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(_, t)| t))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TemplatesResponse { templates })
+}
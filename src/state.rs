@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Uint128, Timestamp};
+use cosmwasm_std::{Addr, Decimal, Uint128, Timestamp};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -87,9 +87,60 @@ pub struct Circle {
     pub refund_mode: RefundMode,
 
     // Locking and Security Features
-    pub creator_lock_amount: Uint128, // = contribution_amount * 2
+    pub creator_lock_amount: Uint128, // derived from creator_collateral_policy at creation
+    /// Policy the creator lock was quoted under. Circles created before policies existed
+    /// deserialize to the historical `FixedMultiple { multiple_bps: 20000 }` (2x contribution).
+    #[serde(default)]
+    pub creator_collateral_policy: CollateralPolicy,
+    /// Circles created before late-fee policies deserialize to `FlatPerRound`.
+    #[serde(default)]
+    pub late_fee_policy: LateFeePolicy,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub distribution_threshold: Option<DistributionThreshold>,
+    /// Entry rules checked on JoinCircle / AcceptInvite; None admits anyone invited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub join_requirements: Option<JoinRequirements>,
+    /// Joiners and depositors must be attested by the platform's attestation registry.
+    #[serde(default)]
+    pub requires_attestation: bool,
+    /// Creator opted to delegate the locks (usaf only) while the circle runs.
+    #[serde(default)]
+    pub stake_locks: bool,
+    /// Delegation of the locks; set at start when `stake_locks` and a validator is approved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_stake: Option<LockStake>,
+    /// Insurance pool fed by penalties and a premium slice of each payout; covers
+    /// contributions owed by ejected members who already received the pot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<CircleReserve>,
+    /// Paid members must top up their lock to cover the rounds they still owe;
+    /// the top-up is withheld from their payout (see PAYOUT_COLLATERAL).
+    #[serde(default)]
+    pub post_payout_collateral: bool,
+    /// Sum of PAYOUT_COLLATERAL for this circle. Held outside total_amount_locked.
+    #[serde(default)]
+    pub total_payout_collateral: Uint128,
+    /// Circle this one was renewed from (`RenewCircle`), and the one renewing it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predecessor_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor_id: Option<u64>,
+    /// Rotating (one member takes the pot each round) or accumulating (contributions
+    /// pool up, members borrow from the pool, everything is shared out at the end).
+    #[serde(default)]
+    pub kind: CircleKind,
+    /// Pool of an accumulating circle; None for rotating circles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub savings: Option<SavingsPool>,
+    /// Sum of completed pause durations; the schedule has been shifted by this much.
+    #[serde(default)]
+    pub total_paused_seconds: u64,
+    /// Sum of PREPAID_BALANCES for this circle. Held outside total_amount_locked until consumed.
+    #[serde(default)]
+    pub total_prepaid: Uint128,
+    /// Sum of PARTIAL_DEPOSITS for the current round, also held outside total_amount_locked.
+    #[serde(default)]
+    pub total_partial_deposits: Uint128,
 
     // Internal State Parameters
     pub circle_status: CircleStatus,
@@ -133,6 +184,67 @@ pub enum CircleStatus {
     Finalizing,
     Completed,
     Cancelled,
+    /// Settled early via WindDownCircle after the circle could not finish: each member's
+    /// net position was credited to PENDING_PAYOUTS; awaiting withdrawals. Terminal.
+    WoundDown,
+}
+
+/// Savings model of a circle. Circles created before ASCA support deserialize to `Rotating`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CircleKind {
+    /// ROSCA: each ProcessPayout hands the round's pot to one member.
+    #[default]
+    Rotating,
+    /// ASCA: each ProcessPayout adds the round's contributions to `Circle::savings`;
+    /// members borrow from it and the last round shares it out.
+    Accumulating {
+        /// Simple interest per round on a loan's outstanding principal.
+        interest_bps_per_round: u64,
+        /// Largest loan, in basis points of the borrower's savings.
+        max_loan_bps: u64,
+        loan_approval: LoanApproval,
+    },
+}
+
+/// Who approves loans in an accumulating circle.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoanApproval {
+    Creator,
+    /// A majority of the members other than the borrower.
+    MemberVote,
+}
+
+/// Pool of an accumulating circle. `balance` is the cash held in the contract
+/// (outside total_amount_locked); lent principal is out with the borrowers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+pub struct SavingsPool {
+    pub balance: Uint128,
+    /// Sum of MEMBER_SAVINGS: the basis of each member's share at share-out.
+    pub total_savings: Uint128,
+    pub outstanding_principal: Uint128,
+    pub interest_earned: Uint128,
+    /// Loan debt neither repaid nor recovered at share-out.
+    pub written_off: Uint128,
+}
+
+/// A member's loan from the savings pool; one at a time. Disbursed once approved.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Loan {
+    pub borrower: Addr,
+    /// Principal still owed (the requested amount until disbursed).
+    pub principal: Uint128,
+    /// Interest accrued and not yet repaid.
+    pub interest_due: Uint128,
+    /// Round through which interest has been accrued.
+    pub interest_accrued_round: u32,
+    pub requested_at: Timestamp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disbursed_at: Option<Timestamp>,
+    /// Members who voted for it (`LoanApproval::MemberVote`).
+    #[serde(default)]
+    pub approvals: Vec<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -219,11 +331,366 @@ pub struct MemberMissedPayments {
     pub last_fee_round: Option<u32>,
 }
 
+/// Shareable invite code for an invite-only / private circle. Only the SHA-256 digest
+/// (lowercase hex) is stored; the plaintext is shared off-chain, e.g. in an invite link.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InviteCode {
+    pub code_hash: String,
+    /// Redemptions left; the code is removed when this reaches zero.
+    pub uses_remaining: u32,
+    /// Code can no longer be redeemed at or after this instant (UTC epoch instant).
+    pub expires_at: Option<Timestamp>,
+    pub created_at: Timestamp,
+}
+
+/// Seat reserved by the creator via `AddPrivateMember`. The member becomes part of
+/// `members_list` only after accepting with the join lock before `expires_at`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PrivateReservation {
+    pub member: Addr,
+    pub pseudonym: Option<String>,
+    pub reserved_at: Timestamp,
+    /// Acceptance deadline (UTC epoch instant). Expired reservations no longer hold a seat.
+    pub expires_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct WaitlistEntry {
+    pub member: Addr,
+    /// Join deposit pre-locked at JoinWaitlist (zero when the entrant did not pre-lock).
+    pub prelocked: Uint128,
+    pub joined_at: Timestamp,
+}
+
+/// Member opt-in to keeper-triggered auto-debit via authz.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AutoDebitEnrollment {
+    pub member: Addr,
+    pub enabled_at: Timestamp,
+}
+
+/// An authz pull sent by AutoDebitContributions, awaiting its reply. The deposit
+/// is recorded only once the pull succeeds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AutoDebitPull {
+    pub circle_id: u64,
+    pub member: Addr,
+    pub cycle: u32,
+    /// Contribution still owed plus `grace_fee`.
+    pub amount: Uint128,
+    /// PerHour fee for pulling after the deposit deadline (zero when on time).
+    pub grace_fee: Uint128,
+}
+
+/// How late fees grow with missed rounds. Rates are basis points of contribution_amount.
+/// A member's "miss number" counts consecutive misses since their last catch-up deposit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LateFeePolicy {
+    /// late_fee_percent per missed round (the original model).
+    #[default]
+    FlatPerRound,
+    /// The Nth consecutive miss costs late_fee_percent + (N - 1) * step_bps.
+    Escalating { step_bps: u64 },
+    /// bps_per_hour for every started hour past the deposit deadline, up to the end of the
+    /// grace window. A full miss is charged the whole grace window; late_fee_percent is unused.
+    PerHour { bps_per_hour: u64 },
+    /// late_fee_percent per missed round, but never more than cap_bps in total until the
+    /// member catches up.
+    Capped { cap_bps: u64 },
+}
+
+/// How the creator lock (`creator_lock_amount`) is derived at creation.
+/// All factors are basis points (10000 = 100%).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollateralPolicy {
+    /// contribution_amount * multiple_bps / 10000
+    FixedMultiple { multiple_bps: u64 },
+    /// contribution_amount * (base_bps + max_members * per_member_bps) / 10000
+    MemberScaled { base_bps: u64, per_member_bps: u64 },
+    /// Share of the full pot: contribution_amount * max_members * pot_bps / 10000
+    PotPercent { pot_bps: u64 },
+}
+
+impl Default for CollateralPolicy {
+    fn default() -> Self {
+        CollateralPolicy::FixedMultiple { multiple_bps: 20000 }
+    }
+}
+
+impl CollateralPolicy {
+    pub fn kind(&self) -> CollateralPolicyKind {
+        match self {
+            CollateralPolicy::FixedMultiple { .. } => CollateralPolicyKind::FixedMultiple,
+            CollateralPolicy::MemberScaled { .. } => CollateralPolicyKind::MemberScaled,
+            CollateralPolicy::PotPercent { .. } => CollateralPolicyKind::PotPercent,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollateralPolicyKind {
+    FixedMultiple,
+    MemberScaled,
+    PotPercent,
+}
+
+/// Platform-approved collateral options. Set by the platform address via
+/// `SetCollateralConfig`; `Default` applies until it is first set.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CollateralConfig {
+    pub allowed_policies: Vec<CollateralPolicyKind>,
+    /// Floor for any policy's result, in basis points of contribution_amount.
+    pub min_lock_bps: u64,
+}
+
+impl Default for CollateralConfig {
+    fn default() -> Self {
+        CollateralConfig {
+            allowed_policies: vec![
+                CollateralPolicyKind::FixedMultiple,
+                CollateralPolicyKind::MemberScaled,
+                CollateralPolicyKind::PotPercent,
+            ],
+            min_lock_bps: 10000,
+        }
+    }
+}
+
+/// Per-circle reserve (see `Circle::reserve`). `balance` is held in the contract
+/// outside total_amount_locked; what is left at finalization goes to the members
+/// who never missed a round.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+pub struct CircleReserve {
+    /// Basis points of each round's contributions set aside before payout (0 = penalties only).
+    pub premium_bps: u64,
+    pub balance: Uint128,
+    pub premiums_collected: Uint128,
+    pub penalties_received: Uint128,
+    pub shortfalls_covered: Uint128,
+}
+
+/// Platform-approved validators for staked locks. Set by the platform address via
+/// `SetStakingConfig`; with no validators no circle can opt into staking.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct StakingConfig {
+    pub validators: Vec<String>,
+    /// Chain unbonding period; locks are undelegated this long before `end_date`.
+    pub unbonding_period_secs: u64,
+}
+
+impl Default for StakingConfig {
+    fn default() -> Self {
+        StakingConfig {
+            validators: vec![],
+            unbonding_period_secs: 21 * 86400,
+        }
+    }
+}
+
+/// Limits for guarantors. Set by the platform address via `SetGuarantorConfig`;
+/// `Default` applies until it is first set.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GuarantorConfig {
+    /// Open guarantees one address may hold at once, across all circles.
+    pub max_members_backed: u32,
+}
+
+impl Default for GuarantorConfig {
+    fn default() -> Self {
+        GuarantorConfig { max_members_backed: 3 }
+    }
+}
+
+/// A guarantor's share of one member's join lock (see `GuaranteeMember`). The
+/// member attaches `contribution_amount - posted` at join; penalties drawn at
+/// ejection come out of `stake` first, and what is left of it is refunded to the
+/// guarantor before the member whenever the lock is returned.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Guarantee {
+    pub guarantor: Addr,
+    pub posted: Uint128,
+    pub stake: Uint128,
+    pub posted_at: Timestamp,
+    /// Set once the member has joined; until then the guarantor may revoke.
+    pub joined: bool,
+}
+
+/// A circle's delegated locks. `delegated` never exceeds the locks still held
+/// (creator_lock_amount + MEMBER_LOCKED_AMOUNTS); anything consumed is undelegated
+/// and payouts credited against `unbonding` (LOCK_BACKED_PENDING) are held back
+/// from Withdraw until `unbonding_matures_at`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LockStake {
+    pub validator: String,
+    pub delegated: Uint128,
+    pub unbonding: Uint128,
+    pub unbonding_matures_at: Option<Timestamp>,
+    /// `ValidatorStake::reward_index` when this circle's rewards were last settled.
+    pub reward_index: Decimal,
+    pub rewards_distributed: Uint128,
+    /// Locks at delegation time; rewards are split pro rata among those still members.
+    pub holders: Vec<(Addr, Uint128)>,
+}
+
+/// All circles' delegation to one validator. Rewards are withdrawn per validator,
+/// so they are split across circles through `reward_index` (rewards per bonded unit).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+pub struct ValidatorStake {
+    pub bonded: Uint128,
+    pub reward_index: Decimal,
+}
+
+/// One Running → Paused → Running interval. On unpause the schedule (next_payout_date,
+/// end_date, calendar rounds from `round` onward) is shifted by `duration_seconds`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PauseRecord {
+    pub paused_at: Timestamp,
+    /// Round (current_cycle_index) in progress when the pause started.
+    pub round: u32,
+    /// None while the circle is still paused.
+    pub resumed_at: Option<Timestamp>,
+    pub duration_seconds: u64,
+    /// Paused via EmergencyStop rather than PauseCircle.
+    pub emergency: bool,
+}
+
+/// Creatorship handover awaiting `AcceptCreatorship` from the nominee.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CreatorNomination {
+    pub nominee: Addr,
+    /// "creator" (TransferCreatorship) or "vote" (member majority via VoteCreatorTransfer)
+    pub initiated_by: String,
+    pub nominated_at: Timestamp,
+}
+
+/// Entry rules a circle applies to joiners, checked against their `MemberHistory`
+/// and, when `attestation_contract` is set, an external attestation.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct JoinRequirements {
+    #[serde(default)]
+    pub min_circles_completed: u32,
+    /// Most ejections allowed on record; None = no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ejections: Option<u32>,
+    /// Reject anyone ejected within this many seconds (31_536_000 ≈ 12 months).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_ejection_within_seconds: Option<u64>,
+    /// Minimum `MemberHistory::on_time_ratio_bps`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_on_time_ratio_bps: Option<u64>,
+    /// Contract answering `AttestationQueryMsg::Attestation`; the joiner must be attested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_contract: Option<Addr>,
+}
+
+/// Cross-circle payment history of an address, kept at contract level so it
+/// survives the circles themselves (see `GetMemberReputation`).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct MemberHistory {
+    /// Circles the address was a member of when they started.
+    pub circles_joined: u32,
+    pub circles_completed: u32,
+    pub deposits_on_time: u32,
+    pub deposits_late: u32,
+    /// Rounds that closed without the member's deposit.
+    pub missed_rounds: u32,
+    pub ejections: u32,
+    pub payouts_received: u32,
+    pub total_deposited: Uint128,
+    pub total_received: Uint128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_ejected_at: Option<Timestamp>,
+}
+
+impl MemberHistory {
+    /// On-time deposits over all rounds owed (on time + late + missed), in basis
+    /// points; 10000 when nothing was owed yet.
+    pub fn on_time_ratio_bps(&self) -> u64 {
+        let owed = self.deposits_on_time as u64 + self.deposits_late as u64 + self.missed_rounds as u64;
+        (self.deposits_on_time as u64 * 10000)
+            .checked_div(owed)
+            .unwrap_or(10000)
+    }
+}
+
+/// Named bundle of CreateCircle parameters, reusable via `CreateCircleFromTemplate`.
+/// Templates saved by the platform address are the platform's; anyone else's are theirs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CircleTemplate {
+    pub template_id: u64,
+    pub template_name: String,
+    pub owner: Addr,
+    pub params: CircleTemplateParams,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+/// Every CreateCircle field except the circle-specific dates and payout order list;
+/// see `ExecuteMsg::CreateCircle` for their meaning.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CircleTemplateParams {
+    pub circle_name: String,
+    #[serde(default)]
+    pub circle_description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circle_image: Option<String>,
+    pub max_members: u32,
+    pub min_members_required: u32,
+    pub invite_only: bool,
+    pub contribution_amount: Uint128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denomination: Option<String>,
+    pub exit_penalty_percent: u64,
+    pub late_fee_percent: u64,
+    pub total_cycles: u32,
+    pub cycle_duration_days: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle_duration_seconds: Option<u64>,
+    pub grace_period_hours: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grace_period_seconds: Option<u64>,
+    pub auto_start_when_full: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_start_type: Option<String>,
+    pub payout_order_type: PayoutOrderType,
+    pub auto_payout_enabled: bool,
+    pub manual_trigger_enabled: bool,
+    pub emergency_stop_enabled: bool,
+    pub auto_refund_if_min_not_met: bool,
+    pub strict_mode: bool,
+    pub visibility: Visibility,
+    pub show_member_identities: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution_threshold: Option<DistributionThreshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator_collateral_policy: Option<CollateralPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub late_fee_policy: Option<LateFeePolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub join_requirements: Option<JoinRequirements>,
+    #[serde(default)]
+    pub requires_attestation: bool,
+    #[serde(default)]
+    pub stake_locks: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve_premium_bps: Option<u64>,
+    #[serde(default)]
+    pub post_payout_collateral: bool,
+    #[serde(default)]
+    pub kind: CircleKind,
+}
+
 // Platform configuration stored at contract level
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct PlatformConfig {
     pub platform_fee_percent: u64,
     pub platform_address: Addr,
+    /// KYC registry queried (`AttestationQueryMsg`) for circles with `requires_attestation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_registry: Option<Addr>,
 }
 
 // --- INVARIANTS (must hold after every execute) ---
@@ -234,6 +701,9 @@ pub struct PlatformConfig {
 
 // Storage
 pub const PLATFORM_CONFIG: Item<PlatformConfig> = Item::new("platform_config");
+pub const COLLATERAL_CONFIG: Item<CollateralConfig> = Item::new("collateral_config");
+pub const STAKING_CONFIG: Item<StakingConfig> = Item::new("staking_config");
+pub const GUARANTOR_CONFIG: Item<GuarantorConfig> = Item::new("guarantor_config");
 pub const CIRCLE_COUNTER: Item<u64> = Item::new("circle_counter");
 pub const CIRCLES: Map<u64, Circle> = Map::new("circles");
 /// Payouts: (circle_id, cycle, recipient) — supports multiple recipients per cycle (Total threshold)
@@ -252,13 +722,61 @@ pub const MEMBER_LOCKED_AMOUNTS: Map<(u64, Addr), Uint128> = Map::new("member_lo
 pub const MEMBER_ACCUMULATED_LATE_FEES: Map<(u64, Addr), Uint128> = Map::new("member_accum_late_fees");
 /// Pending payout amounts waiting for member to call Withdraw
 pub const PENDING_PAYOUTS: Map<(u64, Addr), Uint128> = Map::new("pending_payouts");
+/// Part of a member's pending payout credited against staked locks still unbonding;
+/// held back from Withdraw and roll-over until the circle's unbonding matures
+pub const LOCK_BACKED_PENDING: Map<(u64, Addr), Uint128> = Map::new("lock_backed_pending");
+/// Part of a paid member's payout withheld to cover the contributions they still owe
+pub const PAYOUT_COLLATERAL: Map<(u64, Addr), Uint128> = Map::new("payout_collateral");
 /// Last cycle index for which member deposited (used for late-fee calculation on catch-up deposit)
 pub const MEMBER_LAST_DEPOSITED_CYCLE: Map<(u64, Addr), u32> = Map::new("member_last_deposited_cycle");
+/// Contributions paid ahead for future rounds; drawn down one contribution_amount per round
+pub const PREPAID_BALANCES: Map<(u64, Addr), Uint128> = Map::new("prepaid_balances");
+/// Deposits still short of the round's required amount: (circle_id, member, cycle)
+pub const PARTIAL_DEPOSITS: Map<(u64, Addr, u32), Uint128> = Map::new("partial_deposits");
 pub const BLOCKED_MEMBERS: Map<(u64, Addr), u32> = Map::new("blocked_members");
 pub const MEMBER_PSEUDONYMS: Map<(u64, Addr), String> = Map::new("member_pseudonyms");
 pub const PRIVATE_MEMBER_LIST: Map<u64, Vec<Addr>> = Map::new("private_member_list");
+/// Pending seat reservations from AddPrivateMember: (circle_id, member)
+pub const PRIVATE_RESERVATIONS: Map<(u64, Addr), PrivateReservation> = Map::new("private_reservations");
+/// FIFO waitlist for circles with no free seat. Pre-locked funds are NOT part of total_amount_locked
+/// until the entrant is promoted.
+pub const WAITLIST: Map<u64, Vec<WaitlistEntry>> = Map::new("waitlist");
+/// Late-fee forgiveness votes: (circle_id, member forgiven, voter) -> amount voted for.
+/// Cleared for the member once a forgiveness is applied.
+pub const FORGIVENESS_VOTES: Map<(u64, Addr, Addr), Uint128> = Map::new("forgiveness_votes");
+/// Auto-debit opt-ins: (circle_id, member). Removed when the member opts out.
+pub const AUTO_DEBIT: Map<(u64, Addr), AutoDebitEnrollment> = Map::new("auto_debit");
+/// In-flight auto-debit pulls by submessage reply id. Removed by the reply.
+pub const AUTO_DEBIT_PULLS: Map<u64, AutoDebitPull> = Map::new("auto_debit_pulls");
+pub const AUTO_DEBIT_PULL_COUNTER: Item<u64> = Item::new("auto_debit_pull_counter");
+/// Pause intervals per circle, oldest first.
+pub const PAUSE_HISTORY: Map<u64, Vec<PauseRecord>> = Map::new("pause_history");
+/// Pending creatorship handover per circle (at most one).
+pub const CREATOR_NOMINATIONS: Map<u64, CreatorNomination> = Map::new("creator_nominations");
+/// Succession votes: (circle_id, voter) -> nominee. Cleared whenever creatorship changes.
+pub const CREATOR_VOTES: Map<(u64, Addr), Addr> = Map::new("creator_votes");
+/// Invite codes: (circle_id, sha256 hex of plaintext code)
+pub const INVITE_CODES: Map<(u64, String), InviteCode> = Map::new("invite_codes");
 /// Platform-funded creator reward credited per circle. Set on first
 /// successful `DepositCreatorReward` call; presence means the reward has
 /// already been credited and a second call must be rejected (idempotency
 /// guard against the server retrying broadcasts at the Finalizing edge).
 pub const CREATOR_REWARDS_CREDITED: Map<u64, Uint128> = Map::new("creator_rewards_credited");
+/// Reputation record per address, across every circle of this contract.
+pub const MEMBER_HISTORY: Map<Addr, MemberHistory> = Map::new("member_history");
+/// Guarantor nominated by a prospective member: (circle_id, member) -> guarantor.
+/// Cleared when the guarantor posts.
+pub const GUARANTOR_NOMINATIONS: Map<(u64, Addr), Addr> = Map::new("guarantor_nominations");
+/// Open guarantees: (circle_id, member). Removed when the lock is settled.
+pub const GUARANTEES: Map<(u64, Addr), Guarantee> = Map::new("guarantees");
+/// Index of open guarantees per guarantor: (guarantor, circle_id, member) -> posted.
+pub const GUARANTOR_BACKINGS: Map<(Addr, u64, Addr), Uint128> = Map::new("guarantor_backings");
+/// Contract-wide delegation per validator address (see `ValidatorStake`).
+pub const VALIDATOR_STAKES: Map<&str, ValidatorStake> = Map::new("validator_stakes");
+/// Savings of each member of an accumulating circle: (circle_id, member) -> contributed.
+pub const MEMBER_SAVINGS: Map<(u64, Addr), Uint128> = Map::new("member_savings");
+/// Open loans of accumulating circles: (circle_id, borrower). Removed once repaid.
+pub const LOANS: Map<(u64, Addr), Loan> = Map::new("loans");
+pub const TEMPLATE_COUNTER: Item<u64> = Item::new("template_counter");
+/// Circle templates by id (see `CircleTemplate`).
+pub const TEMPLATES: Map<u64, CircleTemplate> = Map::new("templates");